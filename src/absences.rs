@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::theme::normalize_hex;
+
+pub const VACATION: &str = "vacation";
+pub const SICK: &str = "sick";
+
+/// A kind of absence a day can be marked with. Vacation and sick are always
+/// present; every type, built-ins included, is stored in `absence_types` in
+/// the config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbsenceType {
+    pub key: String,
+    pub name: String,
+    pub glyph: char,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub target_hours: f64,
+    #[serde(default)]
+    pub credit_hours: f64,
+    #[serde(default)]
+    pub credit_as_worked: bool,
+}
+
+impl AbsenceType {
+    pub fn vacation(target_hours: f64, credit_hours: f64, credit_as_worked: bool) -> Self {
+        Self {
+            key: VACATION.to_string(),
+            name: "Vacation".to_string(),
+            glyph: 'V',
+            color: None,
+            target_hours,
+            credit_hours,
+            credit_as_worked,
+        }
+    }
+
+    pub fn sick(target_hours: f64, credit_hours: f64, credit_as_worked: bool) -> Self {
+        Self {
+            key: SICK.to_string(),
+            name: "Sick".to_string(),
+            glyph: 'S',
+            color: None,
+            target_hours,
+            credit_hours,
+            credit_as_worked,
+        }
+    }
+
+    /// Hours credited as worked on a full day of this absence.
+    pub fn credited_hours(&self) -> f64 {
+        if self.credit_as_worked {
+            self.credit_hours
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Checks an absence type against the ones already accepted and normalizes
+/// its key, glyph and colour. Keys and glyphs must be unique, and `H` stays
+/// reserved for holidays.
+pub fn validate_absence_type(
+    absence: &AbsenceType,
+    accepted: &[AbsenceType],
+) -> Result<AbsenceType, String> {
+    let key = absence.key.trim().to_lowercase();
+    if key.is_empty() {
+        return Err("Absence type keys cannot be empty.".to_string());
+    }
+    if accepted.iter().any(|other| other.key == key) {
+        return Err(format!("Absence type key '{key}' is already used."));
+    }
+    let name = absence.name.trim().to_string();
+    if name.is_empty() {
+        return Err(format!("Absence type '{key}' needs a name."));
+    }
+    if !absence.glyph.is_ascii_alphabetic() {
+        return Err(format!("Absence type '{key}' needs a letter as its glyph."));
+    }
+    let glyph = absence.glyph.to_ascii_uppercase();
+    if glyph == 'H' || accepted.iter().any(|other| other.glyph == glyph) {
+        return Err(format!("Absence glyph '{glyph}' is already used."));
+    }
+    if absence.target_hours < 0.0 || absence.credit_hours < 0.0 {
+        return Err(format!("Absence type '{key}' cannot have negative hours."));
+    }
+    let color = absence.color.as_deref().map(normalize_hex).transpose()?;
+
+    Ok(AbsenceType {
+        key,
+        name,
+        glyph,
+        color,
+        ..absence.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(key: &str, glyph: char) -> AbsenceType {
+        AbsenceType {
+            key: key.to_string(),
+            name: "Training".to_string(),
+            glyph,
+            color: None,
+            target_hours: 8.0,
+            credit_hours: 8.0,
+            credit_as_worked: true,
+        }
+    }
+
+    #[test]
+    fn validate_normalizes_custom_types() {
+        let validated = validate_absence_type(
+            &AbsenceType {
+                color: Some("#AABBCC".to_string()),
+                ..custom(" Training ", 't')
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(validated.key, "training");
+        assert_eq!(validated.glyph, 'T');
+        assert_eq!(validated.color.as_deref(), Some("#aabbcc"));
+    }
+
+    #[test]
+    fn validate_rejects_clashing_keys_and_glyphs() {
+        let accepted = [
+            AbsenceType::vacation(8.0, 8.0, true),
+            AbsenceType::sick(8.0, 8.0, true),
+            custom("training", 'T'),
+        ];
+        assert!(validate_absence_type(&custom("sick", 'X'), &accepted).is_err());
+        assert!(validate_absence_type(&custom("comp", 'h'), &accepted).is_err());
+        assert!(validate_absence_type(&custom("comp", 'T'), &accepted).is_err());
+        assert!(validate_absence_type(&custom("comp", 'v'), &accepted).is_err());
+        assert!(validate_absence_type(&custom("comp", '1'), &accepted).is_err());
+        assert!(validate_absence_type(&custom("comp", 'C'), &accepted).is_ok());
+    }
+
+    #[test]
+    fn credited_hours_follow_the_credit_toggle() {
+        assert_eq!(AbsenceType::vacation(8.0, 7.6, true).credited_hours(), 7.6);
+        assert_eq!(AbsenceType::sick(8.0, 7.6, false).credited_hours(), 0.0);
+    }
+}
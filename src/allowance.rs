@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Yearly vacation allowance in days, with optional carry-over of unused
+/// days into the next year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VacationAllowance {
+    pub days_per_year: f64,
+    /// Allowance for specific years, e.g. after a contract change.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub years: BTreeMap<i32, f64>,
+    /// Most days carried into the next year. Unset carries everything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carry_over_max_days: Option<f64>,
+    /// "MM-DD" after which carried days not yet taken lapse.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carry_over_expires: Option<String>,
+    /// First tracked year; nothing is carried into it except `opening_days`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_year: Option<i32>,
+    #[serde(default)]
+    pub opening_days: f64,
+}
+
+/// Vacation balance for one year, in days.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VacationBalance {
+    pub year: i32,
+    pub allowance: f64,
+    pub carried_over: f64,
+    pub expired: f64,
+    pub used: f64,
+    pub planned: f64,
+}
+
+impl VacationBalance {
+    pub fn remaining(&self) -> f64 {
+        self.allowance + self.carried_over - self.expired - self.used - self.planned
+    }
+}
+
+impl VacationAllowance {
+    pub fn allowance_for(&self, year: i32) -> f64 {
+        self.years.get(&year).copied().unwrap_or(self.days_per_year)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.days_per_year < 0.0 || self.years.values().any(|days| *days < 0.0) {
+            return Err("Vacation allowance cannot be negative.".to_string());
+        }
+        if let Some(expires) = &self.carry_over_expires
+            && parse_month_day(expires, 2024).is_none()
+        {
+            return Err(format!("Invalid carry-over expiry '{expires}'. Use MM-DD."));
+        }
+        Ok(())
+    }
+
+    /// Balance for `year`. `days` maps each vacation day to the share of a
+    /// working day it takes (1.0 for a full day). Days after `today` count
+    /// as planned, and carried days only lapse once the expiry has passed.
+    /// Returns `None` for years before `start_year`.
+    pub fn balance(
+        &self,
+        year: i32,
+        today: NaiveDate,
+        days: &BTreeMap<NaiveDate, f64>,
+    ) -> Option<VacationBalance> {
+        let first_year = self
+            .start_year
+            .or_else(|| days.keys().next().map(|day| day.year()))
+            .unwrap_or(year)
+            .min(year);
+        if self.start_year.is_some_and(|start| year < start) {
+            return None;
+        }
+
+        let mut carried_over = self.opening_days;
+        for previous in first_year..year {
+            let taken: f64 = days_in_year(days, previous).map(|(_, share)| share).sum();
+            let expired = self.expired_days(previous, carried_over, days, None);
+            let left = self.allowance_for(previous) + carried_over - expired - taken;
+            carried_over = match self.carry_over_max_days {
+                Some(max) => left.clamp(0.0, max),
+                None => left.max(0.0),
+            };
+        }
+
+        let (used, planned) =
+            days_in_year(days, year).fold((0.0, 0.0), |(used, planned), (day, share)| {
+                if day <= today {
+                    (used + share, planned)
+                } else {
+                    (used, planned + share)
+                }
+            });
+        Some(VacationBalance {
+            year,
+            allowance: self.allowance_for(year),
+            carried_over,
+            expired: self.expired_days(year, carried_over, days, Some(today)),
+            used,
+            planned,
+        })
+    }
+
+    /// Carried days left untaken at the expiry date. With `today` set,
+    /// nothing has lapsed before the expiry date passes.
+    fn expired_days(
+        &self,
+        year: i32,
+        carried_over: f64,
+        days: &BTreeMap<NaiveDate, f64>,
+        today: Option<NaiveDate>,
+    ) -> f64 {
+        let Some(expires) = self
+            .carry_over_expires
+            .as_deref()
+            .and_then(|value| parse_month_day(value, year))
+        else {
+            return 0.0;
+        };
+        if today.is_some_and(|today| today <= expires) {
+            return 0.0;
+        }
+        let taken_before: f64 = days_in_year(days, year)
+            .filter(|(day, _)| *day <= expires)
+            .map(|(_, share)| share)
+            .sum();
+        (carried_over - taken_before).max(0.0)
+    }
+}
+
+fn days_in_year(
+    days: &BTreeMap<NaiveDate, f64>,
+    year: i32,
+) -> impl Iterator<Item = (NaiveDate, f64)> + '_ {
+    days.iter()
+        .filter(move |(day, _)| day.year() == year)
+        .map(|(day, share)| (*day, *share))
+}
+
+fn parse_month_day(value: &str, year: i32) -> Option<NaiveDate> {
+    let (month, day) = value.trim().split_once('-')?;
+    NaiveDate::from_ymd_opt(year, month.parse().ok()?, day.parse().ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn allowance() -> VacationAllowance {
+        VacationAllowance {
+            days_per_year: 25.0,
+            years: BTreeMap::new(),
+            carry_over_max_days: Some(5.0),
+            carry_over_expires: None,
+            start_year: Some(2025),
+            opening_days: 0.0,
+        }
+    }
+
+    fn full_days(days: &[NaiveDate]) -> BTreeMap<NaiveDate, f64> {
+        days.iter().map(|day| (*day, 1.0)).collect()
+    }
+
+    #[test]
+    fn balance_splits_used_and_planned_days() {
+        let mut days = full_days(&[date(2026, 3, 2), date(2026, 3, 3), date(2026, 12, 24)]);
+        days.insert(date(2026, 3, 4), 0.5);
+
+        let balance = allowance()
+            .balance(2026, date(2026, 10, 18), &days)
+            .unwrap();
+
+        assert_eq!(balance.used, 2.5);
+        assert_eq!(balance.planned, 1.0);
+        assert_eq!(balance.carried_over, 5.0);
+        assert_eq!(balance.remaining(), 26.5);
+    }
+
+    #[test]
+    fn carry_over_is_capped_and_lapses_after_expiry() {
+        let config = VacationAllowance {
+            carry_over_expires: Some("03-31".to_string()),
+            ..allowance()
+        };
+        // 20 days taken in 2025 leaves 5 to carry; 2 of them are taken in time.
+        let mut taken: Vec<NaiveDate> = date(2025, 6, 2).iter_days().take(20).collect();
+        taken.extend([date(2026, 3, 2), date(2026, 3, 3), date(2026, 4, 1)]);
+        let days = full_days(&taken);
+
+        let before = config.balance(2026, date(2026, 3, 15), &days).unwrap();
+        assert_eq!(before.carried_over, 5.0);
+        assert_eq!(before.expired, 0.0);
+
+        let after = config.balance(2026, date(2026, 10, 18), &days).unwrap();
+        assert_eq!(after.expired, 3.0);
+        assert_eq!(after.remaining(), 25.0 + 5.0 - 3.0 - 3.0);
+    }
+
+    #[test]
+    fn years_before_start_have_no_balance() {
+        assert_eq!(
+            allowance().balance(2024, date(2026, 10, 18), &BTreeMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_expiry() {
+        let config = VacationAllowance {
+            carry_over_expires: Some("31-03".to_string()),
+            ..allowance()
+        };
+        assert!(config.validate().is_err());
+    }
+}
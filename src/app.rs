@@ -1,21 +1,39 @@
-use chrono::{DateTime, Datelike, Local, NaiveDate};
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveTime};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::widgets::ListState;
 use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
-use crate::dates::{DateRange, parse_date};
-use crate::grouping::{GroupedEntry, GroupedProject, group_entries};
-use crate::models::{Client as TogglClientModel, Project, TimeEntry, Workspace};
-use crate::rollups::{DailyTotal, PeriodRollup, Rollups, WeekStart, build_rollups};
-use crate::rounding::{RoundingConfig, RoundingMode};
-use crate::storage::{self, CacheFile, CachedData, QuotaFile, RollupPreferences};
+use crate::absences::{AbsenceType, SICK, VACATION};
+use crate::allowance::{VacationAllowance, VacationBalance};
+use crate::balance::{
+    BalanceContext, HolidayHours, hours_from_seconds, normalize_delta, period_scope_adjustment,
+};
+use crate::comparison::{PeriodComparison, PeriodFigures, PeriodStep, TREND_PERIODS, TrendPoint};
+use crate::dates::{DateRange, Timezone, WeekStart, parse_range_expression, start_of_week};
+use crate::gaps::{DayTarget, GapReport, GapThresholds, find_gaps};
+use crate::grouping::{GroupedEntry, GroupedProject, group_entries, range_totals};
+use crate::holidays::{self, COUNTRIES};
+use crate::models::{Client as TogglClientModel, Me, Project, TimeEntry, Workspace};
+use crate::notifications::{self, NotificationTracker, WeekProgress};
+use crate::patterns::{GapDay, WorkPattern, build_work_pattern};
+use crate::refetch::{DayCoverage, FRESH_FOR_HOURS, RefetchBatch, plan_batches};
+use crate::rollups::{
+    BillingCycle, Breakdown, CycleRule, DailyTotal, DayWindow, PeriodCalendar, PeriodRollup,
+    Rollups, build_cycle_rollups, build_range_rollup, build_rollups, client_breakdown,
+};
+use crate::rounding::{RoundingConfig, RoundingMode, RoundingPolicy, RoundingRules, RoundingScope};
+use crate::schedule::{AbsenceAmount, ScheduleChange, WorkSchedule};
+use crate::storage::{
+    self, CacheFile, CachedData, QuotaFile, RollupPreferences, SpecialDays, TimeBank, UiState,
+};
 use crate::theme::{
     CustomTheme, ThemeSelection, next_theme_selection, previous_theme_selection,
     theme_selection_label,
 };
 use crate::toggl::{TogglClient, TogglError};
 use crate::update::{self, UpdateInfo};
+use crate::validation::{EntryIssue, validate_entries};
 use arboard::Clipboard;
 
 const CALL_LIMIT: u32 = 30;
@@ -26,10 +44,13 @@ pub enum Mode {
     Dashboard,
     Rollups,
     RefetchConfirm,
+    AbsenceInput,
     Login,
     WorkspaceSelect,
     DateInput(DateInputMode),
     Settings,
+    Gaps,
+    Issues,
     Error,
 }
 
@@ -43,7 +64,36 @@ pub enum DashboardFocus {
 pub enum RollupView {
     Weekly,
     Monthly,
+    /// Quarters of the fiscal year.
+    Quarterly,
     Yearly,
+    /// Periods of the selected billing cycle.
+    Cycle,
+}
+
+impl RollupView {
+    /// Name used to remember the view between sessions.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Quarterly => "quarterly",
+            Self::Yearly => "yearly",
+            Self::Cycle => "cycle",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        [
+            Self::Weekly,
+            Self::Monthly,
+            Self::Quarterly,
+            Self::Yearly,
+            Self::Cycle,
+        ]
+        .into_iter()
+        .find(|view| view.key() == key)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -52,6 +102,20 @@ pub enum RollupFocus {
     Days,
 }
 
+/// What the pane below the rollup summary shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupPane {
+    Calendar,
+    Comparison,
+    Patterns,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupBreakdown {
+    Projects,
+    Clients,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DateInputMode {
     Range,
@@ -88,17 +152,32 @@ pub enum SettingsItem {
     Theme,
     ThemeStudio,
     TargetHours,
-    VacationTargetHours,
-    VacationCreditHours,
-    SickTargetHours,
-    SickCreditHours,
-    CreditVacationDays,
-    CreditSickDays,
+    /// Settings of the absence type at this index in `SpecialDays::types`.
+    CreditAbsenceDays(usize),
+    AbsenceTargetHours(usize),
+    AbsenceCreditHours(usize),
+    HolidayCountry,
+    HolidayImport,
+    HolidayTargetHours,
+    HolidayCreditHours,
+    TimeBankStart,
+    TimeBankOpening,
+    Notifications,
+    EndOfDaySummary,
     RollupsIncludeWeekends,
+    RememberWeekendToggle,
     RollupsWeekStart,
+    FiscalYearStart,
+    GapLowDayPercent,
+    GapMinMinutes,
     TimeRoundingToggle,
     RoundingIncrement,
     RoundingMode,
+    RoundingScope,
+    Timezone,
+    SplitAtMidnight,
+    RestoreDateRange,
+    MaxEntryHours,
     TogglToken,
 }
 
@@ -107,6 +186,11 @@ struct RefetchPlan {
     start: NaiveDate,
     end: NaiveDate,
     scope_label: String,
+    /// In fetch order, one API call each.
+    batches: Vec<RefetchBatch>,
+    fresh_days: usize,
+    /// Future days, and weekends while rollups hide them.
+    skipped_days: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -131,35 +215,72 @@ pub struct App {
     pub client_names: HashMap<u64, String>,
     pub grouped: Vec<GroupedProject>,
     pub total_hours: f64,
+    pub raw_hours: f64,
     pub project_state: ListState,
     pub entry_state: ListState,
     pub rollups: Rollups,
     pub rollup_view: RollupView,
     pub rollup_focus: RollupFocus,
+    pub rollup_breakdown: RollupBreakdown,
+    pub rollup_pane: RollupPane,
+    rollup_comparison: Option<(RollupView, PeriodComparison)>,
+    rollup_pattern: Option<WorkPattern>,
     pub rollup_week_state: ListState,
     pub rollup_month_state: ListState,
+    pub rollup_quarter_state: ListState,
     pub rollup_year_state: ListState,
+    pub rollup_cycle_state: ListState,
     pub rollup_day_state: ListState,
     pub rollups_include_weekends: bool,
     pub rollups_week_start: WeekStart,
+    /// Month (1-12) the fiscal year starts in.
+    pub rollups_fiscal_year_start: u32,
+    remember_weekend_toggle: bool,
+    restore_date_range: bool,
+    /// Screen to show once the first load finishes.
+    start_mode: Option<Mode>,
     rollup_year_cursor: i32,
+    billing_cycles: Vec<BillingCycle>,
+    billing_cycle_index: usize,
     rollup_fetched_days: HashSet<NaiveDate>,
-    vacation_days: HashSet<NaiveDate>,
-    sick_days: HashSet<NaiveDate>,
-    vacation_day_target_hours: f64,
-    vacation_day_credit_hours: f64,
-    sick_day_target_hours: f64,
-    sick_day_credit_hours: f64,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
+    gap_thresholds: GapThresholds,
+    gap_report: Option<GapReport>,
+    pub gap_state: ListState,
+    gap_return_mode: Mode,
+    max_entry_hours: f64,
+    entry_issues: Vec<EntryIssue>,
+    pub issue_state: ListState,
+    time_bank: Option<TimeBank>,
+    time_bank_daily: Vec<DailyTotal>,
+    time_bank_counted_days: HashSet<NaiveDate>,
+    time_bank_missing_days: usize,
+    notifications_enabled: bool,
+    end_of_day_summary: Option<NaiveTime>,
+    notification_tracker: NotificationTracker,
+    last_notification_check: Option<Instant>,
+    settings_notifications_draft: bool,
+    special_days: SpecialDays,
+    vacation_allowance: Option<VacationAllowance>,
+    absence_input: String,
+    absence_input_day: Option<NaiveDate>,
+    holiday_country: Option<String>,
+    imported_holiday_count: usize,
+    holiday_target_hours: f64,
+    holiday_credit_hours: f64,
     pub last_refresh: Option<DateTime<Local>>,
     pub show_help: bool,
     pub theme: ThemeSelection,
     pub target_hours: f64,
+    schedule_changes: Vec<ScheduleChange>,
     pub update_info: Option<UpdateInfo>,
     pub update_installable: bool,
     show_update_popup: bool,
     pub rounding: Option<RoundingConfig>,
+    rounding_scope: RoundingScope,
+    rounding_rules: RoundingRules,
+    pub timezone: Timezone,
+    split_at_midnight: bool,
+    timezone_override: Option<Timezone>,
     token_hash: Option<String>,
     cache: Option<CacheFile>,
     quota: QuotaFile,
@@ -181,10 +302,15 @@ pub struct App {
     settings_rounding_draft_enabled: bool,
     settings_theme_draft: ThemeSelection,
     custom_themes: Vec<CustomTheme>,
-    settings_credit_vacation_days_draft: bool,
-    settings_credit_sick_days_draft: bool,
+    settings_credit_absence_days_draft: bool,
+    settings_split_at_midnight_draft: bool,
+    settings_rounding_scope_draft: RoundingScope,
+    settings_holiday_country_draft: Option<String>,
     settings_rollups_include_weekends_draft: bool,
     settings_rollups_week_start_draft: WeekStart,
+    settings_fiscal_year_start_draft: u32,
+    settings_remember_weekend_toggle_draft: bool,
+    settings_restore_date_range_draft: bool,
     refetch_plan: Option<RefetchPlan>,
     status_created_at: Option<Instant>,
     last_status_snapshot: Option<String>,
@@ -193,7 +319,7 @@ pub struct App {
 }
 
 impl App {
-    pub fn new(date_range: DateRange, force_login: bool, needs_update_check: bool) -> Self {
+    pub fn new(force_login: bool, needs_update_check: bool) -> Self {
         let token = if force_login {
             None
         } else {
@@ -207,23 +333,68 @@ impl App {
         let theme_settings = storage::read_theme_settings();
         let theme = theme_settings.active_theme.clone();
         let target_hours = storage::read_target_hours().unwrap_or(8.0);
+        let schedule_changes = storage::read_work_schedule();
         let rounding = storage::read_rounding();
+        let rounding_scope = storage::read_rounding_scope();
+        let rounding_rules = storage::read_rounding_rules();
         let rollup_preferences = storage::read_rollup_preferences();
-        let special_days = storage::read_special_days();
-        let vacation_day_target_hours =
-            storage::read_vacation_day_target_hours().unwrap_or(target_hours);
-        let vacation_day_credit_hours =
-            storage::read_vacation_day_credit_hours().unwrap_or(vacation_day_target_hours);
-        let sick_day_target_hours = storage::read_sick_day_target_hours().unwrap_or(target_hours);
-        let sick_day_credit_hours =
-            storage::read_sick_day_credit_hours().unwrap_or(sick_day_target_hours);
-        let credit_vacation_days_as_worked = storage::read_credit_vacation_days_as_worked();
-        let credit_sick_days_as_worked = storage::read_credit_sick_days_as_worked();
+        let mut special_days = storage::read_special_days();
+        let (absence_types, absence_type_errors) = storage::read_absence_types();
+        special_days.types = absence_types;
+        let absence_types_status = (!absence_type_errors.is_empty())
+            .then(|| format!("Ignoring absence types: {}", absence_type_errors.join(" ")));
+        let (vacation_allowance, allowance_status) = match storage::read_vacation_allowance() {
+            Ok(allowance) => (allowance, None),
+            Err(err) => (None, Some(format!("Ignoring vacation allowance: {err}"))),
+        };
+        let (billing_cycles, billing_cycle_errors) = storage::read_billing_cycles();
+        let billing_cycles_status = (!billing_cycle_errors.is_empty()).then(|| {
+            format!(
+                "Ignoring billing cycles: {}",
+                billing_cycle_errors.join(" ")
+            )
+        });
+        let startup_problems: Vec<String> = [
+            storage::config_error(),
+            absence_types_status,
+            allowance_status,
+            billing_cycles_status,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let startup_status = (!startup_problems.is_empty()).then(|| startup_problems.join(" "));
+        let holiday_target_hours = storage::read_holiday_target_hours().unwrap_or(0.0);
+        let holiday_credit_hours = storage::read_holiday_credit_hours().unwrap_or(0.0);
+        let holiday_country = storage::read_holiday_country();
+        let split_at_midnight = storage::read_split_entries_at_midnight();
+        let notifications_enabled = storage::read_notifications_enabled();
+        let end_of_day_summary = storage::read_end_of_day_summary()
+            .and_then(|value| notifications::parse_end_of_day(&value).ok().flatten());
         let token_hash = token.as_ref().map(|value| storage::hash_token(value));
         let cache = token_hash
             .as_ref()
             .and_then(|hash| storage::read_cache().filter(|cache| cache.token_hash == *hash));
         let quota = storage::read_quota();
+        let timezone_override =
+            storage::read_timezone_override().and_then(|value| Timezone::parse(&value));
+        let timezone = timezone_override
+            .or_else(|| cache.as_ref().and_then(profile_timezone))
+            .unwrap_or_default();
+        let restore_date_range = storage::read_restore_date_range();
+        let ui_state = storage::read_ui_state();
+        let date_range = match ui_state.date_range {
+            Some((start, end)) if restore_date_range && start <= end => {
+                DateRange::from_bounds_snapped(start, end, rollup_preferences.week_start, timezone)
+            }
+            _ => DateRange::today(timezone),
+        };
+        let rollup_view = ui_state
+            .rollup_view
+            .as_deref()
+            .and_then(RollupView::from_key)
+            .filter(|view| *view != RollupView::Cycle || !billing_cycles.is_empty())
+            .unwrap_or(RollupView::Weekly);
         let mut project_state = ListState::default();
         project_state.select(Some(0));
         let mut workspace_state = ListState::default();
@@ -232,18 +403,30 @@ impl App {
         rollup_week_state.select(Some(0));
         let mut rollup_month_state = ListState::default();
         rollup_month_state.select(Some(0));
+        let mut rollup_quarter_state = ListState::default();
+        rollup_quarter_state.select(Some(0));
         let mut rollup_year_state = ListState::default();
         rollup_year_state.select(Some(0));
+        let mut rollup_cycle_state = ListState::default();
+        rollup_cycle_state.select(Some(0));
         let mut rollup_day_state = ListState::default();
         rollup_day_state.select(Some(0));
-        let rollup_year_cursor = date_range.end_date().year();
+        let rollup_year_cursor = PeriodCalendar {
+            week_start: rollup_preferences.week_start,
+            fiscal_year_start: rollup_preferences.fiscal_year_start,
+        }
+        .fiscal_year(date_range.end_date());
+        let rollup_year_cursor = ui_state
+            .rollup_year
+            .map(clamp_year)
+            .unwrap_or(rollup_year_cursor);
 
         App {
             should_quit: false,
             needs_refresh: token.is_some(),
             mode,
             dashboard_focus: DashboardFocus::Projects,
-            status: None,
+            status: startup_status,
             input: String::new(),
             token,
             workspace_list: Vec::new(),
@@ -255,35 +438,71 @@ impl App {
             client_names: HashMap::new(),
             grouped: Vec::new(),
             total_hours: 0.0,
+            raw_hours: 0.0,
             project_state,
             entry_state: ListState::default(),
             rollups: Rollups::default(),
-            rollup_view: RollupView::Weekly,
+            rollup_view,
             rollup_focus: RollupFocus::Periods,
+            rollup_breakdown: RollupBreakdown::Projects,
+            rollup_pane: RollupPane::Calendar,
+            rollup_comparison: None,
+            rollup_pattern: None,
             rollup_week_state,
             rollup_month_state,
+            rollup_quarter_state,
             rollup_year_state,
+            rollup_cycle_state,
             rollup_day_state,
             rollups_include_weekends: rollup_preferences.include_weekends,
             rollups_week_start: rollup_preferences.week_start,
+            rollups_fiscal_year_start: rollup_preferences.fiscal_year_start,
+            remember_weekend_toggle: rollup_preferences.remember_weekend_toggle,
+            restore_date_range,
+            start_mode: None,
             rollup_year_cursor,
+            billing_cycles,
+            billing_cycle_index: 0,
             rollup_fetched_days: HashSet::new(),
-            vacation_days: special_days.vacation_days,
-            sick_days: special_days.sick_days,
-            vacation_day_target_hours,
-            vacation_day_credit_hours,
-            sick_day_target_hours,
-            sick_day_credit_hours,
-            credit_vacation_days_as_worked,
-            credit_sick_days_as_worked,
+            gap_thresholds: storage::read_gap_thresholds(),
+            gap_report: None,
+            gap_state: ListState::default(),
+            gap_return_mode: Mode::Dashboard,
+            max_entry_hours: storage::read_max_entry_hours().unwrap_or(10.0),
+            entry_issues: Vec::new(),
+            issue_state: ListState::default(),
+            time_bank: storage::read_time_bank(),
+            time_bank_daily: Vec::new(),
+            time_bank_counted_days: HashSet::new(),
+            time_bank_missing_days: 0,
+            notifications_enabled,
+            end_of_day_summary,
+            notification_tracker: NotificationTracker::default(),
+            last_notification_check: None,
+            settings_notifications_draft: notifications_enabled,
+            special_days,
+            vacation_allowance,
+            absence_input: String::new(),
+            absence_input_day: None,
+            settings_holiday_country_draft: holiday_country.clone(),
+            holiday_country,
+            imported_holiday_count: storage::read_imported_holidays().len(),
+            holiday_target_hours,
+            holiday_credit_hours,
             last_refresh: None,
             show_help: false,
             theme: theme.clone(),
             target_hours,
+            schedule_changes,
             update_info: None,
             update_installable: false,
             show_update_popup: false,
             rounding,
+            rounding_scope,
+            rounding_rules,
+            timezone,
+            split_at_midnight,
+            timezone_override,
             token_hash,
             cache,
             quota,
@@ -317,10 +536,14 @@ impl App {
             settings_rounding_draft_enabled: false,
             settings_theme_draft: theme.clone(),
             custom_themes: theme_settings.custom_themes,
-            settings_credit_vacation_days_draft: credit_vacation_days_as_worked,
-            settings_credit_sick_days_draft: credit_sick_days_as_worked,
+            settings_credit_absence_days_draft: false,
+            settings_split_at_midnight_draft: split_at_midnight,
+            settings_rounding_scope_draft: rounding_scope,
             settings_rollups_include_weekends_draft: rollup_preferences.include_weekends,
             settings_rollups_week_start_draft: rollup_preferences.week_start,
+            settings_fiscal_year_start_draft: rollup_preferences.fiscal_year_start,
+            settings_remember_weekend_toggle_draft: rollup_preferences.remember_weekend_toggle,
+            settings_restore_date_range_draft: restore_date_range,
             refetch_plan: None,
             status_created_at: None,
             last_status_snapshot: None,
@@ -336,11 +559,147 @@ impl App {
             Mode::DateInput(mode) => self.handle_date_input(mode, key),
             Mode::Settings => self.handle_settings_input(key),
             Mode::RefetchConfirm => self.handle_refetch_confirm_input(key),
+            Mode::AbsenceInput => self.handle_absence_input(key),
             Mode::Rollups => self.handle_rollups_input(key),
+            Mode::Gaps => self.handle_gaps_input(key),
+            Mode::Issues => self.handle_issues_input(key),
             Mode::Dashboard | Mode::Loading | Mode::Error => self.handle_dashboard_input(key),
         }
     }
 
+    /// Opens `mode` instead of the dashboard after the first load.
+    pub fn set_start_mode(&mut self, mode: Mode) {
+        self.start_mode = Some(mode);
+    }
+
+    /// Saves the rollup view, year and (when enabled) the date range for
+    /// the next launch.
+    pub fn save_ui_state(&self) -> Result<(), std::io::Error> {
+        storage::write_ui_state(UiState {
+            rollup_view: Some(self.rollup_view.key().to_string()),
+            rollup_year: Some(self.rollup_year_cursor),
+            date_range: self
+                .restore_date_range
+                .then(|| (self.date_range.start_date(), self.date_range.end_date())),
+        })
+    }
+
+    /// Builds the `view` period containing `day` without the TUI. Reads the
+    /// cache unless `fetch` is set, in which case the period is fetched from
+    /// Toggl first. `workspace` picks a workspace by id or name.
+    pub fn rollup_report(
+        &mut self,
+        view: RollupView,
+        day: NaiveDate,
+        fetch: bool,
+        workspace: Option<&str>,
+    ) -> Result<RollupReport, String> {
+        if self.token.is_none() {
+            return Err("Not logged in. Run timeshit once to log in.".to_string());
+        }
+        if view == RollupView::Cycle && self.billing_cycles.is_empty() {
+            return Err("No billing cycles configured.".to_string());
+        }
+        self.rollup_view = view;
+        self.rollup_year_cursor = self.period_calendar().fiscal_year(day);
+        self.rebuild_rollups();
+        let (start, end) = self
+            .rollup_periods()
+            .iter()
+            .find(|period| period.start <= day && period.end >= day)
+            .map(|period| (period.start, period.end))
+            .ok_or_else(|| format!("No rollup period contains {day}."))?;
+        self.date_range =
+            DateRange::from_bounds_snapped(start, end, self.rollups_week_start, self.timezone);
+
+        if fetch {
+            self.refresh_intent = RefreshIntent::ForceApi;
+        }
+        self.refresh_data();
+        if let Some(key) = workspace {
+            let selected = self
+                .workspace_list
+                .iter()
+                .find(|candidate| {
+                    candidate.id.to_string() == key || candidate.name.eq_ignore_ascii_case(key)
+                })
+                .cloned()
+                .ok_or_else(|| format!("Unknown workspace: {key}"))?;
+            if self.selected_workspace.as_ref().map(|current| current.id) != Some(selected.id) {
+                self.selected_workspace = Some(selected);
+                if fetch {
+                    self.refresh_intent = RefreshIntent::ForceApi;
+                }
+                self.refresh_data();
+            }
+        }
+        match self.mode {
+            Mode::Login => {
+                return Err(self
+                    .status
+                    .take()
+                    .unwrap_or_else(|| "Not logged in.".to_string()));
+            }
+            Mode::WorkspaceSelect => {
+                let names: Vec<String> = self
+                    .workspace_list
+                    .iter()
+                    .map(|workspace| format!("{} ({})", workspace.name, workspace.id))
+                    .collect();
+                return Err(format!(
+                    "Several workspaces found; pick one with --workspace: {}",
+                    names.join(", ")
+                ));
+            }
+            Mode::Error if self.selected_workspace.is_none() || fetch => {
+                return Err(self
+                    .status
+                    .take()
+                    .unwrap_or_else(|| "Failed to load data.".to_string()));
+            }
+            _ => {}
+        }
+
+        // A failed cache-only load stops before the rollups are rebuilt.
+        self.rebuild_rollups();
+        // Count coverage from the cache alone; the active range may only be
+        // partly cached here.
+        if let Some(workspace_id) = self
+            .selected_workspace
+            .as_ref()
+            .map(|workspace| workspace.id)
+        {
+            self.rollup_fetched_days =
+                self.collect_cached_day_coverage_for_range(workspace_id, start, end);
+        }
+        let period = self
+            .rollup_periods()
+            .iter()
+            .find(|period| period.start == start && period.end == end)
+            .ok_or_else(|| format!("No rollup period contains {day}."))?;
+        let schedule = self.work_schedule();
+        let balance = BalanceContext::new(&schedule, &self.special_days, self.holiday_hours());
+        let daily = &self.rollups.daily;
+        let scope_adjustment = period_scope_adjustment(period, daily);
+        let tracked_hours = hours_from_seconds(period.seconds);
+        let worked_hours = balance.period_effective_hours(period, daily) + scope_adjustment;
+        let (target_hours, target_days) = balance.period_target_hours(period);
+        let overtime = balance.period_overtime_hours(period, daily) + scope_adjustment;
+        Ok(RollupReport {
+            label: period.label.clone(),
+            start: period.start,
+            end: period.end,
+            days: period.days,
+            tracked_hours,
+            credited_hours: normalize_delta(worked_hours - tracked_hours),
+            worked_hours,
+            target_hours,
+            target_days,
+            overtime_hours: normalize_delta(overtime),
+            missing_days: self.rollup_period_missing_days(period),
+        })
+    }
+
     pub fn needs_update_check(&self) -> bool {
         self.needs_update_check
     }
@@ -362,13 +721,20 @@ impl App {
 
     pub fn refetch_plan_view(&self) -> Option<RefetchPlanView> {
         let plan = self.refetch_plan.as_ref()?;
-        let days = days_between(plan.start, plan.end);
         Some(RefetchPlanView {
             scope_label: plan.scope_label.clone(),
             start: plan.start.format("%Y-%m-%d").to_string(),
             end: plan.end.format("%Y-%m-%d").to_string(),
-            days,
-            estimated_calls: days as u32,
+            unfetched_days: plan.batches.iter().map(|batch| batch.unfetched_days).sum(),
+            stale_days: plan.batches.iter().map(|batch| batch.stale_days).sum(),
+            fresh_days: plan.fresh_days,
+            skipped_days: plan.skipped_days,
+            batches: plan
+                .batches
+                .iter()
+                .map(|batch| format_date_span(batch.start, batch.end))
+                .collect(),
+            estimated_calls: plan.batches.len() as u32,
             remaining_calls: self.quota_remaining(),
         })
     }
@@ -422,6 +788,8 @@ impl App {
                 return;
             }
         };
+        // Only the first load may open the start mode, even when it fails.
+        let start_mode = self.start_mode.take();
 
         let token_hash = match self.token_hash.clone() {
             Some(hash) => hash,
@@ -474,6 +842,8 @@ impl App {
                 if manual_refresh {
                     self.refresh_intent = RefreshIntent::ForceApi;
                 }
+                // Picking a workspace finishes the first load.
+                self.start_mode = start_mode;
                 return;
             }
         }
@@ -503,8 +873,23 @@ impl App {
                 None => return,
             };
 
-        let (start, end) = self.date_range.as_rfc3339();
-        let time_entries = match self.resolve_time_entries(
+        if !self.resolve_profile_timezone(&client, allow_api) {
+            return;
+        }
+        self.apply_timezone();
+
+        // Split entries may start the day before the range, so that day is
+        // fetched too and clipped afterwards.
+        let fetch_range = match self.split_window() {
+            Some(window) => DateRange::from_bounds(
+                window.start.pred_opt().unwrap_or(window.start),
+                window.end,
+                self.timezone,
+            ),
+            None => self.date_range.clone(),
+        };
+        let (start, end) = fetch_range.as_rfc3339();
+        let mut time_entries = match self.resolve_time_entries(
             &client,
             allow_api,
             workspace.id,
@@ -516,6 +901,7 @@ impl App {
             Some(entries) => entries,
             None => return,
         };
+        self.clip_overnight_entries(&mut time_entries);
 
         let valid_entries: Vec<TimeEntry> = time_entries
             .into_iter()
@@ -580,13 +966,26 @@ impl App {
             }
         }
 
+        let rounding = RoundingPolicy::new(
+            self.rounding,
+            self.rounding_scope,
+            self.rounding_rules.clone(),
+            &projects,
+        );
+        let split_window = self.split_window();
         let grouped = group_entries(
             &valid_entries,
             &projects,
             &client_names,
-            self.rounding.as_ref(),
+            &rounding,
+            split_window.as_ref(),
+        );
+        let (raw_seconds, rounded_seconds) = range_totals(
+            &valid_entries,
+            &rounding,
+            self.timezone,
+            split_window.as_ref(),
         );
-        let total_hours = grouped.iter().map(|group| group.total_hours).sum();
 
         if self.project_state.selected().is_none() {
             self.project_state.select(Some(0));
@@ -600,7 +999,9 @@ impl App {
         self.time_entries = valid_entries;
         self.client_names = client_names;
         self.grouped = grouped;
-        self.total_hours = total_hours;
+        self.total_hours = rounded_seconds as f64 / 3600.0;
+        self.raw_hours = raw_seconds as f64 / 3600.0;
+        self.revalidate_entries();
         self.rebuild_rollups();
         self.last_refresh = if allow_api && cache_reason.is_none() {
             Some(Local::now())
@@ -619,7 +1020,7 @@ impl App {
             self.status = Some(message);
         }
 
-        self.mode = resume_mode.unwrap_or(Mode::Dashboard);
+        self.mode = resume_mode.or(start_mode).unwrap_or(Mode::Dashboard);
     }
 
     fn handle_error(&mut self, err: TogglError) {
@@ -702,9 +1103,17 @@ impl App {
             KeyCode::Char('q') => self.should_quit = true,
             KeyCode::Char('r') => self.trigger_refresh(),
             KeyCode::Char('t') => {
-                self.set_date_range(DateRange::today());
+                self.set_date_range(DateRange::today(self.timezone));
+            }
+            KeyCode::Char('y') => self.set_date_range(DateRange::yesterday(self.timezone)),
+            KeyCode::Char('w') => {
+                self.set_date_range(DateRange::this_week(self.timezone, self.rollups_week_start));
             }
-            KeyCode::Char('y') => self.set_date_range(DateRange::yesterday()),
+            KeyCode::Char('W') => {
+                self.set_date_range(DateRange::last_week(self.timezone, self.rollups_week_start));
+            }
+            KeyCode::Char('e') => self.set_date_range(DateRange::this_month(self.timezone)),
+            KeyCode::Char('E') => self.set_date_range(DateRange::last_month(self.timezone)),
             KeyCode::Char('[') => self.shift_dashboard_date_range(-1),
             KeyCode::Char(']') => self.shift_dashboard_date_range(1),
             KeyCode::Char('h') => self.show_help = true,
@@ -712,12 +1121,16 @@ impl App {
             KeyCode::Char('g') | KeyCode::Char('G') => self.request_theme_studio(),
             KeyCode::Char('s') => self.enter_settings(),
             KeyCode::Char('o') | KeyCode::Char('O') => self.enter_rollups(),
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                self.open_gap_report(self.date_range.start_date(), self.date_range.end_date());
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => self.open_issues(),
             KeyCode::Char('d') => self.enter_date_input(DateInputMode::Range),
             KeyCode::Char('k') | KeyCode::Char('K') => {
-                self.toggle_vacation_day(self.date_range.end_date());
+                self.toggle_absence(self.date_range.end_date(), VACATION);
             }
             KeyCode::Char('j') | KeyCode::Char('J') => {
-                self.toggle_sick_day(self.date_range.end_date());
+                self.toggle_absence(self.date_range.end_date(), SICK);
             }
             KeyCode::Char('u') | KeyCode::Char('U') => {
                 self.show_update_popup = false;
@@ -805,15 +1218,38 @@ impl App {
             KeyCode::Char('g') | KeyCode::Char('G') => self.request_theme_studio(),
             KeyCode::Char('w') | KeyCode::Char('W') => self.set_rollup_view(RollupView::Weekly),
             KeyCode::Char('m') | KeyCode::Char('M') => self.set_rollup_view(RollupView::Monthly),
+            KeyCode::Char('Q') => self.set_rollup_view(RollupView::Quarterly),
             KeyCode::Char('y') | KeyCode::Char('Y') => self.set_rollup_view(RollupView::Yearly),
+            KeyCode::Char('b') | KeyCode::Char('B') => self.select_billing_cycle_view(),
             KeyCode::Char('[') => self.shift_rollup_year(-1),
             KeyCode::Char(']') => self.shift_rollup_year(1),
             KeyCode::Char('z') | KeyCode::Char('Z') => self.toggle_rollup_weekends(),
+            KeyCode::Char('p') | KeyCode::Char('P') => self.toggle_rollup_breakdown(),
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.toggle_rollup_pane(RollupPane::Comparison);
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                self.toggle_rollup_pane(RollupPane::Patterns);
+            }
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                if let Some((start, end)) = self
+                    .rollup_selected_period()
+                    .map(|period| (period.start, period.end))
+                {
+                    self.open_gap_report(start, end);
+                }
+            }
             KeyCode::Char('k') | KeyCode::Char('K') => {
-                self.toggle_vacation_day(self.rollup_toggle_day());
+                self.toggle_absence(self.rollup_toggle_day(), VACATION);
             }
             KeyCode::Char('j') | KeyCode::Char('J') => {
-                self.toggle_sick_day(self.rollup_toggle_day());
+                self.toggle_absence(self.rollup_toggle_day(), SICK);
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                self.cycle_absence_type(self.rollup_toggle_day());
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                self.open_absence_input(self.rollup_toggle_day());
             }
             KeyCode::Char('R')
                 if key.modifiers.contains(KeyModifiers::SHIFT)
@@ -833,7 +1269,13 @@ impl App {
             KeyCode::Up => match self.rollup_focus {
                 RollupFocus::Periods => self.select_previous_rollup_period(),
                 RollupFocus::Days => {
-                    if matches!(self.rollup_view, RollupView::Monthly | RollupView::Yearly) {
+                    if matches!(
+                        self.rollup_view,
+                        RollupView::Monthly
+                            | RollupView::Quarterly
+                            | RollupView::Yearly
+                            | RollupView::Cycle
+                    ) {
                         self.select_previous_rollup_day_by(self.rollup_vertical_step());
                     } else {
                         self.select_previous_rollup_day();
@@ -843,7 +1285,13 @@ impl App {
             KeyCode::Down => match self.rollup_focus {
                 RollupFocus::Periods => self.select_next_rollup_period(),
                 RollupFocus::Days => {
-                    if matches!(self.rollup_view, RollupView::Monthly | RollupView::Yearly) {
+                    if matches!(
+                        self.rollup_view,
+                        RollupView::Monthly
+                            | RollupView::Quarterly
+                            | RollupView::Yearly
+                            | RollupView::Cycle
+                    ) {
                         self.select_next_rollup_day_by(self.rollup_vertical_step());
                     } else {
                         self.select_next_rollup_day();
@@ -868,6 +1316,83 @@ impl App {
         }
     }
 
+    fn handle_absence_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.apply_absence_input(),
+            KeyCode::Esc => {
+                self.absence_input.clear();
+                self.absence_input_day = None;
+                self.status = None;
+                self.mode = Mode::Rollups;
+            }
+            KeyCode::Backspace => {
+                self.absence_input.pop();
+            }
+            KeyCode::Char(ch) if !ch.is_control() && !ch.is_whitespace() => {
+                self.absence_input.push(ch);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_gaps_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Esc | KeyCode::Char('f') | KeyCode::Char('F') => self.close_gap_report(),
+            KeyCode::Up => {
+                let index = self.gap_state.selected().unwrap_or(0);
+                self.gap_state.select(Some(index.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let count = self
+                    .gap_report
+                    .as_ref()
+                    .map_or(0, |report| report.items.len());
+                let index = self.gap_state.selected().unwrap_or(0);
+                if index + 1 < count {
+                    self.gap_state.select(Some(index + 1));
+                }
+            }
+            KeyCode::Enter => self.jump_to_selected_gap(),
+            _ => {}
+        }
+    }
+
+    fn handle_issues_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Esc | KeyCode::Char('i') | KeyCode::Char('I') => {
+                self.mode = Mode::Dashboard;
+            }
+            KeyCode::Up => {
+                let index = self.issue_state.selected().unwrap_or(0);
+                self.issue_state.select(Some(index.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let index = self.issue_state.selected().unwrap_or(0);
+                if index + 1 < self.entry_issues.len() {
+                    self.issue_state.select(Some(index + 1));
+                }
+            }
+            KeyCode::Enter => {
+                let Some(day) = self
+                    .issue_state
+                    .selected()
+                    .and_then(|index| self.entry_issues.get(index))
+                    .map(|issue| issue.start.date())
+                else {
+                    return;
+                };
+                if self.date_range.start_date() == day && self.date_range.end_date() == day {
+                    self.mode = Mode::Dashboard;
+                } else {
+                    self.set_date_range(DateRange::from_bounds(day, day, self.timezone));
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn handle_workspace_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Char('q') => self.should_quit = true,
@@ -931,7 +1456,6 @@ impl App {
 
     fn handle_date_input(&mut self, mode: DateInputMode, key: KeyEvent) {
         match key.code {
-            KeyCode::Char('q') => self.should_quit = true,
             KeyCode::Enter => {
                 let updated = match mode {
                     DateInputMode::Range => self.update_range_from_input(),
@@ -976,10 +1500,15 @@ impl App {
     }
 
     fn enter_date_input(&mut self, mode: DateInputMode) {
+        // End stays empty so the Start expression alone sets the range.
         let start = self.date_range.start_date().format("%Y-%m-%d").to_string();
         let end = self.date_range.end_date().format("%Y-%m-%d").to_string();
-        self.date_start_input = start;
-        self.date_end_input = end;
+        self.date_start_input = if start == end {
+            start
+        } else {
+            format!("{start}..{end}")
+        };
+        self.date_end_input.clear();
         self.date_active_field = DateField::Start;
         self.mode = Mode::DateInput(mode);
         self.status = None;
@@ -1011,6 +1540,148 @@ impl App {
         self.mode = Mode::Dashboard;
     }
 
+    fn open_gap_report(&mut self, start: NaiveDate, end: NaiveDate) {
+        let report = self.build_gap_report(start, end);
+        self.gap_state
+            .select((!report.items.is_empty()).then_some(0));
+        self.gap_report = Some(report);
+        self.gap_return_mode = if self.mode == Mode::Rollups {
+            Mode::Rollups
+        } else {
+            Mode::Dashboard
+        };
+        self.status = None;
+        self.mode = Mode::Gaps;
+    }
+
+    fn open_issues(&mut self) {
+        if self.entry_issues.is_empty() {
+            self.set_toast("No issues in the loaded entries.", false);
+            return;
+        }
+        let index = self.issue_state.selected().unwrap_or(0);
+        self.issue_state
+            .select(Some(index.min(self.entry_issues.len() - 1)));
+        self.status = None;
+        self.mode = Mode::Issues;
+    }
+
+    /// Re-runs the validator over the loaded entries.
+    fn revalidate_entries(&mut self) {
+        let max_seconds = (self.max_entry_hours * 3600.0).round() as i64;
+        self.entry_issues = validate_entries(
+            &self.time_entries,
+            self.timezone,
+            self.timezone.now(),
+            max_seconds,
+            |day| {
+                if self.special_days.partial_days.contains_key(&day) {
+                    return None;
+                }
+                self.special_days
+                    .absence(day)
+                    .map(|absence| absence.name.clone())
+            },
+        );
+    }
+
+    pub fn entry_issues(&self) -> &[EntryIssue] {
+        &self.entry_issues
+    }
+
+    pub fn max_entry_hours(&self) -> f64 {
+        self.max_entry_hours
+    }
+
+    fn close_gap_report(&mut self) {
+        self.gap_report = None;
+        self.mode = self.gap_return_mode;
+    }
+
+    fn jump_to_selected_gap(&mut self) {
+        let Some(day) = self.gap_state.selected().and_then(|index| {
+            self.gap_report
+                .as_ref()
+                .and_then(|report| report.items.get(index))
+                .map(|item| item.date)
+        }) else {
+            return;
+        };
+        self.gap_report = None;
+        self.set_date_range(DateRange::from_bounds(day, day, self.timezone));
+    }
+
+    pub fn gap_report(&self) -> Option<&GapReport> {
+        self.gap_report.as_ref()
+    }
+
+    pub fn gap_thresholds(&self) -> GapThresholds {
+        self.gap_thresholds
+    }
+
+    pub fn gap_return_mode(&self) -> Mode {
+        self.gap_return_mode
+    }
+
+    /// Checks fetched days only; today and later are never reported as low
+    /// since they may still be in progress.
+    fn build_gap_report(&self, start: NaiveDate, end: NaiveDate) -> GapReport {
+        let active_start = self.date_range.start_date();
+        let active_end = self.date_range.end_date();
+        let (entries, mut fetched) = match self
+            .selected_workspace
+            .as_ref()
+            .map(|workspace| workspace.id)
+        {
+            Some(workspace_id) => (
+                self.collect_rollup_entries(workspace_id, start, end),
+                self.collect_cached_day_coverage_for_range(workspace_id, start, end),
+            ),
+            None => (
+                self.time_entries
+                    .iter()
+                    .filter(|entry| entry.stop.is_some())
+                    .cloned()
+                    .collect(),
+                HashSet::new(),
+            ),
+        };
+        fetched.extend(
+            active_start
+                .iter_days()
+                .take_while(|day| *day <= active_end),
+        );
+
+        let today = self.timezone.today();
+        let schedule = self.work_schedule();
+        find_gaps(
+            &entries,
+            start,
+            end,
+            self.timezone,
+            self.gap_thresholds,
+            |day| {
+                if day >= today {
+                    return DayTarget::Off;
+                }
+                if !fetched.contains(&day) {
+                    return DayTarget::Unknown;
+                }
+                let scheduled = schedule.hours_for(day);
+                if scheduled <= 0.0 || self.special_days.holidays.contains(day) {
+                    return DayTarget::Off;
+                }
+                match self.special_days.partial_days.get(&day) {
+                    Some(amount) => {
+                        DayTarget::Hours(scheduled * (1.0 - amount.fraction_of(scheduled)))
+                    }
+                    None if self.special_days.is_absent(day) => DayTarget::Off,
+                    None => DayTarget::Hours(scheduled),
+                }
+            },
+        )
+    }
+
     fn trigger_refresh(&mut self) {
         self.mode = Mode::Loading;
         self.refresh_intent = RefreshIntent::ForceApi;
@@ -1022,7 +1693,7 @@ impl App {
     }
 
     fn set_date_range_with_resume(&mut self, range: DateRange, resume_mode: Option<Mode>) {
-        self.rollup_year_cursor = range.end_date().year();
+        self.rollup_year_cursor = self.period_calendar().fiscal_year(range.end_date());
         self.date_range = range;
         self.mode = Mode::Loading;
         self.refresh_intent = RefreshIntent::CacheOnly;
@@ -1035,6 +1706,10 @@ impl App {
         if direction == 0 {
             return;
         }
+        if let Some(range) = self.date_range.step(direction, self.timezone) {
+            self.set_date_range(range);
+            return;
+        }
         let start = self.date_range.start_date();
         let end = self.date_range.end_date();
         let span_days = days_between(start, end) as i64;
@@ -1044,7 +1719,7 @@ impl App {
         let shift = chrono::Duration::days(span_days * direction as i64);
         let next_start = start + shift;
         let next_end = end + shift;
-        self.set_date_range(DateRange::from_bounds(next_start, next_end));
+        self.set_date_range(DateRange::from_bounds(next_start, next_end, self.timezone));
     }
 
     fn handle_settings_input(&mut self, key: KeyEvent) {
@@ -1119,16 +1794,30 @@ impl App {
                     self.settings_rollups_include_weekends_draft =
                         !self.settings_rollups_include_weekends_draft;
                 }
-                SettingsItem::CreditVacationDays => {
-                    self.settings_credit_vacation_days_draft =
-                        !self.settings_credit_vacation_days_draft;
+                SettingsItem::CreditAbsenceDays(_) => {
+                    self.settings_credit_absence_days_draft =
+                        !self.settings_credit_absence_days_draft;
                 }
-                SettingsItem::CreditSickDays => {
-                    self.settings_credit_sick_days_draft = !self.settings_credit_sick_days_draft;
+                SettingsItem::SplitAtMidnight => {
+                    self.settings_split_at_midnight_draft = !self.settings_split_at_midnight_draft;
+                }
+                SettingsItem::RestoreDateRange => {
+                    self.settings_restore_date_range_draft =
+                        !self.settings_restore_date_range_draft;
+                }
+                SettingsItem::RememberWeekendToggle => {
+                    self.settings_remember_weekend_toggle_draft =
+                        !self.settings_remember_weekend_toggle_draft;
+                }
+                SettingsItem::Notifications => {
+                    self.settings_notifications_draft = !self.settings_notifications_draft;
                 }
                 SettingsItem::RollupsWeekStart => {
                     self.cycle_rollup_week_start(true);
                 }
+                SettingsItem::FiscalYearStart => {
+                    self.cycle_fiscal_year_start(true);
+                }
                 SettingsItem::TimeRoundingToggle => {
                     self.settings_rounding_draft_enabled = !self.settings_rounding_draft_enabled;
                 }
@@ -1138,10 +1827,13 @@ impl App {
                 SettingsItem::RoundingMode => {
                     self.cycle_rounding_mode(true);
                 }
-                SettingsItem::VacationTargetHours
-                | SettingsItem::VacationCreditHours
-                | SettingsItem::SickTargetHours
-                | SettingsItem::SickCreditHours => {}
+                SettingsItem::RoundingScope => {
+                    self.cycle_rounding_scope(true);
+                }
+                SettingsItem::HolidayCountry => {
+                    self.cycle_holiday_country(true);
+                }
+                SettingsItem::AbsenceTargetHours(_) | SettingsItem::AbsenceCreditHours(_) => {}
                 _ => {}
             },
             KeyCode::Down => match item {
@@ -1152,16 +1844,30 @@ impl App {
                     self.settings_rollups_include_weekends_draft =
                         !self.settings_rollups_include_weekends_draft;
                 }
-                SettingsItem::CreditVacationDays => {
-                    self.settings_credit_vacation_days_draft =
-                        !self.settings_credit_vacation_days_draft;
+                SettingsItem::CreditAbsenceDays(_) => {
+                    self.settings_credit_absence_days_draft =
+                        !self.settings_credit_absence_days_draft;
+                }
+                SettingsItem::SplitAtMidnight => {
+                    self.settings_split_at_midnight_draft = !self.settings_split_at_midnight_draft;
+                }
+                SettingsItem::RestoreDateRange => {
+                    self.settings_restore_date_range_draft =
+                        !self.settings_restore_date_range_draft;
                 }
-                SettingsItem::CreditSickDays => {
-                    self.settings_credit_sick_days_draft = !self.settings_credit_sick_days_draft;
+                SettingsItem::RememberWeekendToggle => {
+                    self.settings_remember_weekend_toggle_draft =
+                        !self.settings_remember_weekend_toggle_draft;
+                }
+                SettingsItem::Notifications => {
+                    self.settings_notifications_draft = !self.settings_notifications_draft;
                 }
                 SettingsItem::RollupsWeekStart => {
                     self.cycle_rollup_week_start(false);
                 }
+                SettingsItem::FiscalYearStart => {
+                    self.cycle_fiscal_year_start(false);
+                }
                 SettingsItem::TimeRoundingToggle => {
                     self.settings_rounding_draft_enabled = !self.settings_rounding_draft_enabled;
                 }
@@ -1171,18 +1877,29 @@ impl App {
                 SettingsItem::RoundingMode => {
                     self.cycle_rounding_mode(false);
                 }
-                SettingsItem::VacationTargetHours
-                | SettingsItem::VacationCreditHours
-                | SettingsItem::SickTargetHours
-                | SettingsItem::SickCreditHours => {}
+                SettingsItem::RoundingScope => {
+                    self.cycle_rounding_scope(false);
+                }
+                SettingsItem::HolidayCountry => {
+                    self.cycle_holiday_country(false);
+                }
+                SettingsItem::AbsenceTargetHours(_) | SettingsItem::AbsenceCreditHours(_) => {}
                 _ => {}
             },
             KeyCode::Backspace => match item {
                 SettingsItem::TargetHours
-                | SettingsItem::VacationTargetHours
-                | SettingsItem::VacationCreditHours
-                | SettingsItem::SickTargetHours
-                | SettingsItem::SickCreditHours
+                | SettingsItem::AbsenceTargetHours(_)
+                | SettingsItem::AbsenceCreditHours(_)
+                | SettingsItem::HolidayTargetHours
+                | SettingsItem::HolidayCreditHours
+                | SettingsItem::MaxEntryHours
+                | SettingsItem::HolidayImport
+                | SettingsItem::TimeBankStart
+                | SettingsItem::TimeBankOpening
+                | SettingsItem::GapLowDayPercent
+                | SettingsItem::GapMinMinutes
+                | SettingsItem::EndOfDaySummary
+                | SettingsItem::Timezone
                 | SettingsItem::TogglToken => {
                     self.settings_input.pop();
                 }
@@ -1195,6 +1912,31 @@ impl App {
                         self.settings_input.push(ch);
                     }
                 }
+                SettingsItem::Timezone if !ch.is_control() && !ch.is_whitespace() => {
+                    self.settings_input.push(ch);
+                }
+                SettingsItem::HolidayImport if !ch.is_control() => {
+                    self.settings_input.push(ch);
+                }
+                SettingsItem::TimeBankStart if ch.is_ascii_digit() || ch == '-' => {
+                    self.settings_input.push(ch);
+                }
+                SettingsItem::EndOfDaySummary if ch.is_ascii_digit() || ch == ':' => {
+                    self.settings_input.push(ch);
+                }
+                SettingsItem::GapLowDayPercent | SettingsItem::GapMinMinutes
+                    if ch.is_ascii_digit() =>
+                {
+                    self.settings_input.push(ch);
+                }
+                SettingsItem::TimeBankOpening
+                    if ch.is_ascii_digit()
+                        || ch == '.'
+                        || ch == ','
+                        || (self.settings_input.is_empty() && (ch == '-' || ch == '+')) =>
+                {
+                    self.settings_input.push(ch);
+                }
                 SettingsItem::TargetHours => {
                     if ch.is_ascii_digit() {
                         self.settings_input.push(ch);
@@ -1210,10 +1952,11 @@ impl App {
                         self.settings_input.push(ch);
                     }
                 }
-                SettingsItem::VacationTargetHours
-                | SettingsItem::VacationCreditHours
-                | SettingsItem::SickTargetHours
-                | SettingsItem::SickCreditHours => {
+                SettingsItem::AbsenceTargetHours(_)
+                | SettingsItem::AbsenceCreditHours(_)
+                | SettingsItem::HolidayTargetHours
+                | SettingsItem::HolidayCreditHours
+                | SettingsItem::MaxEntryHours => {
                     if ch.is_ascii_digit() {
                         self.settings_input.push(ch);
                         return;
@@ -1229,8 +1972,7 @@ impl App {
                     }
                 }
                 SettingsItem::RollupsIncludeWeekends => {}
-                SettingsItem::CreditVacationDays => {}
-                SettingsItem::CreditSickDays => {}
+                SettingsItem::CreditAbsenceDays(_) => {}
                 SettingsItem::RollupsWeekStart => {}
                 _ => {}
             },
@@ -1290,16 +2032,25 @@ impl App {
             .map(|project| (project.client_name.clone(), project.project_name.clone()));
         let selected_entry_key = self.current_entry().map(|entry| entry.description.clone());
 
+        let rounding = self.rounding_policy();
+        let split_window = self.split_window();
         let grouped = group_entries(
             &self.time_entries,
             &self.projects,
             &self.client_names,
-            self.rounding.as_ref(),
+            &rounding,
+            split_window.as_ref(),
+        );
+        let (raw_seconds, rounded_seconds) = range_totals(
+            &self.time_entries,
+            &rounding,
+            self.timezone,
+            split_window.as_ref(),
         );
-        let total_hours: f64 = grouped.iter().map(|group| group.total_hours).sum();
 
         self.grouped = grouped;
-        self.total_hours = total_hours;
+        self.total_hours = rounded_seconds as f64 / 3600.0;
+        self.raw_hours = raw_seconds as f64 / 3600.0;
         self.rebuild_rollups();
 
         if let Some((client_name, project_name)) = selected_project_key {
@@ -1329,24 +2080,49 @@ impl App {
 
     fn sync_settings_items_for_category(&mut self) {
         self.settings_items = match self.settings_selected_category() {
-            "Integrations" => vec![SettingsItem::TogglToken],
-            "Rollups" => vec![
-                SettingsItem::RollupsIncludeWeekends,
-                SettingsItem::RollupsWeekStart,
-                SettingsItem::CreditVacationDays,
-                SettingsItem::CreditSickDays,
-                SettingsItem::VacationTargetHours,
-                SettingsItem::VacationCreditHours,
-                SettingsItem::SickTargetHours,
-                SettingsItem::SickCreditHours,
+            "Integrations" => vec![
+                SettingsItem::TogglToken,
+                SettingsItem::Notifications,
+                SettingsItem::EndOfDaySummary,
             ],
+            "Rollups" => {
+                let mut items = vec![
+                    SettingsItem::RollupsIncludeWeekends,
+                    SettingsItem::RememberWeekendToggle,
+                    SettingsItem::RollupsWeekStart,
+                    SettingsItem::FiscalYearStart,
+                ];
+                for index in 0..self.special_days.types.len() {
+                    items.extend([
+                        SettingsItem::CreditAbsenceDays(index),
+                        SettingsItem::AbsenceTargetHours(index),
+                        SettingsItem::AbsenceCreditHours(index),
+                    ]);
+                }
+                items.extend([
+                    SettingsItem::HolidayCountry,
+                    SettingsItem::HolidayImport,
+                    SettingsItem::HolidayTargetHours,
+                    SettingsItem::HolidayCreditHours,
+                    SettingsItem::TimeBankStart,
+                    SettingsItem::TimeBankOpening,
+                    SettingsItem::GapLowDayPercent,
+                    SettingsItem::GapMinMinutes,
+                ]);
+                items
+            }
             _ => vec![
                 SettingsItem::Theme,
                 SettingsItem::ThemeStudio,
                 SettingsItem::TargetHours,
+                SettingsItem::Timezone,
+                SettingsItem::SplitAtMidnight,
+                SettingsItem::RestoreDateRange,
+                SettingsItem::MaxEntryHours,
                 SettingsItem::TimeRoundingToggle,
                 SettingsItem::RoundingIncrement,
                 SettingsItem::RoundingMode,
+                SettingsItem::RoundingScope,
             ],
         };
         if !self.settings_items.is_empty() {
@@ -1375,33 +2151,87 @@ impl App {
             SettingsItem::TargetHours => {
                 self.settings_input = format!("{:.2}", self.target_hours);
             }
-            SettingsItem::VacationTargetHours => {
-                self.settings_input = format!("{:.2}", self.vacation_day_target_hours);
+            SettingsItem::AbsenceTargetHours(index) => {
+                let hours = self.special_days.types[index].target_hours;
+                self.settings_input = format!("{:.2}", hours);
+            }
+            SettingsItem::AbsenceCreditHours(index) => {
+                let hours = self.special_days.types[index].credit_hours;
+                self.settings_input = format!("{:.2}", hours);
+            }
+            SettingsItem::HolidayCountry => {
+                self.settings_holiday_country_draft = self.holiday_country.clone();
+            }
+            SettingsItem::HolidayImport => {
+                self.settings_input.clear();
+            }
+            SettingsItem::HolidayTargetHours => {
+                self.settings_input = format!("{:.2}", self.holiday_target_hours);
+            }
+            SettingsItem::MaxEntryHours => {
+                self.settings_input = format!("{:.2}", self.max_entry_hours);
+            }
+            SettingsItem::HolidayCreditHours => {
+                self.settings_input = format!("{:.2}", self.holiday_credit_hours);
+            }
+            SettingsItem::TimeBankStart => {
+                self.settings_input = self
+                    .time_bank
+                    .map(|bank| bank.start.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
             }
-            SettingsItem::VacationCreditHours => {
-                self.settings_input = format!("{:.2}", self.vacation_day_credit_hours);
+            SettingsItem::TimeBankOpening => {
+                self.settings_input = format!("{:.2}", self.settings_time_bank_opening_display());
             }
-            SettingsItem::SickTargetHours => {
-                self.settings_input = format!("{:.2}", self.sick_day_target_hours);
+            SettingsItem::GapLowDayPercent => {
+                self.settings_input = self.gap_thresholds.low_day_percent.to_string();
             }
-            SettingsItem::SickCreditHours => {
-                self.settings_input = format!("{:.2}", self.sick_day_credit_hours);
+            SettingsItem::GapMinMinutes => {
+                self.settings_input = self.gap_thresholds.min_gap_minutes.to_string();
             }
             SettingsItem::RollupsIncludeWeekends => {
                 self.settings_rollups_include_weekends_draft = self.rollups_include_weekends;
             }
-            SettingsItem::CreditVacationDays => {
-                self.settings_credit_vacation_days_draft = self.credit_vacation_days_as_worked;
+            SettingsItem::CreditAbsenceDays(index) => {
+                self.settings_credit_absence_days_draft =
+                    self.special_days.types[index].credit_as_worked;
             }
-            SettingsItem::CreditSickDays => {
-                self.settings_credit_sick_days_draft = self.credit_sick_days_as_worked;
+            SettingsItem::SplitAtMidnight => {
+                self.settings_split_at_midnight_draft = self.split_at_midnight;
+            }
+            SettingsItem::RestoreDateRange => {
+                self.settings_restore_date_range_draft = self.restore_date_range;
+            }
+            SettingsItem::RememberWeekendToggle => {
+                self.settings_remember_weekend_toggle_draft = self.remember_weekend_toggle;
             }
             SettingsItem::RollupsWeekStart => {
                 self.settings_rollups_week_start_draft = self.rollups_week_start;
             }
+            SettingsItem::FiscalYearStart => {
+                self.settings_fiscal_year_start_draft = self.rollups_fiscal_year_start;
+            }
+            SettingsItem::Timezone => {
+                self.settings_input = self
+                    .timezone_override
+                    .map(|timezone| timezone.label())
+                    .unwrap_or_default();
+            }
             SettingsItem::TogglToken => {
                 self.settings_input = self.token.clone().unwrap_or_default();
             }
+            SettingsItem::Notifications => {
+                self.settings_notifications_draft = self.notifications_enabled;
+            }
+            SettingsItem::EndOfDaySummary => {
+                self.settings_input = self
+                    .end_of_day_summary
+                    .map(|time| time.format("%H:%M").to_string())
+                    .unwrap_or_default();
+            }
+            SettingsItem::RoundingScope => {
+                self.settings_rounding_scope_draft = self.rounding_scope;
+            }
             SettingsItem::TimeRoundingToggle => {
                 self.settings_rounding_draft_enabled = self.rounding.is_some();
                 self.settings_rounding_draft = self.rounding.unwrap_or_default();
@@ -1444,48 +2274,129 @@ impl App {
             SettingsItem::ThemeStudio => {
                 self.request_theme_studio();
             }
-            SettingsItem::RollupsIncludeWeekends | SettingsItem::RollupsWeekStart => {
+            SettingsItem::RollupsIncludeWeekends
+            | SettingsItem::RememberWeekendToggle
+            | SettingsItem::RollupsWeekStart
+            | SettingsItem::FiscalYearStart => {
                 let next = RollupPreferences {
                     include_weekends: self.settings_rollups_include_weekends_draft,
                     week_start: self.settings_rollups_week_start_draft,
+                    fiscal_year_start: self.settings_fiscal_year_start_draft,
+                    remember_weekend_toggle: self.settings_remember_weekend_toggle_draft,
                 };
                 if let Err(err) = storage::write_rollup_preferences(next) {
                     self.status = Some(format!("Failed to save: {err}"));
                     return;
                 }
                 self.rollups_include_weekends = next.include_weekends;
+                self.remember_weekend_toggle = next.remember_weekend_toggle;
+                let realigned = (self.rollups_week_start != next.week_start).then(|| {
+                    self.date_range
+                        .with_week_start(next.week_start, self.timezone)
+                });
                 self.rollups_week_start = next.week_start;
+                if self.rollups_fiscal_year_start != next.fiscal_year_start {
+                    self.rollups_fiscal_year_start = next.fiscal_year_start;
+                    self.rollup_year_cursor = self
+                        .period_calendar()
+                        .fiscal_year(self.date_range.end_date());
+                }
                 self.status = Some("Rollup defaults updated.".to_string());
                 self.set_toast("Rollup defaults saved.", false);
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
                 self.rebuild_rollups();
+                if let Some(range) = realigned
+                    && range.start_date() != self.date_range.start_date()
+                {
+                    self.set_date_range_with_resume(range, Some(Mode::Settings));
+                }
             }
-            SettingsItem::CreditVacationDays => {
-                let next = self.settings_credit_vacation_days_draft;
-                if let Err(err) = storage::write_credit_vacation_days_as_worked(next) {
+            SettingsItem::CreditAbsenceDays(index) => {
+                let absence = AbsenceType {
+                    credit_as_worked: self.settings_credit_absence_days_draft,
+                    ..self.special_days.types[index].clone()
+                };
+                if let Err(err) = storage::write_absence_type(&absence) {
                     self.status = Some(format!("Failed to save: {err}"));
                     return;
                 }
-                self.credit_vacation_days_as_worked = next;
-                self.status = Some("Vacation-day crediting updated.".to_string());
-                self.set_toast("Vacation-day crediting saved.", false);
+                let name = absence.name.clone();
+                self.special_days.types[index] = absence;
+                self.status = Some(format!("{name} crediting updated."));
+                self.set_toast(format!("{name} crediting saved."), false);
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
                 self.rebuild_rollups();
             }
-            SettingsItem::CreditSickDays => {
-                let next = self.settings_credit_sick_days_draft;
-                if let Err(err) = storage::write_credit_sick_days_as_worked(next) {
+            SettingsItem::Notifications => {
+                let next = self.settings_notifications_draft;
+                if let Err(err) = storage::write_notifications_enabled(next) {
                     self.status = Some(format!("Failed to save: {err}"));
                     return;
                 }
-                self.credit_sick_days_as_worked = next;
-                self.status = Some("Sick-day crediting updated.".to_string());
-                self.set_toast("Sick-day crediting saved.", false);
+                self.notifications_enabled = next;
+                self.notification_tracker = NotificationTracker::default();
+                self.last_notification_check = None;
+                self.status = Some(if next {
+                    "Desktop notifications enabled.".to_string()
+                } else {
+                    "Desktop notifications disabled.".to_string()
+                });
+                self.set_toast("Notifications saved.", false);
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
-                self.rebuild_rollups();
+            }
+            SettingsItem::EndOfDaySummary => {
+                let next = match notifications::parse_end_of_day(&self.settings_input) {
+                    Ok(value) => value,
+                    Err(message) => {
+                        self.status = Some(message);
+                        return;
+                    }
+                };
+                let stored = next.map(|time| time.format("%H:%M").to_string());
+                if let Err(err) = storage::write_end_of_day_summary(stored.clone()) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.end_of_day_summary = next;
+                self.status = Some(match stored {
+                    Some(time) => format!("End-of-day summary at {time}."),
+                    None => "End-of-day summary disabled.".to_string(),
+                });
+                self.set_toast("End-of-day summary saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+            }
+            SettingsItem::RestoreDateRange => {
+                let next = self.settings_restore_date_range_draft;
+                if let Err(err) = storage::write_restore_date_range(next) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.restore_date_range = next;
+                self.status = Some(if next {
+                    "The last date range reopens on launch.".to_string()
+                } else {
+                    "Launch opens today.".to_string()
+                });
+                self.set_toast("Date range restore saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+            }
+            SettingsItem::SplitAtMidnight => {
+                let next = self.settings_split_at_midnight_draft;
+                if let Err(err) = storage::write_split_entries_at_midnight(next) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.split_at_midnight = next;
+                self.set_toast("Midnight splitting saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+                let range = self.date_range.clone();
+                self.set_date_range_with_resume(range, Some(Mode::Settings));
             }
             SettingsItem::TargetHours => {
                 let parsed = match self.parse_target_hours() {
@@ -1506,84 +2417,260 @@ impl App {
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
             }
-            SettingsItem::VacationTargetHours => {
-                let parsed = match self.parse_hours_input("Vacation target hours", true) {
+            SettingsItem::AbsenceTargetHours(index) | SettingsItem::AbsenceCreditHours(index) => {
+                let mut absence = self.special_days.types[index].clone();
+                let label = self.absence_hours_label(item);
+                let parsed = match self.parse_hours_input(&label, true) {
                     Ok(value) => value,
                     Err(message) => {
                         self.status = Some(message);
                         return;
                     }
                 };
-                if let Err(err) = storage::write_vacation_day_target_hours(parsed) {
+                if matches!(item, SettingsItem::AbsenceTargetHours(_)) {
+                    absence.target_hours = parsed;
+                } else {
+                    absence.credit_hours = parsed;
+                }
+                if let Err(err) = storage::write_absence_type(&absence) {
                     self.status = Some(format!("Failed to save: {err}"));
                     return;
                 }
-                self.vacation_day_target_hours = parsed;
+                self.special_days.types[index] = absence;
                 self.settings_input = format!("{:.2}", parsed);
-                self.status = Some("Vacation target hours updated.".to_string());
-                self.set_toast("Vacation target hours saved.", false);
+                self.status = Some(format!("{label} updated."));
+                self.set_toast(format!("{label} saved."), false);
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
             }
-            SettingsItem::VacationCreditHours => {
-                let parsed = match self.parse_hours_input("Vacation credit hours", true) {
+            SettingsItem::MaxEntryHours => {
+                let parsed = match self.parse_hours_input("Max entry duration", true) {
                     Ok(value) => value,
                     Err(message) => {
                         self.status = Some(message);
                         return;
                     }
                 };
-                if let Err(err) = storage::write_vacation_day_credit_hours(parsed) {
+                if let Err(err) = storage::write_max_entry_hours(parsed) {
                     self.status = Some(format!("Failed to save: {err}"));
                     return;
                 }
-                self.vacation_day_credit_hours = parsed;
+                self.max_entry_hours = parsed;
+                self.revalidate_entries();
                 self.settings_input = format!("{:.2}", parsed);
-                self.status = Some("Vacation credit hours updated.".to_string());
-                self.set_toast("Vacation credit hours saved.", false);
+                self.status = Some(if parsed > 0.0 {
+                    "Max entry duration updated.".to_string()
+                } else {
+                    "Long entry check turned off.".to_string()
+                });
+                self.set_toast("Max entry duration saved.", false);
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
             }
-            SettingsItem::SickTargetHours => {
-                let parsed = match self.parse_hours_input("Sick target hours", true) {
+            SettingsItem::HolidayTargetHours => {
+                let parsed = match self.parse_hours_input("Holiday target hours", true) {
                     Ok(value) => value,
                     Err(message) => {
                         self.status = Some(message);
                         return;
                     }
                 };
-                if let Err(err) = storage::write_sick_day_target_hours(parsed) {
+                if let Err(err) = storage::write_holiday_target_hours(parsed) {
                     self.status = Some(format!("Failed to save: {err}"));
                     return;
                 }
-                self.sick_day_target_hours = parsed;
+                self.holiday_target_hours = parsed;
                 self.settings_input = format!("{:.2}", parsed);
-                self.status = Some("Sick target hours updated.".to_string());
-                self.set_toast("Sick target hours saved.", false);
+                self.status = Some("Holiday target hours updated.".to_string());
+                self.set_toast("Holiday target hours saved.", false);
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
             }
-            SettingsItem::SickCreditHours => {
-                let parsed = match self.parse_hours_input("Sick credit hours", true) {
+            SettingsItem::HolidayCreditHours => {
+                let parsed = match self.parse_hours_input("Holiday credit hours", true) {
                     Ok(value) => value,
                     Err(message) => {
                         self.status = Some(message);
                         return;
                     }
                 };
-                if let Err(err) = storage::write_sick_day_credit_hours(parsed) {
+                if let Err(err) = storage::write_holiday_credit_hours(parsed) {
                     self.status = Some(format!("Failed to save: {err}"));
                     return;
                 }
-                self.sick_day_credit_hours = parsed;
+                self.holiday_credit_hours = parsed;
                 self.settings_input = format!("{:.2}", parsed);
-                self.status = Some("Sick credit hours updated.".to_string());
-                self.set_toast("Sick credit hours saved.", false);
+                self.status = Some("Holiday credit hours updated.".to_string());
+                self.set_toast("Holiday credit hours saved.", false);
                 self.settings_edit_item = None;
                 self.settings_focus = SettingsFocus::Items;
             }
-            SettingsItem::TogglToken => {
-                let token = self.settings_input.trim().to_string();
+            SettingsItem::TimeBankStart => {
+                let value = self.settings_input.trim().to_string();
+                let next = if value.is_empty() {
+                    None
+                } else {
+                    let Ok(start) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") else {
+                        self.status = Some("Use a YYYY-MM-DD start date.".to_string());
+                        return;
+                    };
+                    Some(TimeBank {
+                        start,
+                        opening_hours: self.settings_time_bank_opening_display(),
+                    })
+                };
+                if let Err(err) = storage::write_time_bank(next) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.time_bank = next;
+                self.rebuild_time_bank();
+                self.status = Some(match next {
+                    Some(bank) => format!("Time bank starts {}.", bank.start.format("%Y-%m-%d")),
+                    None => "Time bank disabled.".to_string(),
+                });
+                self.set_toast("Time bank start saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+            }
+            SettingsItem::TimeBankOpening => {
+                let Some(bank) = self.time_bank else {
+                    self.status = Some("Set a time bank start date first.".to_string());
+                    return;
+                };
+                let opening_hours = match parse_signed_hours(&self.settings_input) {
+                    Ok(value) => value,
+                    Err(message) => {
+                        self.status = Some(message);
+                        return;
+                    }
+                };
+                let next = TimeBank {
+                    opening_hours,
+                    ..bank
+                };
+                if let Err(err) = storage::write_time_bank(Some(next)) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.time_bank = Some(next);
+                self.settings_input = format!("{opening_hours:.2}");
+                self.status = Some("Time bank opening balance updated.".to_string());
+                self.set_toast("Time bank opening balance saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+            }
+            SettingsItem::GapLowDayPercent | SettingsItem::GapMinMinutes => {
+                let (label, max) = if item == SettingsItem::GapLowDayPercent {
+                    ("Low-day threshold", 100)
+                } else {
+                    ("Minimum break", 24 * 60)
+                };
+                let value = match self.settings_input.trim().parse::<u32>() {
+                    Ok(value) if value <= max => value,
+                    _ => {
+                        self.status =
+                            Some(format!("{label} must be a whole number from 0 to {max}."));
+                        return;
+                    }
+                };
+                let mut next = self.gap_thresholds;
+                if item == SettingsItem::GapLowDayPercent {
+                    next.low_day_percent = value;
+                } else {
+                    next.min_gap_minutes = value;
+                }
+                if let Err(err) = storage::write_gap_thresholds(next) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.gap_thresholds = next;
+                self.status = Some(if value == 0 {
+                    format!("{label} check turned off.")
+                } else {
+                    format!("{label} updated.")
+                });
+                self.set_toast("Gap report saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+            }
+            SettingsItem::HolidayCountry => {
+                let next = self.settings_holiday_country_draft.clone();
+                if let Err(err) = storage::write_holiday_country(next.clone()) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.holiday_country = next;
+                self.reload_holidays();
+                self.status = Some("Holiday country updated.".to_string());
+                self.set_toast("Holiday country saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+            }
+            SettingsItem::HolidayImport => {
+                let path = self.settings_input.trim().to_string();
+                let imported = if path.is_empty() {
+                    Vec::new()
+                } else {
+                    match read_ics_holidays(&path) {
+                        Ok(imported) => imported,
+                        Err(message) => {
+                            self.status = Some(message.clone());
+                            self.set_toast(message, true);
+                            return;
+                        }
+                    }
+                };
+                let count = imported.len();
+                if let Err(err) = storage::write_imported_holidays(imported) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.imported_holiday_count = count;
+                self.reload_holidays();
+                let message = if path.is_empty() {
+                    "Imported holidays cleared.".to_string()
+                } else {
+                    format!("Imported {count} holiday(s).")
+                };
+                self.status = Some(message.clone());
+                self.set_toast(message, false);
+                self.settings_input.clear();
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+            }
+            SettingsItem::Timezone => {
+                let value = self.settings_input.trim().to_string();
+                let next = if value.is_empty() {
+                    None
+                } else {
+                    match Timezone::parse(&value) {
+                        Some(timezone) => Some(timezone),
+                        None => {
+                            self.status = Some(format!(
+                                "Unknown timezone \"{value}\". Use an IANA name like Europe/Brussels."
+                            ));
+                            return;
+                        }
+                    }
+                };
+                if let Err(err) = storage::write_timezone_override(next.map(|tz| tz.label())) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.timezone_override = next;
+                self.set_toast("Timezone saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+                let timezone = self.resolved_timezone();
+                if timezone != self.timezone {
+                    self.timezone = timezone;
+                    let range = self.date_range.with_timezone(timezone);
+                    self.set_date_range_with_resume(range, Some(Mode::Settings));
+                }
+            }
+            SettingsItem::TogglToken => {
+                let token = self.settings_input.trim().to_string();
                 if token.is_empty() {
                     self.status = Some("Token is required.".to_string());
                     return;
@@ -1650,6 +2737,19 @@ impl App {
                 self.settings_focus = SettingsFocus::Items;
                 self.rebuild_grouped();
             }
+            SettingsItem::RoundingScope => {
+                let next = self.settings_rounding_scope_draft;
+                if let Err(err) = storage::write_rounding_scope(next) {
+                    self.status = Some(format!("Failed to save: {err}"));
+                    return;
+                }
+                self.rounding_scope = next;
+                self.status = Some("Rounding scope updated.".to_string());
+                self.set_toast("Rounding scope saved.", false);
+                self.settings_edit_item = None;
+                self.settings_focus = SettingsFocus::Items;
+                self.rebuild_grouped();
+            }
         }
     }
 
@@ -1671,6 +2771,15 @@ impl App {
         };
     }
 
+    fn cycle_fiscal_year_start(&mut self, up: bool) {
+        let month = self.settings_fiscal_year_start_draft;
+        self.settings_fiscal_year_start_draft = if up {
+            month % 12 + 1
+        } else {
+            (month + 10) % 12 + 1
+        };
+    }
+
     fn cycle_rounding_increment(&mut self, up: bool) {
         let values = [15u32, 30, 45, 60];
         let current = self.settings_rounding_draft.increment_minutes;
@@ -1717,6 +2826,59 @@ impl App {
         self.settings_rounding_draft.mode = values[next_index];
     }
 
+    fn cycle_holiday_country(&mut self, up: bool) {
+        let current = self
+            .settings_holiday_country_draft
+            .as_deref()
+            .and_then(|country| {
+                COUNTRIES
+                    .iter()
+                    .position(|value| value.eq_ignore_ascii_case(country))
+            });
+        // `None` (no bundled rules) sits before the first country.
+        let next = match (current, up) {
+            (None, true) => Some(COUNTRIES.len() - 1),
+            (None, false) => Some(0),
+            (Some(0), true) => None,
+            (Some(index), true) => Some(index - 1),
+            (Some(index), false) if index + 1 >= COUNTRIES.len() => None,
+            (Some(index), false) => Some(index + 1),
+        };
+        self.settings_holiday_country_draft = next.map(|index| COUNTRIES[index].to_string());
+    }
+
+    fn reload_holidays(&mut self) {
+        self.special_days.holidays = storage::read_special_days().holidays;
+    }
+
+    fn cycle_rounding_scope(&mut self, up: bool) {
+        let values = [
+            RoundingScope::Entry,
+            RoundingScope::Line,
+            RoundingScope::Day,
+            RoundingScope::Period,
+        ];
+        let current = self.settings_rounding_scope_draft;
+        let index = values
+            .iter()
+            .position(|value| *value == current)
+            .unwrap_or(0);
+        let next_index = if up {
+            if index == 0 {
+                values.len() - 1
+            } else {
+                index - 1
+            }
+        } else {
+            if index + 1 >= values.len() {
+                0
+            } else {
+                index + 1
+            }
+        };
+        self.settings_rounding_scope_draft = values[next_index];
+    }
+
     fn select_previous_setting_item(&mut self) {
         if self.settings_items.is_empty() {
             return;
@@ -1850,48 +3012,81 @@ impl App {
         &self.custom_themes
     }
 
-    pub fn settings_vacation_target_hours_display(&self) -> f64 {
-        if self.settings_focus == SettingsFocus::Edit
-            && self.settings_edit_item == Some(SettingsItem::VacationTargetHours)
-        {
+    /// Label of an absence type's hours setting, e.g. "Vacation target hours".
+    pub fn absence_hours_label(&self, item: SettingsItem) -> String {
+        match item {
+            SettingsItem::AbsenceTargetHours(index) => {
+                format!("{} target hours", self.special_days.types[index].name)
+            }
+            SettingsItem::AbsenceCreditHours(index) => {
+                format!("{} credit hours", self.special_days.types[index].name)
+            }
+            _ => String::new(),
+        }
+    }
+
+    pub fn settings_absence_hours_display(&self, item: SettingsItem) -> f64 {
+        let saved = match item {
+            SettingsItem::AbsenceTargetHours(index) => self.special_days.types[index].target_hours,
+            SettingsItem::AbsenceCreditHours(index) => self.special_days.types[index].credit_hours,
+            _ => 0.0,
+        };
+        if self.settings_focus == SettingsFocus::Edit && self.settings_edit_item == Some(item) {
             return self
-                .parse_hours_input("Vacation target hours", true)
-                .unwrap_or(self.vacation_day_target_hours);
+                .parse_hours_input(&self.absence_hours_label(item), true)
+                .unwrap_or(saved);
         }
-        self.vacation_day_target_hours
+        saved
+    }
+
+    pub fn absence_type(&self, index: usize) -> &AbsenceType {
+        &self.special_days.types[index]
     }
 
-    pub fn settings_vacation_credit_hours_display(&self) -> f64 {
+    pub fn settings_holiday_country_display(&self) -> Option<String> {
         if self.settings_focus == SettingsFocus::Edit
-            && self.settings_edit_item == Some(SettingsItem::VacationCreditHours)
+            && self.settings_edit_item == Some(SettingsItem::HolidayCountry)
         {
-            return self
-                .parse_hours_input("Vacation credit hours", true)
-                .unwrap_or(self.vacation_day_credit_hours);
+            self.settings_holiday_country_draft.clone()
+        } else {
+            self.holiday_country.clone()
         }
-        self.vacation_day_credit_hours
     }
 
-    pub fn settings_sick_target_hours_display(&self) -> f64 {
+    pub fn imported_holiday_count(&self) -> usize {
+        self.imported_holiday_count
+    }
+
+    pub fn settings_holiday_target_hours_display(&self) -> f64 {
         if self.settings_focus == SettingsFocus::Edit
-            && self.settings_edit_item == Some(SettingsItem::SickTargetHours)
+            && self.settings_edit_item == Some(SettingsItem::HolidayTargetHours)
         {
             return self
-                .parse_hours_input("Sick target hours", true)
-                .unwrap_or(self.sick_day_target_hours);
+                .parse_hours_input("Holiday target hours", true)
+                .unwrap_or(self.holiday_target_hours);
         }
-        self.sick_day_target_hours
+        self.holiday_target_hours
     }
 
-    pub fn settings_sick_credit_hours_display(&self) -> f64 {
+    pub fn settings_holiday_credit_hours_display(&self) -> f64 {
         if self.settings_focus == SettingsFocus::Edit
-            && self.settings_edit_item == Some(SettingsItem::SickCreditHours)
+            && self.settings_edit_item == Some(SettingsItem::HolidayCreditHours)
         {
             return self
-                .parse_hours_input("Sick credit hours", true)
-                .unwrap_or(self.sick_day_credit_hours);
+                .parse_hours_input("Holiday credit hours", true)
+                .unwrap_or(self.holiday_credit_hours);
         }
-        self.sick_day_credit_hours
+        self.holiday_credit_hours
+    }
+
+    pub fn settings_time_bank_start_display(&self) -> String {
+        self.time_bank
+            .map(|bank| bank.start.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "Off".to_string())
+    }
+
+    pub fn settings_time_bank_opening_display(&self) -> f64 {
+        self.time_bank.map(|bank| bank.opening_hours).unwrap_or(0.0)
     }
 
     pub fn settings_rollups_include_weekends_display(&self) -> bool {
@@ -1904,23 +3099,73 @@ impl App {
         }
     }
 
-    pub fn settings_credit_vacation_days_display(&self) -> bool {
+    pub fn settings_credit_absence_days_display(&self, index: usize) -> bool {
+        if self.settings_focus == SettingsFocus::Edit
+            && self.settings_edit_item == Some(SettingsItem::CreditAbsenceDays(index))
+        {
+            self.settings_credit_absence_days_draft
+        } else {
+            self.special_days.types[index].credit_as_worked
+        }
+    }
+
+    pub fn settings_rounding_scope_display(&self) -> RoundingScope {
+        if self.settings_focus == SettingsFocus::Edit
+            && self.settings_edit_item == Some(SettingsItem::RoundingScope)
+        {
+            self.settings_rounding_scope_draft
+        } else {
+            self.rounding_scope
+        }
+    }
+
+    pub fn settings_notifications_display(&self) -> bool {
+        if self.settings_focus == SettingsFocus::Edit
+            && self.settings_edit_item == Some(SettingsItem::Notifications)
+        {
+            self.settings_notifications_draft
+        } else {
+            self.notifications_enabled
+        }
+    }
+
+    pub fn settings_end_of_day_summary_display(&self) -> String {
+        self.end_of_day_summary
+            .map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_else(|| "Off".to_string())
+    }
+
+    pub fn notifications_enabled(&self) -> bool {
+        self.notifications_enabled
+    }
+
+    pub fn settings_restore_date_range_display(&self) -> bool {
         if self.settings_focus == SettingsFocus::Edit
-            && self.settings_edit_item == Some(SettingsItem::CreditVacationDays)
+            && self.settings_edit_item == Some(SettingsItem::RestoreDateRange)
         {
-            self.settings_credit_vacation_days_draft
+            self.settings_restore_date_range_draft
         } else {
-            self.credit_vacation_days_as_worked
+            self.restore_date_range
         }
     }
 
-    pub fn settings_credit_sick_days_display(&self) -> bool {
+    pub fn settings_remember_weekend_toggle_display(&self) -> bool {
         if self.settings_focus == SettingsFocus::Edit
-            && self.settings_edit_item == Some(SettingsItem::CreditSickDays)
+            && self.settings_edit_item == Some(SettingsItem::RememberWeekendToggle)
         {
-            self.settings_credit_sick_days_draft
+            self.settings_remember_weekend_toggle_draft
         } else {
-            self.credit_sick_days_as_worked
+            self.remember_weekend_toggle
+        }
+    }
+
+    pub fn settings_split_at_midnight_display(&self) -> bool {
+        if self.settings_focus == SettingsFocus::Edit
+            && self.settings_edit_item == Some(SettingsItem::SplitAtMidnight)
+        {
+            self.settings_split_at_midnight_draft
+        } else {
+            self.split_at_midnight
         }
     }
 
@@ -1934,6 +3179,16 @@ impl App {
         }
     }
 
+    pub fn settings_fiscal_year_start_display(&self) -> u32 {
+        if self.settings_focus == SettingsFocus::Edit
+            && self.settings_edit_item == Some(SettingsItem::FiscalYearStart)
+        {
+            self.settings_fiscal_year_start_draft
+        } else {
+            self.rollups_fiscal_year_start
+        }
+    }
+
     fn select_previous_project(&mut self) {
         if self.grouped.is_empty() {
             return;
@@ -2027,6 +3282,8 @@ impl App {
     }
 
     fn rebuild_rollups(&mut self) {
+        self.rollup_comparison = None;
+        self.rollup_pattern = None;
         let (rollup_start, rollup_end) = self.rollup_bounds();
         let Some(workspace_id) = self
             .selected_workspace
@@ -2038,12 +3295,17 @@ impl App {
                 &self.time_entries,
                 rollup_start,
                 rollup_end,
-                self.rounding.as_ref(),
-                self.rollups_week_start,
+                &self.rounding_policy(),
+                self.period_calendar(),
+                self.timezone,
+                self.split_at_midnight,
             );
+            self.rollups.cycle =
+                self.build_selected_cycle_rollups(&self.time_entries, rollup_start, rollup_end);
             self.align_rollup_selection_to_active_range();
             self.align_rollup_day_selection_to_active_range();
             self.ensure_rollup_selections();
+            self.rebuild_time_bank();
             return;
         };
 
@@ -2051,8 +3313,60 @@ impl App {
             self.collect_cached_day_coverage_for_range(workspace_id, rollup_start, rollup_end);
         self.extend_rollup_coverage_with_active_range(rollup_start, rollup_end);
 
+        let rollup_entries = self.collect_rollup_entries(workspace_id, rollup_start, rollup_end);
+        self.rollups = build_rollups(
+            &rollup_entries,
+            rollup_start,
+            rollup_end,
+            &self.rounding_policy(),
+            self.period_calendar(),
+            self.timezone,
+            self.split_at_midnight,
+        );
+        self.rollups.cycle =
+            self.build_selected_cycle_rollups(&rollup_entries, rollup_start, rollup_end);
+        self.align_rollup_selection_to_active_range();
+        self.align_rollup_day_selection_to_active_range();
+        self.ensure_rollup_selections();
+        self.rebuild_time_bank();
+    }
+
+    /// Only built while the cycle view is open, since the bounds follow it.
+    fn build_selected_cycle_rollups(
+        &self,
+        entries: &[TimeEntry],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Vec<PeriodRollup> {
+        match self.selected_billing_cycle() {
+            Some(cycle) if self.rollup_view == RollupView::Cycle => build_cycle_rollups(
+                entries,
+                start,
+                end,
+                &self.rounding_policy(),
+                cycle,
+                self.timezone,
+                self.split_at_midnight,
+            ),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Finished entries from the cache and the active range, deduplicated.
+    fn collect_rollup_entries(
+        &self,
+        workspace_id: u64,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Vec<TimeEntry> {
         let mut entries_by_id: HashMap<u64, TimeEntry> = HashMap::new();
-        for entry in self.collect_cached_entries_for_range(workspace_id, rollup_start, rollup_end) {
+        // A split entry that started the day before can still land in range.
+        let collect_start = if self.split_at_midnight {
+            start.pred_opt().unwrap_or(start)
+        } else {
+            start
+        };
+        for entry in self.collect_cached_entries_for_range(workspace_id, collect_start, end) {
             if entry.stop.is_some() {
                 entries_by_id.insert(entry.id, entry);
             }
@@ -2063,40 +3377,176 @@ impl App {
             }
         }
 
-        let mut rollup_entries: Vec<TimeEntry> = entries_by_id.into_values().collect();
-        rollup_entries
-            .sort_by(|left, right| left.start.cmp(&right.start).then(left.id.cmp(&right.id)));
+        let mut entries: Vec<TimeEntry> = entries_by_id.into_values().collect();
+        entries.sort_by(|left, right| left.start.cmp(&right.start).then(left.id.cmp(&right.id)));
+        entries
+    }
 
-        self.rollups = build_rollups(
-            &rollup_entries,
-            rollup_start,
-            rollup_end,
-            self.rounding.as_ref(),
-            self.rollups_week_start,
+    /// Daily totals from the time bank start through today, the fetched days
+    /// before today that count towards the balance, and a count of scheduled
+    /// days in that span that have never been fetched.
+    fn rebuild_time_bank(&mut self) {
+        self.time_bank_daily.clear();
+        self.time_bank_counted_days.clear();
+        self.time_bank_missing_days = 0;
+        let today = self.timezone.today();
+        let (Some(bank), Some(workspace_id)) = (
+            self.time_bank,
+            self.selected_workspace
+                .as_ref()
+                .map(|workspace| workspace.id),
+        ) else {
+            return;
+        };
+        if bank.start > today {
+            return;
+        }
+
+        let entries = self.collect_rollup_entries(workspace_id, bank.start, today);
+        self.time_bank_daily = build_rollups(
+            &entries,
+            bank.start,
+            today,
+            &self.rounding_policy(),
+            self.period_calendar(),
+            self.timezone,
+            self.split_at_midnight,
+        )
+        .daily;
+
+        let mut fetched =
+            self.collect_cached_day_coverage_for_range(workspace_id, bank.start, today);
+        let active_start = self.date_range.start_date().max(bank.start);
+        let active_end = self.date_range.end_date().min(today);
+        fetched.extend(
+            active_start
+                .iter_days()
+                .take_while(|day| *day <= active_end),
         );
-        self.align_rollup_selection_to_active_range();
-        self.align_rollup_day_selection_to_active_range();
-        self.ensure_rollup_selections();
+        let schedule = self.work_schedule();
+        self.time_bank_missing_days = bank
+            .start
+            .iter_days()
+            .take_while(|day| *day <= today)
+            .filter(|day| !fetched.contains(day) && schedule.hours_for(*day) > 0.0)
+            .count();
+        // Today only counts once it is over.
+        fetched.remove(&today);
+        self.time_bank_counted_days = fetched;
+    }
+
+    /// Sends any due desktop notifications. Runs at most every 30 seconds
+    /// and only looks at data that is already cached.
+    pub fn check_notifications(&mut self) {
+        if !self.notifications_enabled
+            || self
+                .last_notification_check
+                .is_some_and(|checked| checked.elapsed() < Duration::from_secs(30))
+        {
+            return;
+        }
+        self.last_notification_check = Some(Instant::now());
+        let Some(workspace_id) = self
+            .selected_workspace
+            .as_ref()
+            .map(|workspace| workspace.id)
+        else {
+            return;
+        };
+
+        let now = self.timezone.now().naive_local();
+        let today = now.date();
+        let week_start = start_of_week(today, self.rollups_week_start);
+        let entries = self.collect_rollup_entries(workspace_id, week_start, today);
+        let daily = build_rollups(
+            &entries,
+            week_start,
+            today,
+            &self.rounding_policy(),
+            self.period_calendar(),
+            self.timezone,
+            self.split_at_midnight,
+        )
+        .daily;
+
+        let schedule = self.work_schedule();
+        let balance = BalanceContext::new(&schedule, &self.special_days, self.holiday_hours());
+        let worked_hours: HashMap<NaiveDate, f64> = daily
+            .iter()
+            .map(|day| (day.date, hours_from_seconds(day.seconds)))
+            .collect();
+        let progress_for = |day: NaiveDate| notifications::Progress {
+            worked: balance.effective_hours(day, worked_hours.get(&day).copied().unwrap_or(0.0)),
+            target: balance.target_hours(day),
+        };
+        let mut week = WeekProgress::default();
+        for day in week_start.iter_days().take(7) {
+            let progress = progress_for(day);
+            if day < today {
+                week.before_today.worked += progress.worked;
+                week.before_today.target += progress.target;
+            }
+            week.total.worked += progress.worked;
+            week.total.target += progress.target;
+        }
+
+        let pending = self.notification_tracker.check(
+            now,
+            self.end_of_day_summary,
+            progress_for(today),
+            week,
+        );
+        for notification in pending {
+            if let Err(err) = notifications::send(&notification) {
+                self.status = Some(format!("Notification failed: {err}"));
+                break;
+            }
+        }
+    }
+
+    pub fn time_bank(&self) -> Option<TimeBank> {
+        self.time_bank
+    }
+
+    pub fn time_bank_daily(&self) -> &[DailyTotal] {
+        &self.time_bank_daily
+    }
+
+    pub fn time_bank_counted_days(&self) -> &HashSet<NaiveDate> {
+        &self.time_bank_counted_days
+    }
+
+    pub fn time_bank_missing_days(&self) -> usize {
+        self.time_bank_missing_days
     }
 
     fn rollup_bounds(&self) -> (NaiveDate, NaiveDate) {
+        let calendar = self.period_calendar();
+        let fallback = || {
+            let today = self.timezone.today();
+            (year_start(today), year_end(today))
+        };
         let year = clamp_year(self.rollup_year_cursor);
+        let year_bounds = calendar.fiscal_year_bounds(year).unwrap_or_else(fallback);
         match self.rollup_view {
-            RollupView::Weekly | RollupView::Monthly => (
-                NaiveDate::from_ymd_opt(year, 1, 1)
-                    .unwrap_or_else(|| year_start(Local::now().date_naive())),
-                NaiveDate::from_ymd_opt(year, 12, 31)
-                    .unwrap_or_else(|| year_end(Local::now().date_naive())),
-            ),
+            RollupView::Weekly | RollupView::Monthly | RollupView::Quarterly => year_bounds,
+            // Whole cycles overlapping the year, so the edges are not cut off.
+            RollupView::Cycle => match self.selected_billing_cycle() {
+                Some(cycle) => (
+                    cycle.cycle_start(year_bounds.0),
+                    cycle.cycle_end(cycle.cycle_start(year_bounds.1)),
+                ),
+                None => year_bounds,
+            },
             RollupView::Yearly => {
-                let window_start = clamp_year(year.saturating_sub(2));
-                let window_end = clamp_year(year.saturating_add(5));
-                (
-                    NaiveDate::from_ymd_opt(window_start, 1, 1)
-                        .unwrap_or_else(|| year_start(Local::now().date_naive())),
-                    NaiveDate::from_ymd_opt(window_end, 12, 31)
-                        .unwrap_or_else(|| year_end(Local::now().date_naive())),
-                )
+                let (window_start, window_end) = self.rollup_year_window();
+                match (
+                    calendar.fiscal_year_bounds(window_start),
+                    calendar.fiscal_year_bounds(window_end),
+                ) {
+                    (Some((start, _)), Some((_, end))) => (start, end),
+                    _ => fallback(),
+                }
             }
         }
     }
@@ -2120,6 +3570,14 @@ impl App {
             .or_else(|| (!self.rollups.monthly.is_empty()).then_some(0));
         self.rollup_month_state.select(monthly_index);
 
+        let quarterly_index = self
+            .rollups
+            .quarterly
+            .iter()
+            .position(|period| period.start <= target_day && period.end >= target_day)
+            .or_else(|| (!self.rollups.quarterly.is_empty()).then_some(0));
+        self.rollup_quarter_state.select(quarterly_index);
+
         let yearly_index = self
             .rollups
             .yearly
@@ -2127,6 +3585,14 @@ impl App {
             .position(|period| period.start <= target_day && period.end >= target_day)
             .or_else(|| (!self.rollups.yearly.is_empty()).then_some(0));
         self.rollup_year_state.select(yearly_index);
+
+        let cycle_index = self
+            .rollups
+            .cycle
+            .iter()
+            .position(|period| period.start <= target_day && period.end >= target_day)
+            .or_else(|| (!self.rollups.cycle.is_empty()).then_some(0));
+        self.rollup_cycle_state.select(cycle_index);
     }
 
     fn align_rollup_day_selection_to_active_range(&mut self) {
@@ -2149,19 +3615,22 @@ impl App {
 
     fn rollup_target_day(&self) -> NaiveDate {
         let active_day = self.date_range.end_date();
+        let calendar = self.period_calendar();
+        let year_start = calendar
+            .fiscal_year_bounds(clamp_year(self.rollup_year_cursor))
+            .map_or(active_day, |(start, _)| start);
         match self.rollup_view {
-            RollupView::Weekly | RollupView::Monthly => {
-                if active_day.year() == self.rollup_year_cursor {
+            RollupView::Weekly
+            | RollupView::Monthly
+            | RollupView::Quarterly
+            | RollupView::Cycle => {
+                if calendar.fiscal_year(active_day) == self.rollup_year_cursor {
                     active_day
                 } else {
-                    NaiveDate::from_ymd_opt(clamp_year(self.rollup_year_cursor), 1, 1)
-                        .unwrap_or(active_day)
+                    year_start
                 }
             }
-            RollupView::Yearly => {
-                NaiveDate::from_ymd_opt(clamp_year(self.rollup_year_cursor), 1, 1)
-                    .unwrap_or(active_day)
-            }
+            RollupView::Yearly => year_start,
         }
     }
 
@@ -2255,51 +3724,358 @@ impl App {
         } else {
             selected + 1
         };
-        self.entry_state.select(Some(new_index));
+        self.entry_state.select(Some(new_index));
+    }
+
+    fn set_rollup_view(&mut self, view: RollupView) {
+        if self.rollup_view == view {
+            return;
+        }
+        self.rollup_view = view;
+        self.rebuild_rollups();
+        self.ensure_rollup_selections();
+    }
+
+    /// Opens the billing cycle view, or moves to the next configured cycle
+    /// when it is already open.
+    fn select_billing_cycle_view(&mut self) {
+        if self.billing_cycles.is_empty() {
+            self.status = Some("No billing cycles configured.".to_string());
+            return;
+        }
+        if self.rollup_view == RollupView::Cycle {
+            self.billing_cycle_index = (self.billing_cycle_index + 1) % self.billing_cycles.len();
+            self.rebuild_rollups();
+            self.ensure_rollup_selections();
+        } else {
+            self.set_rollup_view(RollupView::Cycle);
+        }
+        if let Some(cycle) = self.selected_billing_cycle() {
+            self.status = Some(format!("Billing cycle: {}", cycle.name));
+        }
+    }
+
+    pub fn selected_billing_cycle(&self) -> Option<&BillingCycle> {
+        self.billing_cycles.get(self.billing_cycle_index)
+    }
+
+    fn shift_rollup_year(&mut self, direction: i32) {
+        let direction = direction.signum();
+        if direction == 0 {
+            return;
+        }
+        let next = clamp_year(self.rollup_year_cursor.saturating_add(direction));
+        if next == self.rollup_year_cursor {
+            return;
+        }
+        self.rollup_year_cursor = next;
+        self.rebuild_rollups();
+        self.ensure_rollup_selections();
+        self.status = Some(format!(
+            "Rollups year: {}",
+            self.period_calendar()
+                .fiscal_year_label(self.rollup_year_cursor)
+        ));
+    }
+
+    fn toggle_rollup_focus(&mut self) {
+        if self.rollup_pane != RollupPane::Calendar {
+            return;
+        }
+        self.rollup_focus = match self.rollup_focus {
+            RollupFocus::Periods => RollupFocus::Days,
+            RollupFocus::Days => RollupFocus::Periods,
+        };
+    }
+
+    fn toggle_rollup_weekends(&mut self) {
+        self.rollups_include_weekends = !self.rollups_include_weekends;
+        self.align_rollup_day_selection_to_active_range();
+        self.ensure_rollup_day_selection();
+        if self.rollups_include_weekends {
+            self.status = Some("Rollups: weekends included.".to_string());
+        } else {
+            self.status = Some("Rollups: weekends excluded.".to_string());
+        }
+        if self.remember_weekend_toggle {
+            let next = RollupPreferences {
+                include_weekends: self.rollups_include_weekends,
+                ..storage::read_rollup_preferences()
+            };
+            if let Err(err) = storage::write_rollup_preferences(next) {
+                self.set_toast(format!("Failed to save weekend toggle: {err}"), true);
+            }
+        }
+    }
+
+    fn toggle_rollup_breakdown(&mut self) {
+        self.rollup_breakdown = match self.rollup_breakdown {
+            RollupBreakdown::Projects => RollupBreakdown::Clients,
+            RollupBreakdown::Clients => RollupBreakdown::Projects,
+        };
+    }
+
+    /// A day or period project breakdown, folded into clients when the pane
+    /// shows clients.
+    pub fn rollup_breakdown_of(&self, projects: &Breakdown) -> Breakdown {
+        match self.rollup_breakdown {
+            RollupBreakdown::Projects => projects.clone(),
+            RollupBreakdown::Clients => client_breakdown(projects, &self.project_clients()),
+        }
+    }
+
+    fn project_clients(&self) -> HashMap<u64, u64> {
+        self.projects
+            .iter()
+            .filter_map(|project| project.client_id.map(|client| (project.id, client)))
+            .collect()
+    }
+
+    /// Shows `pane` below the summary, or the calendar again when it is
+    /// already shown. Other panes follow the period list, so day focus ends.
+    fn toggle_rollup_pane(&mut self, pane: RollupPane) {
+        self.rollup_pane = if self.rollup_pane == pane {
+            RollupPane::Calendar
+        } else {
+            pane
+        };
+        let message = match self.rollup_pane {
+            RollupPane::Calendar => "Rollups: calendar.",
+            RollupPane::Comparison => "Rollups: comparing with earlier periods.",
+            RollupPane::Patterns => "Rollups: work patterns.",
+        };
+        if self.rollup_pane != RollupPane::Calendar {
+            self.rollup_focus = RollupFocus::Periods;
+        }
+        self.status = Some(message.to_string());
+    }
+
+    /// Work pattern of the selected period up to today, rebuilt when the
+    /// selection or the underlying data changed.
+    pub fn rollup_pattern(&mut self) -> Option<&WorkPattern> {
+        let period = self.rollup_selected_period()?;
+        let start = period.start;
+        let end = period.end.min(self.timezone.today());
+        let fresh = self
+            .rollup_pattern
+            .as_ref()
+            .is_some_and(|pattern| pattern.start == start && pattern.end == end);
+        if !fresh {
+            self.rollup_pattern = Some(self.build_rollup_pattern(start, end));
+        }
+        self.rollup_pattern.as_ref()
+    }
+
+    fn build_rollup_pattern(&self, start: NaiveDate, end: NaiveDate) -> WorkPattern {
+        let entries = match self
+            .selected_workspace
+            .as_ref()
+            .map(|workspace| workspace.id)
+        {
+            Some(workspace_id) => self.collect_rollup_entries(workspace_id, start, end),
+            None => self
+                .time_entries
+                .iter()
+                .filter(|entry| entry.stop.is_some())
+                .cloned()
+                .collect(),
+        };
+        let schedule = self.work_schedule();
+        build_work_pattern(&entries, start, end, self.timezone, |day| {
+            if schedule.hours_for(day) <= 0.0
+                || self.special_days.holidays.contains(day)
+                || self.special_days.is_absent(day)
+            {
+                GapDay::Off
+            } else if self.rollup_fetched_days.contains(&day) {
+                GapDay::Workday
+            } else {
+                GapDay::Unknown
+            }
+        })
     }
 
-    fn set_rollup_view(&mut self, view: RollupView) {
-        if self.rollup_view == view {
-            return;
+    /// Comparison for the selected period, rebuilt from cached entries when
+    /// the selection or the underlying data changed.
+    pub fn rollup_comparison(&mut self) -> Option<&PeriodComparison> {
+        let period = self.rollup_selected_period()?;
+        let (start, end) = (period.start, period.end);
+        let fresh = self
+            .rollup_comparison
+            .as_ref()
+            .is_some_and(|(view, comparison)| {
+                *view == self.rollup_view
+                    && comparison.current.start == start
+                    && comparison.current.end == end
+            });
+        if !fresh {
+            let comparison = self.build_rollup_comparison(start, end);
+            self.rollup_comparison = Some((self.rollup_view, comparison));
         }
-        self.rollup_view = view;
-        self.rebuild_rollups();
-        self.ensure_rollup_selections();
+        self.rollup_comparison
+            .as_ref()
+            .map(|(_, comparison)| comparison)
     }
 
-    fn shift_rollup_year(&mut self, direction: i32) {
-        let direction = direction.signum();
-        if direction == 0 {
-            return;
+    /// Steps back to the previous period and to the same period a year
+    /// earlier. Years have no separate last-year step; it is the previous one.
+    fn rollup_comparison_steps(&self) -> (PeriodStep, Option<PeriodStep>) {
+        match self.rollup_view {
+            RollupView::Weekly => (PeriodStep::Days(7), Some(PeriodStep::Days(364))),
+            RollupView::Monthly => (PeriodStep::Months(1), Some(PeriodStep::Months(12))),
+            RollupView::Quarterly => (PeriodStep::Months(3), Some(PeriodStep::Months(12))),
+            RollupView::Yearly => (PeriodStep::Months(12), None),
+            RollupView::Cycle => match self.selected_billing_cycle().map(|cycle| cycle.rule) {
+                Some(CycleRule::Weeks { weeks, .. }) => {
+                    let days = 7 * i64::from(weeks.max(1));
+                    // The whole number of cycles closest to a year back.
+                    let per_year = ((364 + days / 2) / days).max(1);
+                    (
+                        PeriodStep::Days(days),
+                        Some(PeriodStep::Days(days * per_year)),
+                    )
+                }
+                _ => (PeriodStep::Months(1), Some(PeriodStep::Months(12))),
+            },
         }
-        let next = clamp_year(self.rollup_year_cursor.saturating_add(direction));
-        if next == self.rollup_year_cursor {
-            return;
+    }
+
+    fn trend_label(&self, start: NaiveDate) -> String {
+        match self.rollup_view {
+            RollupView::Weekly => format!("W{:02}", start.iso_week().week()),
+            RollupView::Monthly => start.format("%b").to_string(),
+            RollupView::Quarterly => format!("Q{}", self.period_calendar().fiscal_quarter(start).1),
+            RollupView::Yearly => {
+                let year = self.period_calendar().fiscal_year(start);
+                format!("{:02}", year.rem_euclid(100))
+            }
+            RollupView::Cycle => start.format("%m-%d").to_string(),
         }
-        self.rollup_year_cursor = next;
-        self.rebuild_rollups();
-        self.ensure_rollup_selections();
-        self.status = Some(format!("Rollups year: {}", self.rollup_year_cursor));
     }
 
-    fn toggle_rollup_focus(&mut self) {
-        self.rollup_focus = match self.rollup_focus {
-            RollupFocus::Periods => RollupFocus::Days,
-            RollupFocus::Days => RollupFocus::Periods,
+    fn build_rollup_comparison(&self, start: NaiveDate, end: NaiveDate) -> PeriodComparison {
+        let (previous_step, year_step) = self.rollup_comparison_steps();
+        let trend_ranges: Vec<(NaiveDate, NaiveDate)> = (0..TREND_PERIODS)
+            .rev()
+            .filter_map(|steps| previous_step.back(start, end, steps))
+            .collect();
+        let last_year = year_step.and_then(|step| step.back(start, end, 1));
+        let earliest = trend_ranges
+            .iter()
+            .chain(&last_year)
+            .map(|(range_start, _)| *range_start)
+            .min()
+            .unwrap_or(start);
+
+        let workspace_id = self
+            .selected_workspace
+            .as_ref()
+            .map(|workspace| workspace.id);
+        let entries = match workspace_id {
+            Some(workspace_id) => self.collect_rollup_entries(workspace_id, earliest, end),
+            None => self
+                .time_entries
+                .iter()
+                .filter(|entry| entry.stop.is_some())
+                .cloned()
+                .collect(),
+        };
+        let mut fetched = workspace_id
+            .map(|workspace_id| {
+                self.collect_cached_day_coverage_for_range(workspace_id, earliest, end)
+            })
+            .unwrap_or_default();
+        fetched.extend(
+            self.date_range
+                .start_date()
+                .iter_days()
+                .take_while(|day| *day <= self.date_range.end_date()),
+        );
+
+        let rounding = self.rounding_policy();
+        let schedule = self.work_schedule();
+        let balance = BalanceContext::new(&schedule, &self.special_days, self.holiday_hours());
+        let project_clients = self.project_clients();
+        let figures = |(start, end): (NaiveDate, NaiveDate)| {
+            let (period, daily) = build_range_rollup(
+                &entries,
+                start,
+                end,
+                &rounding,
+                self.timezone,
+                self.split_at_midnight,
+            );
+            let hours = balance.period_effective_hours(&period, &daily)
+                + period_scope_adjustment(&period, &daily);
+            let (target, _) = balance.period_target_hours(&period);
+            PeriodFigures {
+                start,
+                end,
+                hours,
+                overtime: normalize_delta(hours - target),
+                clients: client_breakdown(&period.projects, &project_clients),
+                missing_days: start
+                    .iter_days()
+                    .take_while(|day| *day <= end)
+                    .filter(|day| self.is_rollup_day_included(*day) && !fetched.contains(day))
+                    .count(),
+            }
         };
+
+        let trend_figures: Vec<PeriodFigures> = trend_ranges.into_iter().map(figures).collect();
+        let current = trend_figures
+            .last()
+            .cloned()
+            .unwrap_or_else(|| figures((start, end)));
+        let previous = trend_figures
+            .len()
+            .checked_sub(2)
+            .and_then(|index| trend_figures.get(index))
+            .cloned();
+        PeriodComparison {
+            current,
+            previous,
+            last_year: last_year.map(figures),
+            trend: trend_figures
+                .iter()
+                .map(|figures| TrendPoint {
+                    label: self.trend_label(figures.start),
+                    hours: figures.hours,
+                })
+                .collect(),
+        }
     }
 
-    fn toggle_rollup_weekends(&mut self) {
-        self.rollups_include_weekends = !self.rollups_include_weekends;
-        self.align_rollup_day_selection_to_active_range();
-        self.ensure_rollup_day_selection();
-        if self.rollups_include_weekends {
-            self.status = Some("Rollups: weekends included.".to_string());
-        } else {
-            self.status = Some("Rollups: weekends excluded.".to_string());
+    /// Name for a breakdown row id, following the pane toggle.
+    pub fn rollup_breakdown_label(&self, id: Option<u64>) -> String {
+        match (self.rollup_breakdown, id) {
+            (RollupBreakdown::Projects, None) => "No Project".to_string(),
+            (RollupBreakdown::Projects, Some(id)) => self
+                .projects
+                .iter()
+                .find(|project| project.id == id)
+                .map(|project| project.name.clone())
+                .unwrap_or_else(|| format!("Project {id}")),
+            (RollupBreakdown::Clients, id) => self.client_label(id),
         }
     }
 
+    pub fn client_label(&self, id: Option<u64>) -> String {
+        let Some(id) = id else {
+            return "No Client".to_string();
+        };
+        self.client_names
+            .get(&id)
+            .cloned()
+            .or_else(|| {
+                self.projects
+                    .iter()
+                    .find(|project| project.client_id == Some(id))
+                    .and_then(|project| project.client_name.clone())
+            })
+            .unwrap_or_else(|| format!("Client {id}"))
+    }
+
     fn rollup_toggle_day(&self) -> NaiveDate {
         let daily = self.rollup_daily_for_selected_period();
         if let Some(index) = self.rollup_day_state.selected() {
@@ -2313,86 +4089,220 @@ impl App {
         self.date_range.end_date()
     }
 
-    fn persist_special_days(
-        &mut self,
-        previous_vacation: HashSet<NaiveDate>,
-        previous_sick: HashSet<NaiveDate>,
-    ) -> bool {
-        if let Err(err) = storage::write_special_days(&self.vacation_days, &self.sick_days) {
-            self.vacation_days = previous_vacation;
-            self.sick_days = previous_sick;
+    fn persist_special_days(&mut self, previous: SpecialDays) -> bool {
+        if let Err(err) = storage::write_special_days(&self.special_days) {
+            self.special_days = previous;
             let message = format!("Failed to save special days: {err}");
             self.status = Some(message.clone());
             self.set_toast(message, true);
             return false;
         }
+        self.rollup_comparison = None;
+        self.rollup_pattern = None;
+        self.revalidate_entries();
         true
     }
 
-    fn toggle_vacation_day(&mut self, day: NaiveDate) {
-        let previous_vacation = self.vacation_days.clone();
-        let previous_sick = self.sick_days.clone();
-        let was_vacation = self.vacation_days.contains(&day);
+    fn toggle_absence(&mut self, day: NaiveDate, key: &str) {
+        let previous = self.special_days.clone();
+        let was_marked = self.special_days.is_absent_as(day, key);
 
-        if was_vacation {
-            self.vacation_days.remove(&day);
+        if was_marked {
+            self.special_days.absences.remove(&day);
+            self.special_days.partial_days.remove(&day);
         } else {
-            self.vacation_days.insert(day);
-            self.sick_days.remove(&day);
+            self.special_days.absences.insert(day, key.to_string());
+        }
+
+        if !self.persist_special_days(previous) {
+            return;
+        }
+        self.announce_absence(day, was_marked.then_some(key));
+    }
+
+    /// Steps the day through no absence and each absence type in turn.
+    fn cycle_absence_type(&mut self, day: NaiveDate) {
+        let previous = self.special_days.clone();
+        let current = self.special_days.absences.get(&day).cloned();
+        let index = current.as_ref().and_then(|key| {
+            self.special_days
+                .types
+                .iter()
+                .position(|absence| absence.key == *key)
+        });
+        let next = match index {
+            None => self.special_days.types.first(),
+            Some(index) => self.special_days.types.get(index + 1),
         }
+        .map(|absence| absence.key.clone());
 
-        if !self.persist_special_days(previous_vacation, previous_sick) {
+        let removed = match next {
+            Some(key) => {
+                self.special_days.absences.insert(day, key);
+                None
+            }
+            None => {
+                self.special_days.absences.remove(&day);
+                self.special_days.partial_days.remove(&day);
+                current
+            }
+        };
+
+        if !self.persist_special_days(previous) {
             return;
         }
+        self.announce_absence(day, removed.as_deref());
+    }
 
+    fn announce_absence(&mut self, day: NaiveDate, removed: Option<&str>) {
         let date = day.format("%Y-%m-%d");
-        let message = if was_vacation {
-            format!("Removed vacation day on {date}.")
-        } else {
-            format!(
-                "Marked {date} as vacation day ({:.2}h target, {:.2}h credit).",
-                self.vacation_day_target_hours, self.vacation_day_credit_hours
-            )
+        let message = match (removed, self.special_days.absence(day)) {
+            (Some(key), _) => {
+                let name = self
+                    .special_days
+                    .types
+                    .iter()
+                    .find(|absence| absence.key == key)
+                    .map(|absence| absence.name.to_lowercase())
+                    .unwrap_or_else(|| key.to_string());
+                format!("Removed {name} on {date}.")
+            }
+            (None, Some(absence)) => format!(
+                "Marked {date} as {} ({:.2}h target, {:.2}h credit).",
+                absence.name.to_lowercase(),
+                absence.target_hours,
+                absence.credited_hours()
+            ),
+            (None, None) => format!("Cleared absence on {date}."),
+        };
+        let overdrawn = self
+            .special_days
+            .is_absent_as(day, VACATION)
+            .then(|| self.vacation_balance(day.year()))
+            .flatten()
+            .map(|balance| balance.remaining())
+            .filter(|remaining| *remaining < 0.0);
+        let message = match overdrawn {
+            Some(remaining) => format!(
+                "{message} Exceeds the {} vacation allowance by {:.1} day(s).",
+                day.year(),
+                -remaining
+            ),
+            None => message,
         };
         self.status = Some(message.clone());
-        self.set_toast(message, false);
+        self.set_toast(message, overdrawn.is_some());
+    }
+
+    /// Calendar year whose vacation allowance goes with the rollup year in
+    /// focus, which is a fiscal year.
+    pub fn vacation_allowance_year(&self) -> i32 {
+        self.period_calendar()
+            .calendar_year_of(self.rollup_year_cursor, self.date_range.end_date())
     }
 
-    fn toggle_sick_day(&mut self, day: NaiveDate) {
-        let previous_vacation = self.vacation_days.clone();
-        let previous_sick = self.sick_days.clone();
-        let was_sick = self.sick_days.contains(&day);
+    /// Vacation balance for `year`, when an allowance is configured.
+    pub fn vacation_balance(&self, year: i32) -> Option<VacationBalance> {
+        let allowance = self.vacation_allowance.as_ref()?;
+        let schedule = self.work_schedule();
+        // Each vacation day takes its share of the scheduled hours; days
+        // without scheduled hours or that are holidays take nothing.
+        let days = self
+            .special_days
+            .absences
+            .iter()
+            .filter(|(day, key)| *key == VACATION && !self.special_days.holidays.contains(**day))
+            .filter_map(|(day, _)| {
+                let scheduled = schedule.hours_for(*day);
+                let share = match self.special_days.partial_days.get(day) {
+                    Some(amount) => amount.fraction_of(scheduled),
+                    None if scheduled > 0.0 => 1.0,
+                    None => 0.0,
+                };
+                (share > 0.0).then_some((*day, share))
+            })
+            .collect();
+        allowance.balance(year, self.timezone.today(), &days)
+    }
 
-        if was_sick {
-            self.sick_days.remove(&day);
-        } else {
-            self.sick_days.insert(day);
-            self.vacation_days.remove(&day);
+    fn open_absence_input(&mut self, day: NaiveDate) {
+        if !self.special_days.is_absent(day) {
+            let message = "Mark the day as an absence (k, j or t) first.";
+            self.status = Some(message.to_string());
+            self.set_toast(message, true);
+            return;
         }
+        self.absence_input = self
+            .special_days
+            .partial_days
+            .get(&day)
+            .map(|amount| match amount {
+                AbsenceAmount::Fraction(fraction) => format!("{fraction}"),
+                AbsenceAmount::Hours(hours) => format!("{hours}h"),
+            })
+            .unwrap_or_default();
+        self.absence_input_day = Some(day);
+        self.status = None;
+        self.mode = Mode::AbsenceInput;
+    }
+
+    fn apply_absence_input(&mut self) {
+        let Some(day) = self.absence_input_day else {
+            self.mode = Mode::Rollups;
+            return;
+        };
+        let amount = match AbsenceAmount::parse(&self.absence_input) {
+            Ok(amount) => amount,
+            Err(message) => {
+                self.status = Some(message);
+                return;
+            }
+        };
 
-        if !self.persist_special_days(previous_vacation, previous_sick) {
+        let previous = self.special_days.clone();
+        match amount {
+            Some(amount) => self.special_days.partial_days.insert(day, amount),
+            None => self.special_days.partial_days.remove(&day),
+        };
+        self.absence_input.clear();
+        self.absence_input_day = None;
+        self.mode = Mode::Rollups;
+        if !self.persist_special_days(previous) {
             return;
         }
 
         let date = day.format("%Y-%m-%d");
-        let message = if was_sick {
-            format!("Removed sick day on {date}.")
-        } else {
-            format!(
-                "Marked {date} as sick day ({:.2}h target, {:.2}h credit).",
-                self.sick_day_target_hours, self.sick_day_credit_hours
-            )
+        let message = match amount {
+            Some(amount) => format!("Set absence on {date} to {}.", amount.label()),
+            None => format!("Set absence on {date} to the whole day."),
         };
         self.status = Some(message.clone());
         self.set_toast(message, false);
     }
 
+    pub fn absence_input_value(&self) -> &str {
+        &self.absence_input
+    }
+
+    pub fn absence_input_day(&self) -> Option<NaiveDate> {
+        self.absence_input_day
+    }
+
     fn open_rollup_refetch_confirm(&mut self) {
         let Some(plan) = self.selected_rollup_refetch_plan() else {
             self.status = Some("Select a rollup period/day first.".to_string());
             self.set_toast("Select a rollup period/day first.", true);
             return;
         };
+        if plan.batches.is_empty() {
+            let message = format!(
+                "Nothing to refetch for {}: {} day(s) fetched in the last {}h.",
+                plan.scope_label, plan.fresh_days, FRESH_FOR_HOURS
+            );
+            self.status = Some(message.clone());
+            self.set_toast(message, false);
+            return;
+        }
         self.refetch_plan = Some(plan);
         self.mode = Mode::RefetchConfirm;
     }
@@ -2402,11 +4312,12 @@ impl App {
             let daily = self.rollup_daily_for_selected_period();
             if let Some(index) = self.rollup_day_state.selected() {
                 if let Some(day) = daily.get(index) {
-                    return Some(RefetchPlan {
-                        start: day.date,
-                        end: day.date,
-                        scope_label: format!("Day {}", day.date.format("%Y-%m-%d")),
-                    });
+                    return Some(self.build_refetch_plan(
+                        day.date,
+                        day.date,
+                        format!("Day {}", day.date.format("%Y-%m-%d")),
+                        true,
+                    ));
                 }
             }
         }
@@ -2415,13 +4326,100 @@ impl App {
         let scope = match self.rollup_view {
             RollupView::Weekly => "Week",
             RollupView::Monthly => "Month",
+            RollupView::Quarterly => "Quarter",
             RollupView::Yearly => "Year",
+            RollupView::Cycle => self
+                .selected_billing_cycle()
+                .map_or("Cycle", |cycle| cycle.name.as_str()),
         };
-        Some(RefetchPlan {
-            start: period.start,
-            end: period.end,
-            scope_label: format!("{scope} {}", period.label),
-        })
+        Some(self.build_refetch_plan(
+            period.start,
+            period.end,
+            format!("{scope} {}", period.label),
+            false,
+        ))
+    }
+
+    /// A selected day is always refetched. Periods skip fresh and future
+    /// days, and weekends while rollups hide them.
+    fn build_refetch_plan(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        scope_label: String,
+        single_day: bool,
+    ) -> RefetchPlan {
+        let fetch_times = self
+            .selected_workspace
+            .as_ref()
+            .map(|workspace| self.cached_day_fetch_times(workspace.id, start, end))
+            .unwrap_or_default();
+        let today = self.timezone.today();
+        let mut days = Vec::new();
+        let mut skipped_days = 0;
+        for date in date_span(start, end) {
+            if !single_day && (date > today || !self.is_rollup_day_included(date)) {
+                skipped_days += 1;
+                continue;
+            }
+            days.push(DayCoverage {
+                date,
+                fetched_at: fetch_times.get(&date).copied(),
+                day_end: self.timezone.end_of_day(date),
+            });
+        }
+
+        let batches = if single_day {
+            days.iter().map(RefetchBatch::of_day).collect()
+        } else {
+            plan_batches(&days, self.timezone.now())
+        };
+        let planned_days: usize = batches.iter().map(RefetchBatch::days).sum();
+        RefetchPlan {
+            start,
+            end,
+            scope_label,
+            fresh_days: days.len() - planned_days,
+            skipped_days,
+            batches,
+        }
+    }
+
+    /// Latest fetch per day among cached ranges. Fetches made before a day
+    /// began say nothing about it and are ignored.
+    fn cached_day_fetch_times(
+        &self,
+        workspace_id: u64,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> HashMap<NaiveDate, DateTime<FixedOffset>> {
+        let mut latest: HashMap<NaiveDate, DateTime<FixedOffset>> = HashMap::new();
+        let Some(cache) = self.cache.as_ref() else {
+            return latest;
+        };
+
+        for (key, cached) in &cache.time_entries {
+            let Some((cached_workspace, cached_start, cached_end)) = parse_cache_key_bounds(key)
+            else {
+                continue;
+            };
+            if cached_workspace != workspace_id || cached_end < start || cached_start > end {
+                continue;
+            }
+            let Ok(fetched_at) = DateTime::parse_from_rfc3339(&cached.fetched_at) else {
+                continue;
+            };
+            for day in date_span(cached_start.max(start), cached_end.min(end)) {
+                if fetched_at < self.timezone.start_of_day(day) {
+                    continue;
+                }
+                latest
+                    .entry(day)
+                    .and_modify(|current| *current = (*current).max(fetched_at))
+                    .or_insert(fetched_at);
+            }
+        }
+        latest
     }
 
     fn execute_rollup_refetch(&mut self) {
@@ -2453,25 +4451,25 @@ impl App {
 
         self.ensure_quota_today();
         let client = TogglClient::new(token);
-        let dates = date_span(plan.start, plan.end);
-        let total_days = dates.len();
+        let total_days: usize = plan.batches.iter().map(RefetchBatch::days).sum();
         let mut fetched_days: Vec<NaiveDate> = Vec::new();
+        let mut fetched_batches = 0;
         let mut stop_reason: Option<String> = None;
 
-        for (index, day) in dates.iter().enumerate() {
+        for batch in &plan.batches {
             if self.quota_remaining() == 0 {
                 stop_reason = Some("local quota reached".to_string());
-                if index < total_days {
-                    break;
-                }
+                break;
             }
 
             self.consume_quota();
-            let (start_rfc, end_rfc) = DateRange::from_bounds(*day, *day).as_rfc3339();
+            let (start_rfc, end_rfc) =
+                DateRange::from_bounds(batch.start, batch.end, self.timezone).as_rfc3339();
             match client.fetch_time_entries(&start_rfc, &end_rfc) {
                 Ok(entries) => {
                     self.update_cache_time_entries(workspace_id, &start_rfc, &end_rfc, &entries);
-                    fetched_days.push(*day);
+                    fetched_days.extend(date_span(batch.start, batch.end));
+                    fetched_batches += 1;
                 }
                 Err(TogglError::Unauthorized) => {
                     self.refetch_plan = None;
@@ -2499,22 +4497,22 @@ impl App {
         self.refresh_intent = RefreshIntent::CacheOnly;
         self.needs_refresh = true;
 
-        if fetched_days.len() == total_days {
-            let message = format!("Refetched {} day(s) for {}.", total_days, plan.scope_label);
+        if fetched_batches == plan.batches.len() {
+            let message = format!(
+                "Refetched {} day(s) in {} call(s) for {}.",
+                total_days, fetched_batches, plan.scope_label
+            );
             self.status = Some(message.clone());
             self.set_toast(message, false);
             return;
         }
 
-        let skipped_days = if fetched_days.len() < total_days {
-            dates
-                .iter()
-                .skip(fetched_days.len())
-                .copied()
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        };
+        let skipped_days = plan
+            .batches
+            .iter()
+            .skip(fetched_batches)
+            .flat_map(|batch| date_span(batch.start, batch.end))
+            .collect::<Vec<_>>();
         let reason = stop_reason.unwrap_or_else(|| "fetch interrupted".to_string());
         let message = format!(
             "Partial refetch {}/{} day(s). Cached: {}. Stopped: {}. Skipped: {}.",
@@ -2532,7 +4530,9 @@ impl App {
         match self.rollup_view {
             RollupView::Weekly => &self.rollups.weekly,
             RollupView::Monthly => &self.rollups.monthly,
+            RollupView::Quarterly => &self.rollups.quarterly,
             RollupView::Yearly => &self.rollups.yearly,
+            RollupView::Cycle => &self.rollups.cycle,
         }
     }
 
@@ -2540,7 +4540,9 @@ impl App {
         match view {
             RollupView::Weekly => &self.rollups.weekly,
             RollupView::Monthly => &self.rollups.monthly,
+            RollupView::Quarterly => &self.rollups.quarterly,
             RollupView::Yearly => &self.rollups.yearly,
+            RollupView::Cycle => &self.rollups.cycle,
         }
     }
 
@@ -2548,7 +4550,9 @@ impl App {
         match view {
             RollupView::Weekly => &mut self.rollup_week_state,
             RollupView::Monthly => &mut self.rollup_month_state,
+            RollupView::Quarterly => &mut self.rollup_quarter_state,
             RollupView::Yearly => &mut self.rollup_year_state,
+            RollupView::Cycle => &mut self.rollup_cycle_state,
         }
     }
 
@@ -2556,7 +4560,9 @@ impl App {
         match view {
             RollupView::Weekly => &self.rollup_week_state,
             RollupView::Monthly => &self.rollup_month_state,
+            RollupView::Quarterly => &self.rollup_quarter_state,
             RollupView::Yearly => &self.rollup_year_state,
+            RollupView::Cycle => &self.rollup_cycle_state,
         }
     }
 
@@ -2565,6 +4571,13 @@ impl App {
         self.rollup_periods().get(index)
     }
 
+    pub fn period_calendar(&self) -> PeriodCalendar {
+        PeriodCalendar {
+            week_start: self.rollups_week_start,
+            fiscal_year_start: self.rollups_fiscal_year_start,
+        }
+    }
+
     pub fn rollup_year_cursor(&self) -> i32 {
         self.rollup_year_cursor
     }
@@ -2599,44 +4612,27 @@ impl App {
         missing
     }
 
-    pub fn vacation_days(&self) -> &HashSet<NaiveDate> {
-        &self.vacation_days
-    }
-
-    pub fn sick_days(&self) -> &HashSet<NaiveDate> {
-        &self.sick_days
-    }
-
-    pub fn is_vacation_day(&self, day: NaiveDate) -> bool {
-        self.vacation_days.contains(&day)
-    }
-
-    pub fn is_sick_day(&self, day: NaiveDate) -> bool {
-        self.sick_days.contains(&day)
-    }
-
-    pub fn vacation_day_target_hours(&self) -> f64 {
-        self.vacation_day_target_hours
-    }
-
-    pub fn vacation_day_credit_hours(&self) -> f64 {
-        self.vacation_day_credit_hours
-    }
-
-    pub fn sick_day_target_hours(&self) -> f64 {
-        self.sick_day_target_hours
+    pub fn special_days(&self) -> &SpecialDays {
+        &self.special_days
     }
 
-    pub fn sick_day_credit_hours(&self) -> f64 {
-        self.sick_day_credit_hours
+    pub fn holiday_name(&self, day: NaiveDate) -> Option<String> {
+        self.special_days.holidays.name(day)
     }
 
-    pub fn credit_vacation_days_as_worked(&self) -> bool {
-        self.credit_vacation_days_as_worked
+    pub fn holiday_hours(&self) -> HolidayHours {
+        HolidayHours {
+            target_hours: self.holiday_target_hours,
+            credit_hours: self.holiday_credit_hours,
+        }
     }
 
-    pub fn credit_sick_days_as_worked(&self) -> bool {
-        self.credit_sick_days_as_worked
+    pub fn work_schedule(&self) -> WorkSchedule {
+        WorkSchedule::new(
+            self.target_hours,
+            self.rollups_include_weekends,
+            self.schedule_changes.clone(),
+        )
     }
 
     pub fn rollup_daily_for_selected_period(&self) -> Vec<&DailyTotal> {
@@ -2678,7 +4674,9 @@ impl App {
     fn ensure_rollup_selections(&mut self) {
         self.ensure_rollup_state_for_view(RollupView::Weekly);
         self.ensure_rollup_state_for_view(RollupView::Monthly);
+        self.ensure_rollup_state_for_view(RollupView::Quarterly);
         self.ensure_rollup_state_for_view(RollupView::Yearly);
+        self.ensure_rollup_state_for_view(RollupView::Cycle);
         self.ensure_rollup_day_selection();
     }
 
@@ -3069,15 +5067,27 @@ impl App {
     fn update_range_from_input(&self) -> Result<DateRange, String> {
         let start_text = self.date_start_input.trim();
         let end_text = self.date_end_input.trim();
-        if start_text.is_empty() || end_text.is_empty() {
-            return Err("Start and end date are required.".to_string());
-        }
-        let start_date = parse_date(start_text)?;
-        let end_date = parse_date(end_text)?;
+        if start_text.is_empty() {
+            return Err("Start date is required.".to_string());
+        }
+        let today = self.timezone.today();
+        let (start_date, start_end) =
+            parse_range_expression(start_text, today, self.rollups_week_start)?;
+        // An empty end takes the whole span of the start expression.
+        let end_date = if end_text.is_empty() {
+            start_end
+        } else {
+            parse_range_expression(end_text, today, self.rollups_week_start)?.1
+        };
         if start_date > end_date {
             return Err("Start date cannot be after end date.".to_string());
         }
-        Ok(DateRange::from_bounds(start_date, end_date))
+        Ok(DateRange::from_bounds_snapped(
+            start_date,
+            end_date,
+            self.rollups_week_start,
+            self.timezone,
+        ))
     }
 
     fn active_date_input_mut(&mut self) -> &mut String {
@@ -3184,7 +5194,7 @@ impl App {
             }
 
             for entry in &cached.data {
-                let Some(entry_date) = parse_entry_date(entry) else {
+                let Some(entry_date) = parse_entry_date(entry, self.timezone) else {
                     continue;
                 };
                 if entry_date >= start_date && entry_date <= end_date {
@@ -3222,6 +5232,35 @@ impl App {
         })
     }
 
+    fn rounding_policy(&self) -> RoundingPolicy {
+        RoundingPolicy::new(
+            self.rounding,
+            self.rounding_scope,
+            self.rounding_rules.clone(),
+            &self.projects,
+        )
+    }
+
+    fn split_window(&self) -> Option<DayWindow> {
+        self.split_at_midnight.then(|| DayWindow {
+            start: self.date_range.start_date(),
+            end: self.date_range.end_date(),
+            timezone: self.timezone,
+        })
+    }
+
+    /// Drops entries from the day before the active range unless they run
+    /// past midnight into it.
+    fn clip_overnight_entries(&self, entries: &mut Vec<TimeEntry>) {
+        let Some(window) = self.split_window() else {
+            return;
+        };
+        entries.retain(|entry| {
+            parse_entry_date(entry, self.timezone).is_some_and(|date| date >= window.start)
+                || (entry.stop.is_some() && window.clip_seconds(entry, entry.duration) > 0)
+        });
+    }
+
     fn collect_cached_entries_for_range(
         &self,
         workspace_id: u64,
@@ -3243,7 +5282,7 @@ impl App {
             }
 
             for entry in &cached.data {
-                let Some(entry_date) = parse_entry_date(entry) else {
+                let Some(entry_date) = parse_entry_date(entry, self.timezone) else {
                     continue;
                 };
                 if entry_date >= start && entry_date <= end {
@@ -3265,6 +5304,16 @@ impl App {
         let _ = storage::write_cache(cache);
     }
 
+    fn update_cache_me(&mut self, me: &Me) {
+        let cached = CachedData {
+            data: me.clone(),
+            fetched_at: storage::now_rfc3339(),
+        };
+        let cache = self.cache_mut();
+        cache.me = Some(cached);
+        let _ = storage::write_cache(cache);
+    }
+
     fn update_cache_projects(&mut self, workspace_id: u64, projects: &[Project]) {
         let cached = CachedData {
             data: projects.to_vec(),
@@ -3367,6 +5416,64 @@ impl App {
         }
     }
 
+    fn resolve_profile_timezone(&mut self, client: &TogglClient, allow_api: bool) -> bool {
+        // The profile only supplies the timezone, so an override makes it
+        // unnecessary and a recent copy is reused.
+        let cached_fresh = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.me.as_ref())
+            .and_then(|cached| parse_cached_time(&cached.fetched_at))
+            .is_some_and(|fetched_at| {
+                Local::now() - fetched_at < chrono::Duration::hours(FRESH_FOR_HOURS)
+            });
+        if !allow_api
+            || self.timezone_override.is_some()
+            || cached_fresh
+            || self.quota_remaining() == 0
+        {
+            return true;
+        }
+
+        self.consume_quota();
+        match client.fetch_me() {
+            Ok(me) => {
+                self.update_cache_me(&me);
+                true
+            }
+            Err(TogglError::Unauthorized) => {
+                self.handle_error(TogglError::Unauthorized);
+                false
+            }
+            Err(_) => true,
+        }
+    }
+
+    pub fn timezone_source(&self) -> &'static str {
+        if self.timezone_override.is_some() {
+            "override"
+        } else if self.cache.as_ref().and_then(profile_timezone).is_some() {
+            "Toggl profile"
+        } else {
+            "system"
+        }
+    }
+
+    fn resolved_timezone(&self) -> Timezone {
+        self.timezone_override
+            .or_else(|| self.cache.as_ref().and_then(profile_timezone))
+            .unwrap_or_default()
+    }
+
+    fn apply_timezone(&mut self) {
+        let timezone = self.resolved_timezone();
+        if timezone == self.timezone {
+            return;
+        }
+        self.timezone = timezone;
+        self.date_range = self.date_range.with_timezone(timezone);
+    }
+
     fn resolve_client_names(
         &mut self,
         client: &TogglClient,
@@ -3510,20 +5617,62 @@ impl App {
     }
 }
 
+fn parse_signed_hours(value: &str) -> Result<f64, String> {
+    let value = value.trim().replace(',', ".");
+    if value.is_empty() {
+        return Err("Opening balance is required.".to_string());
+    }
+    let parsed = value
+        .strip_prefix('+')
+        .unwrap_or(&value)
+        .parse::<f64>()
+        .ok()
+        .filter(|hours| hours.is_finite());
+    parsed.ok_or_else(|| "Use signed hours (e.g. -4.50 or 12).".to_string())
+}
+
+fn read_ics_holidays(path: &str) -> Result<Vec<holidays::Holiday>, String> {
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .ok_or_else(|| "Home directory not found.".to_string())?,
+        None => std::path::PathBuf::from(path),
+    };
+    let content = std::fs::read_to_string(&path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    let imported = holidays::parse_ics(&content)?;
+    if imported.is_empty() {
+        return Err(format!("No events found in {}.", path.display()));
+    }
+    Ok(imported)
+}
+
 fn parse_cached_time(value: &str) -> Option<DateTime<Local>> {
     DateTime::parse_from_rfc3339(value)
         .ok()
         .map(|dt| dt.with_timezone(&Local))
 }
 
+// Range bounds are written in the bucketing timezone, so their own offset
+// already yields the intended calendar day.
 fn parse_rfc3339_date(value: &str) -> Option<NaiveDate> {
     DateTime::parse_from_rfc3339(value)
         .ok()
-        .map(|dt| dt.with_timezone(&Local).date_naive())
+        .map(|dt| dt.date_naive())
 }
 
-fn parse_entry_date(entry: &TimeEntry) -> Option<NaiveDate> {
-    parse_rfc3339_date(&entry.start)
+fn parse_entry_date(entry: &TimeEntry, timezone: Timezone) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(&entry.start)
+        .ok()
+        .map(|dt| timezone.date_of(&dt))
+}
+
+fn profile_timezone(cache: &CacheFile) -> Option<Timezone> {
+    cache
+        .me
+        .as_ref()
+        .and_then(|cached| cached.data.timezone.as_deref())
+        .and_then(Timezone::parse)
 }
 
 fn missing_project_ids(entries: &[TimeEntry], projects: &[Project]) -> HashSet<u64> {
@@ -3623,11 +5772,32 @@ pub struct ToastView {
     pub is_error: bool,
 }
 
+/// Figures of one rollup period, as printed by `timeshit rollup`.
+pub struct RollupReport {
+    pub label: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days: usize,
+    pub tracked_hours: f64,
+    /// Hours credited for holidays and absences on top of tracked time.
+    pub credited_hours: f64,
+    pub worked_hours: f64,
+    pub target_hours: f64,
+    pub target_days: usize,
+    pub overtime_hours: f64,
+    pub missing_days: usize,
+}
+
 pub struct RefetchPlanView {
     pub scope_label: String,
     pub start: String,
     pub end: String,
-    pub days: usize,
+    pub unfetched_days: usize,
+    pub stale_days: usize,
+    pub fresh_days: usize,
+    pub skipped_days: usize,
+    /// Day spans in fetch order, one call each.
+    pub batches: Vec<String>,
     pub estimated_calls: u32,
     pub remaining_calls: u32,
 }
@@ -0,0 +1,556 @@
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+use crate::rollups::{DailyTotal, PeriodRollup};
+use crate::schedule::WorkSchedule;
+use crate::storage::SpecialDays;
+
+/// Target and credited hours for public holidays.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HolidayHours {
+    pub target_hours: f64,
+    pub credit_hours: f64,
+}
+
+/// The schedule and marked days that turn tracked hours into worked,
+/// target and overtime hours. Holidays win over absences on the same day.
+#[derive(Debug, Clone, Copy)]
+pub struct BalanceContext<'a> {
+    schedule: &'a WorkSchedule,
+    special_days: &'a SpecialDays,
+    holiday_hours: HolidayHours,
+}
+
+impl<'a> BalanceContext<'a> {
+    pub fn new(
+        schedule: &'a WorkSchedule,
+        special_days: &'a SpecialDays,
+        holiday_hours: HolidayHours,
+    ) -> Self {
+        Self {
+            schedule,
+            special_days,
+            holiday_hours,
+        }
+    }
+
+    /// Hours credited as worked for a holiday or absence on `day`.
+    pub fn credit_hours(&self, day: NaiveDate) -> f64 {
+        if self.special_days.holidays.contains(day) {
+            return self.holiday_hours.credit_hours;
+        }
+        let Some(absence) = self.special_days.absence(day) else {
+            return 0.0;
+        };
+        let credit = absence.credited_hours();
+        match self.special_days.partial_days.get(&day) {
+            Some(amount) => amount.credit_hours(credit),
+            None => credit,
+        }
+    }
+
+    /// Tracked hours on `day` combined with any credit. A full-day credit is
+    /// a floor; a partial absence credits the missing part on top.
+    pub fn effective_hours(&self, day: NaiveDate, worked_hours: f64) -> f64 {
+        let credit = self.credit_hours(day);
+        if self.special_days.partial_days.contains_key(&day)
+            && !self.special_days.holidays.contains(day)
+        {
+            worked_hours + credit
+        } else {
+            worked_hours.max(credit)
+        }
+    }
+
+    pub fn target_hours(&self, day: NaiveDate) -> f64 {
+        if self.special_days.holidays.contains(day) {
+            return self.holiday_hours.target_hours;
+        }
+        let Some(absence) = self.special_days.absence(day) else {
+            return self.schedule.hours_for(day);
+        };
+        match self.special_days.partial_days.get(&day) {
+            Some(amount) => amount.target_hours(self.schedule.hours_for(day), absence.target_hours),
+            None => absence.target_hours,
+        }
+    }
+
+    pub fn overtime_hours(&self, day: NaiveDate, worked_hours: f64) -> f64 {
+        normalize_delta(self.effective_hours(day, worked_hours) - self.target_hours(day))
+    }
+
+    /// Effective hours over the days of `daily` inside `period`.
+    pub fn period_effective_hours(&self, period: &PeriodRollup, daily: &[DailyTotal]) -> f64 {
+        days_in_period(period, daily, period.end)
+            .map(|day| self.effective_hours(day.date, hours_from_seconds(day.seconds)))
+            .sum()
+    }
+
+    /// Target hours for `period` and the number of days with a target.
+    pub fn period_target_hours(&self, period: &PeriodRollup) -> (f64, usize) {
+        period
+            .start
+            .iter_days()
+            .take_while(|day| *day <= period.end)
+            .map(|day| self.target_hours(day))
+            .fold((0.0, 0), |(total, days), target| {
+                (total + target, days + usize::from(target > 0.0))
+            })
+    }
+
+    pub fn period_overtime_hours(&self, period: &PeriodRollup, daily: &[DailyTotal]) -> f64 {
+        let (target, _) = self.period_target_hours(period);
+        normalize_delta(self.period_effective_hours(period, daily) - target)
+    }
+
+    /// Effective hours and the number of days with any, up to `cutoff`.
+    pub fn period_worked_totals_until(
+        &self,
+        period: &PeriodRollup,
+        daily: &[DailyTotal],
+        cutoff: NaiveDate,
+    ) -> (f64, usize) {
+        days_in_period(period, daily, cutoff)
+            .map(|day| self.effective_hours(day.date, hours_from_seconds(day.seconds)))
+            .filter(|hours| *hours > 0.0)
+            .fold((0.0, 0), |(total, days), hours| (total + hours, days + 1))
+    }
+
+    /// Running overtime balance at the end of each day in `daily`, starting
+    /// from `opening_hours`. Days outside `counted` carry the balance over.
+    pub fn running_balance(
+        &self,
+        opening_hours: f64,
+        daily: &[DailyTotal],
+        counted: &HashSet<NaiveDate>,
+    ) -> Vec<(NaiveDate, f64)> {
+        let mut balance = opening_hours;
+        daily
+            .iter()
+            .map(|day| {
+                if counted.contains(&day.date) {
+                    let worked = self.effective_hours(day.date, hours_from_seconds(day.seconds));
+                    balance += worked - self.target_hours(day.date);
+                }
+                (day.date, balance)
+            })
+            .collect()
+    }
+}
+
+/// Extra hours from rounding the period as a whole rather than day by day.
+pub fn period_scope_adjustment(period: &PeriodRollup, daily: &[DailyTotal]) -> f64 {
+    let daily_seconds: i64 = days_in_period(period, daily, period.end)
+        .map(|day| day.seconds)
+        .sum();
+    hours_from_seconds(period.seconds - daily_seconds)
+}
+
+pub fn hours_from_seconds(seconds: i64) -> f64 {
+    seconds as f64 / 3600.0
+}
+
+/// Treats deltas under 0.005h (18 seconds) as zero.
+pub fn normalize_delta(value: f64) -> f64 {
+    if value.abs() < 0.005 { 0.0 } else { value }
+}
+
+fn days_in_period<'d>(
+    period: &PeriodRollup,
+    daily: &'d [DailyTotal],
+    cutoff: NaiveDate,
+) -> impl Iterator<Item = &'d DailyTotal> {
+    let start = period.start;
+    let end = period.end.min(cutoff);
+    daily
+        .iter()
+        .filter(move |day| day.date >= start && day.date <= end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::absences::{AbsenceType, SICK, VACATION};
+    use crate::holidays::{Holiday, HolidayCalendar};
+    use crate::rollups::Breakdown;
+    use crate::schedule::{AbsenceAmount, ScheduleChange, WeekdayHours};
+    use std::collections::HashMap;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn daily_total(year: i32, month: u32, day: u32, hours: f64) -> DailyTotal {
+        DailyTotal {
+            date: date(year, month, day),
+            seconds: (hours * 3600.0).round() as i64,
+            raw_seconds: (hours * 3600.0).round() as i64,
+            projects: Breakdown::new(),
+        }
+    }
+
+    fn period(label: &str, start: NaiveDate, end: NaiveDate) -> PeriodRollup {
+        PeriodRollup {
+            label: label.to_string(),
+            start,
+            end,
+            days: (end - start).num_days() as usize + 1,
+            seconds: 0,
+            raw_seconds: 0,
+            projects: Breakdown::new(),
+        }
+    }
+
+    fn marked_days(
+        days: &[NaiveDate],
+        key: &str,
+        vacation: AbsenceType,
+        sick: AbsenceType,
+    ) -> SpecialDays {
+        SpecialDays {
+            absences: days.iter().map(|day| (*day, key.to_string())).collect(),
+            types: vec![vacation, sick],
+            ..SpecialDays::default()
+        }
+    }
+
+    fn weekdays(hours: f64) -> WorkSchedule {
+        WorkSchedule::new(hours, false, Vec::new())
+    }
+
+    const NO_HOLIDAY_HOURS: HolidayHours = HolidayHours {
+        target_hours: 0.0,
+        credit_hours: 0.0,
+    };
+
+    fn approx_eq(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.000_1,
+            "expected {expected:.4}, got {actual:.4}"
+        );
+    }
+
+    #[test]
+    fn sick_day_credit_and_target_hours_are_calculated_separately() {
+        let period = period("Mar 2026", date(2026, 3, 1), date(2026, 3, 31));
+        let daily = vec![
+            daily_total(2026, 3, 2, 0.0),
+            daily_total(2026, 3, 3, 0.0),
+            daily_total(2026, 3, 4, 0.0),
+            daily_total(2026, 3, 5, 0.0),
+            daily_total(2026, 3, 6, 10.5),
+            daily_total(2026, 3, 9, 14.0),
+            daily_total(2026, 3, 10, 10.5),
+            daily_total(2026, 3, 11, 9.5),
+            daily_total(2026, 3, 12, 8.0),
+            daily_total(2026, 3, 13, 10.0),
+            daily_total(2026, 3, 16, 11.75),
+            daily_total(2026, 3, 17, 6.0),
+            daily_total(2026, 3, 18, 8.75),
+            daily_total(2026, 3, 19, 10.25),
+            daily_total(2026, 3, 20, 10.25),
+            daily_total(2026, 3, 23, 9.25),
+            daily_total(2026, 3, 24, 7.0),
+            daily_total(2026, 3, 25, 7.0),
+            daily_total(2026, 3, 26, 11.5),
+            daily_total(2026, 3, 27, 4.0),
+        ];
+        let special_days = marked_days(
+            &[
+                date(2026, 3, 2),
+                date(2026, 3, 3),
+                date(2026, 3, 4),
+                date(2026, 3, 5),
+            ],
+            SICK,
+            AbsenceType::vacation(8.0, 7.6, false),
+            AbsenceType::sick(8.0, 7.6, true),
+        );
+        let schedule = weekdays(8.0);
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        let total = balance.period_effective_hours(&period, &daily);
+        let (target, target_days) = balance.period_target_hours(&period);
+        let delta = normalize_delta(total - target);
+        let overtime = balance.period_overtime_hours(&period, &daily);
+
+        approx_eq(total, 178.65);
+        approx_eq(target, 176.0);
+        approx_eq(delta, 2.65);
+        approx_eq(overtime, 2.65);
+        assert_eq!(target_days, 22);
+    }
+
+    #[test]
+    fn vacation_day_credit_and_target_hours_are_calculated_separately() {
+        let period = period("Apr 2026", date(2026, 4, 6), date(2026, 4, 10));
+        let daily = vec![
+            daily_total(2026, 4, 6, 0.0),
+            daily_total(2026, 4, 7, 8.0),
+            daily_total(2026, 4, 8, 8.0),
+            daily_total(2026, 4, 9, 8.0),
+            daily_total(2026, 4, 10, 8.0),
+        ];
+        let special_days = marked_days(
+            &[date(2026, 4, 6)],
+            VACATION,
+            AbsenceType::vacation(8.25, 6.75, true),
+            AbsenceType::sick(8.0, 7.5, false),
+        );
+        let schedule = weekdays(8.0);
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        let total = balance.period_effective_hours(&period, &daily);
+        let (target, target_days) = balance.period_target_hours(&period);
+
+        approx_eq(total, 38.75);
+        approx_eq(target, 40.25);
+        approx_eq(balance.effective_hours(date(2026, 4, 6), 0.0), 6.75);
+        approx_eq(balance.target_hours(date(2026, 4, 6)), 8.25);
+        assert_eq!(target_days, 5);
+    }
+
+    #[test]
+    fn full_day_credit_is_a_floor_not_an_addition() {
+        let special_days = marked_days(
+            &[date(2026, 4, 6)],
+            VACATION,
+            AbsenceType::vacation(8.0, 8.0, true),
+            AbsenceType::sick(8.0, 8.0, true),
+        );
+        let schedule = weekdays(8.0);
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        approx_eq(balance.effective_hours(date(2026, 4, 6), 3.0), 8.0);
+        approx_eq(balance.effective_hours(date(2026, 4, 6), 9.5), 9.5);
+        approx_eq(balance.overtime_hours(date(2026, 4, 6), 9.5), 1.5);
+    }
+
+    #[test]
+    fn partial_absence_adds_credit_to_worked_hours() {
+        let mut special_days = SpecialDays {
+            absences: HashMap::from([
+                (date(2026, 4, 7), VACATION.to_string()),
+                (date(2026, 4, 8), SICK.to_string()),
+            ]),
+            partial_days: HashMap::from([
+                (date(2026, 4, 7), AbsenceAmount::Fraction(0.5)),
+                (date(2026, 4, 8), AbsenceAmount::Hours(2.0)),
+            ]),
+            types: vec![
+                AbsenceType::vacation(8.0, 8.0, true),
+                AbsenceType::sick(8.0, 8.0, true),
+            ],
+            ..SpecialDays::default()
+        };
+        let schedule = weekdays(8.0);
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        approx_eq(balance.effective_hours(date(2026, 4, 7), 4.0), 8.0);
+        approx_eq(balance.effective_hours(date(2026, 4, 8), 6.0), 8.0);
+        approx_eq(balance.target_hours(date(2026, 4, 7)), 8.0);
+
+        special_days.types[1].target_hours = 0.0;
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+        approx_eq(balance.target_hours(date(2026, 4, 8)), 6.0);
+    }
+
+    #[test]
+    fn holidays_use_their_own_hours_over_absences() {
+        let mut special_days = marked_days(
+            &[date(2026, 12, 25)],
+            VACATION,
+            AbsenceType::vacation(8.0, 8.0, true),
+            AbsenceType::sick(8.0, 8.0, true),
+        );
+        special_days.holidays = HolidayCalendar::new(
+            &[Holiday {
+                date: date(2026, 12, 25),
+                name: "Christmas Day".to_string(),
+            }],
+            None,
+        );
+        let schedule = weekdays(8.0);
+        let holiday_hours = HolidayHours {
+            target_hours: 0.0,
+            credit_hours: 2.0,
+        };
+        let balance = BalanceContext::new(&schedule, &special_days, holiday_hours);
+
+        approx_eq(balance.target_hours(date(2026, 12, 25)), 0.0);
+        approx_eq(balance.credit_hours(date(2026, 12, 25)), 2.0);
+        approx_eq(balance.effective_hours(date(2026, 12, 25), 1.0), 2.0);
+        approx_eq(balance.overtime_hours(date(2026, 12, 25), 1.0), 2.0);
+    }
+
+    #[test]
+    fn custom_absence_types_use_their_own_hours_and_credit_toggle() {
+        let training = AbsenceType {
+            key: "training".to_string(),
+            name: "Training".to_string(),
+            glyph: 'T',
+            color: Some("#aa66ff".to_string()),
+            target_hours: 8.0,
+            credit_hours: 8.0,
+            credit_as_worked: true,
+        };
+        let unpaid = AbsenceType {
+            key: "unpaid".to_string(),
+            name: "Unpaid leave".to_string(),
+            glyph: 'U',
+            color: None,
+            target_hours: 0.0,
+            credit_hours: 0.0,
+            credit_as_worked: false,
+        };
+        let special_days = SpecialDays {
+            absences: HashMap::from([
+                (date(2026, 4, 6), "training".to_string()),
+                (date(2026, 4, 7), "unpaid".to_string()),
+            ]),
+            types: vec![training, unpaid],
+            ..SpecialDays::default()
+        };
+        let period = period("W15 2026", date(2026, 4, 6), date(2026, 4, 10));
+        let daily = vec![
+            daily_total(2026, 4, 6, 0.0),
+            daily_total(2026, 4, 7, 0.0),
+            daily_total(2026, 4, 8, 8.0),
+            daily_total(2026, 4, 9, 8.0),
+            daily_total(2026, 4, 10, 8.0),
+        ];
+        let schedule = weekdays(8.0);
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        let (target, target_days) = balance.period_target_hours(&period);
+
+        approx_eq(target, 32.0);
+        assert_eq!(target_days, 4);
+        approx_eq(balance.period_overtime_hours(&period, &daily), 0.0);
+    }
+
+    #[test]
+    fn overtime_follows_schedule_change_within_period() {
+        let period = period("W36 2026", date(2026, 8, 31), date(2026, 9, 4));
+        let daily = vec![
+            daily_total(2026, 8, 31, 8.0),
+            daily_total(2026, 9, 1, 8.0),
+            daily_total(2026, 9, 2, 8.0),
+            daily_total(2026, 9, 3, 8.0),
+            daily_total(2026, 9, 4, 5.0),
+        ];
+        let schedule = WorkSchedule::new(
+            8.0,
+            false,
+            vec![ScheduleChange {
+                from: date(2026, 9, 1),
+                hours: WeekdayHours {
+                    mon: 8.0,
+                    tue: 8.0,
+                    wed: 8.0,
+                    thu: 8.0,
+                    fri: 4.0,
+                    ..WeekdayHours::default()
+                },
+            }],
+        );
+        let special_days = SpecialDays::default();
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        let (target, target_days) = balance.period_target_hours(&period);
+
+        approx_eq(target, 36.0);
+        approx_eq(balance.period_overtime_hours(&period, &daily), 1.0);
+        assert_eq!(target_days, 5);
+    }
+
+    #[test]
+    fn worked_totals_stop_at_the_cutoff_and_skip_empty_days() {
+        let period = period("W15 2026", date(2026, 4, 6), date(2026, 4, 12));
+        let daily = vec![
+            daily_total(2026, 4, 6, 7.5),
+            daily_total(2026, 4, 7, 0.0),
+            daily_total(2026, 4, 8, 8.25),
+            daily_total(2026, 4, 9, 9.0),
+        ];
+        let schedule = weekdays(8.0);
+        let special_days = SpecialDays::default();
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        let (hours, days) = balance.period_worked_totals_until(&period, &daily, date(2026, 4, 8));
+        approx_eq(hours, 15.75);
+        assert_eq!(days, 2);
+
+        let (hours, days) = balance.period_worked_totals_until(&period, &daily, date(2026, 4, 1));
+        approx_eq(hours, 0.0);
+        assert_eq!(days, 0);
+    }
+
+    #[test]
+    fn running_balance_carries_across_weeks_from_opening_offset() {
+        let special_days = marked_days(
+            &[date(2026, 4, 14)],
+            VACATION,
+            AbsenceType::vacation(8.0, 8.0, true),
+            AbsenceType::sick(8.0, 8.0, true),
+        );
+        let daily = vec![
+            daily_total(2026, 4, 10, 9.0),
+            daily_total(2026, 4, 11, 1.0),
+            daily_total(2026, 4, 12, 0.0),
+            daily_total(2026, 4, 13, 6.0),
+            daily_total(2026, 4, 14, 0.0),
+        ];
+        let schedule = weekdays(8.0);
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+
+        let counted: HashSet<NaiveDate> = daily.iter().map(|day| day.date).collect();
+
+        let balances = balance.running_balance(-2.0, &daily, &counted);
+
+        let values: Vec<f64> = balances.iter().map(|(_, balance)| *balance).collect();
+        assert_eq!(values, vec![-1.0, 0.0, 0.0, -2.0, -2.0]);
+        assert_eq!(
+            balances.last().map(|(day, _)| *day),
+            Some(date(2026, 4, 14))
+        );
+    }
+
+    #[test]
+    fn running_balance_skips_days_that_are_not_counted() {
+        let daily = vec![
+            daily_total(2026, 4, 13, 9.0),
+            daily_total(2026, 4, 14, 0.0),
+            daily_total(2026, 4, 15, 3.0),
+        ];
+        let schedule = weekdays(8.0);
+        let special_days = SpecialDays::default();
+        let balance = BalanceContext::new(&schedule, &special_days, NO_HOLIDAY_HOURS);
+        let counted = HashSet::from([date(2026, 4, 13)]);
+
+        let balances = balance.running_balance(0.0, &daily, &counted);
+
+        let values: Vec<f64> = balances.iter().map(|(_, balance)| *balance).collect();
+        assert_eq!(values, vec![1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn scope_adjustment_is_the_period_rounding_difference() {
+        let mut period = period("W15 2026", date(2026, 4, 6), date(2026, 4, 7));
+        period.seconds = 9 * 3600;
+        let daily = vec![
+            daily_total(2026, 4, 6, 4.25),
+            daily_total(2026, 4, 7, 4.25),
+            daily_total(2026, 4, 8, 3.0),
+        ];
+
+        approx_eq(period_scope_adjustment(&period, &daily), 0.5);
+    }
+
+    #[test]
+    fn normalize_delta_hides_rounding_noise() {
+        assert_eq!(normalize_delta(0.004), 0.0);
+        assert_eq!(normalize_delta(-0.004), 0.0);
+        assert_eq!(normalize_delta(0.25), 0.25);
+    }
+}
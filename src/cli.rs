@@ -0,0 +1,195 @@
+use crate::app::{App, RollupReport, RollupView};
+use crate::dates::parse_range_expression;
+
+const ROLLUP_USAGE: &str = "Usage: timeshit rollup [--view week|month|quarter|year] \
+[--period <date or range>] [--format table|json] [--fetch] [--workspace <id or name>]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RollupArgs {
+    view: RollupView,
+    period: String,
+    format: OutputFormat,
+    fetch: bool,
+    workspace: Option<String>,
+}
+
+/// `timeshit rollup ...`: prints one rollup period from the cache.
+pub fn run_rollup(args: &[String]) -> Result<(), String> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{ROLLUP_USAGE}");
+        return Ok(());
+    }
+    let args = parse_rollup_args(args)?;
+    let mut app = App::new(false, false);
+    let (day, _) =
+        parse_range_expression(&args.period, app.timezone.today(), app.rollups_week_start)?;
+    let report = app.rollup_report(args.view, day, args.fetch, args.workspace.as_deref())?;
+    if let Some(status) = app.status.take() {
+        eprintln!("{status}");
+    }
+    match args.format {
+        OutputFormat::Table => print!("{}", format_table(&report)),
+        OutputFormat::Json => println!("{}", format_json(args.view, &report)),
+    }
+    Ok(())
+}
+
+fn parse_rollup_args(args: &[String]) -> Result<RollupArgs, String> {
+    let mut parsed = RollupArgs {
+        view: RollupView::Weekly,
+        period: "today".to_string(),
+        format: OutputFormat::Table,
+        fetch: false,
+        workspace: None,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        if flag == "--fetch" {
+            parsed.fetch = true;
+            continue;
+        }
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| format!("{flag} needs a value.\n{ROLLUP_USAGE}"))
+        };
+        match flag {
+            "--view" => {
+                parsed.view = match value()?.as_str() {
+                    "week" => RollupView::Weekly,
+                    "month" => RollupView::Monthly,
+                    "quarter" => RollupView::Quarterly,
+                    "year" => RollupView::Yearly,
+                    other => return Err(format!("Unknown view `{other}`.\n{ROLLUP_USAGE}")),
+                };
+            }
+            "--period" => parsed.period = value()?,
+            "--format" => {
+                parsed.format = match value()?.as_str() {
+                    "table" => OutputFormat::Table,
+                    "json" => OutputFormat::Json,
+                    other => return Err(format!("Unknown format `{other}`.\n{ROLLUP_USAGE}")),
+                };
+            }
+            "--workspace" => parsed.workspace = Some(value()?),
+            other => return Err(format!("Unknown argument `{other}`.\n{ROLLUP_USAGE}")),
+        }
+    }
+    Ok(parsed)
+}
+
+fn format_table(report: &RollupReport) -> String {
+    let fetched = report.days - report.missing_days.min(report.days);
+    [
+        format!(
+            "Period    {} ({} → {})",
+            report.label,
+            report.start.format("%Y-%m-%d"),
+            report.end.format("%Y-%m-%d")
+        ),
+        format!("Tracked   {:.2}h", report.tracked_hours),
+        format!("Credited  {:.2}h", report.credited_hours),
+        format!("Worked    {:.2}h", report.worked_hours),
+        format!(
+            "Target    {:.2}h ({} target days)",
+            report.target_hours, report.target_days
+        ),
+        format!("Overtime  {:+.2}h", report.overtime_hours),
+        format!(
+            "Coverage  {fetched}/{} days fetched ({} missing)",
+            report.days, report.missing_days
+        ),
+    ]
+    .iter()
+    .map(|line| format!("{line}\n"))
+    .collect()
+}
+
+fn format_json(view: RollupView, report: &RollupReport) -> String {
+    let round = |hours: f64| (hours * 100.0).round() / 100.0;
+    serde_json::json!({
+        "view": view.key(),
+        "period": report.label,
+        "start": report.start.format("%Y-%m-%d").to_string(),
+        "end": report.end.format("%Y-%m-%d").to_string(),
+        "days": report.days,
+        "tracked_hours": round(report.tracked_hours),
+        "credited_hours": round(report.credited_hours),
+        "worked_hours": round(report.worked_hours),
+        "target_hours": round(report.target_hours),
+        "target_days": report.target_days,
+        "overtime_hours": round(report.overtime_hours),
+        "missing_days": report.missing_days,
+        "complete": report.missing_days == 0,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn report() -> RollupReport {
+        RollupReport {
+            label: "2026-W41".to_string(),
+            start: NaiveDate::from_ymd_opt(2026, 10, 5).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 10, 11).unwrap(),
+            days: 7,
+            tracked_hours: 30.0,
+            credited_hours: 8.0,
+            worked_hours: 38.0,
+            target_hours: 40.0,
+            target_days: 5,
+            overtime_hours: -2.0,
+            missing_days: 2,
+        }
+    }
+
+    #[test]
+    fn parses_flags_with_and_without_equals() {
+        let parsed = parse_rollup_args(&args(&[
+            "--view",
+            "month",
+            "--period=2026-10",
+            "--format",
+            "json",
+            "--fetch",
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.view, RollupView::Monthly);
+        assert_eq!(parsed.period, "2026-10");
+        assert_eq!(parsed.format, OutputFormat::Json);
+        assert!(parsed.fetch);
+        assert!(parse_rollup_args(&args(&["--view", "day"])).is_err());
+        assert!(parse_rollup_args(&args(&["--period"])).is_err());
+    }
+
+    #[test]
+    fn json_output_rounds_hours() {
+        let value: serde_json::Value =
+            serde_json::from_str(&format_json(RollupView::Weekly, &report())).unwrap();
+
+        assert_eq!(value["view"], "weekly");
+        assert_eq!(value["overtime_hours"], -2.0);
+        assert_eq!(value["missing_days"], 2);
+        assert_eq!(value["complete"], false);
+        assert!(format_table(&report()).contains("Coverage  5/7 days fetched"));
+    }
+}
@@ -0,0 +1,167 @@
+use chrono::{Duration, Months, NaiveDate};
+
+use crate::rollups::Breakdown;
+
+/// Periods shown in the trend chart, the selected one included.
+pub const TREND_PERIODS: u32 = 12;
+
+/// Distance between a period and the one it is compared with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodStep {
+    Days(i64),
+    Months(u32),
+}
+
+impl PeriodStep {
+    /// The range `steps` steps before `start..=end`. Month steps keep the
+    /// range aligned to the same day of the month, so a whole month maps to
+    /// the whole earlier month.
+    pub fn back(
+        self,
+        start: NaiveDate,
+        end: NaiveDate,
+        steps: u32,
+    ) -> Option<(NaiveDate, NaiveDate)> {
+        match self {
+            Self::Days(days) => {
+                let offset = Duration::days(days.checked_mul(i64::from(steps))?);
+                Some((
+                    start.checked_sub_signed(offset)?,
+                    end.checked_sub_signed(offset)?,
+                ))
+            }
+            Self::Months(months) => {
+                let offset = Months::new(months.checked_mul(steps)?);
+                let start = start.checked_sub_months(offset)?;
+                let end = end.succ_opt()?.checked_sub_months(offset)?.pred_opt()?;
+                Some((start, end))
+            }
+        }
+    }
+}
+
+/// Totals of one period in the comparison pane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodFigures {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub hours: f64,
+    pub overtime: f64,
+    /// Rounded seconds per client.
+    pub clients: Breakdown,
+    pub missing_days: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendPoint {
+    pub label: String,
+    pub hours: f64,
+}
+
+/// The selected period next to the previous one and the same period a year
+/// earlier, plus the hours of the periods leading up to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodComparison {
+    pub current: PeriodFigures,
+    pub previous: Option<PeriodFigures>,
+    pub last_year: Option<PeriodFigures>,
+    pub trend: Vec<TrendPoint>,
+}
+
+impl PeriodComparison {
+    /// Clients to list, biggest in the selected period first, followed by
+    /// clients that only appear in the other periods.
+    pub fn client_rows(&self, limit: usize) -> Vec<Option<u64>> {
+        let mut rows: Vec<(Option<u64>, i64)> = self
+            .current
+            .clients
+            .iter()
+            .map(|(id, seconds)| (*id, *seconds))
+            .collect();
+        rows.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(&right.0)));
+
+        let mut others: Vec<(Option<u64>, i64)> = Vec::new();
+        for figures in self.previous.iter().chain(&self.last_year) {
+            for (id, seconds) in &figures.clients {
+                if self.current.clients.contains_key(id) {
+                    continue;
+                }
+                match others.iter_mut().find(|(other, _)| other == id) {
+                    Some((_, total)) => *total += seconds,
+                    None => others.push((*id, *seconds)),
+                }
+            }
+        }
+        others.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(&right.0)));
+
+        rows.into_iter()
+            .chain(others)
+            .map(|(id, _)| id)
+            .take(limit)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn figures(clients: &[(Option<u64>, i64)]) -> PeriodFigures {
+        PeriodFigures {
+            start: date(2026, 3, 1),
+            end: date(2026, 3, 31),
+            hours: 0.0,
+            overtime: 0.0,
+            clients: clients.iter().copied().collect(),
+            missing_days: 0,
+        }
+    }
+
+    #[test]
+    fn month_steps_map_whole_months() {
+        let step = PeriodStep::Months(1);
+        assert_eq!(
+            step.back(date(2026, 3, 1), date(2026, 3, 31), 1),
+            Some((date(2026, 2, 1), date(2026, 2, 28)))
+        );
+        assert_eq!(
+            step.back(date(2026, 3, 26), date(2026, 4, 25), 1),
+            Some((date(2026, 2, 26), date(2026, 3, 25)))
+        );
+        assert_eq!(
+            PeriodStep::Months(12).back(date(2024, 2, 1), date(2024, 2, 29), 1),
+            Some((date(2023, 2, 1), date(2023, 2, 28)))
+        );
+    }
+
+    #[test]
+    fn day_steps_keep_weekdays() {
+        assert_eq!(
+            PeriodStep::Days(364).back(date(2026, 3, 2), date(2026, 3, 8), 1),
+            Some((date(2025, 3, 3), date(2025, 3, 9)))
+        );
+        assert_eq!(
+            PeriodStep::Days(7).back(date(2026, 3, 2), date(2026, 3, 8), 0),
+            Some((date(2026, 3, 2), date(2026, 3, 8)))
+        );
+    }
+
+    #[test]
+    fn client_rows_list_current_clients_first() {
+        let comparison = PeriodComparison {
+            current: figures(&[(Some(1), 100), (Some(2), 300)]),
+            previous: Some(figures(&[(Some(1), 50), (Some(3), 20)])),
+            last_year: Some(figures(&[(None, 80)])),
+            trend: Vec::new(),
+        };
+        assert_eq!(
+            comparison.client_rows(10),
+            vec![Some(2), Some(1), None, Some(3)]
+        );
+        assert_eq!(comparison.client_rows(2), vec![Some(2), Some(1)]);
+    }
+}
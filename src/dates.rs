@@ -1,33 +1,100 @@
 use chrono::Duration;
-use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone};
+use chrono::offset::LocalResult;
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Timezone used to decide which calendar day an instant belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timezone {
+    #[default]
+    Local,
+    Named(Tz),
+}
+
+impl Timezone {
+    pub fn parse(value: &str) -> Option<Self> {
+        let trimmed = value.trim();
+        if trimmed.eq_ignore_ascii_case("local") {
+            return Some(Self::Local);
+        }
+        trimmed.parse::<Tz>().ok().map(Self::Named)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Local => "Local".to_string(),
+            Self::Named(tz) => tz.name().to_string(),
+        }
+    }
+
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        self.convert(&Utc::now())
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+
+    pub fn convert<T: TimeZone>(&self, value: &DateTime<T>) -> DateTime<FixedOffset> {
+        match self {
+            Self::Local => value.with_timezone(&Local).fixed_offset(),
+            Self::Named(tz) => value.with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    pub fn date_of<T: TimeZone>(&self, value: &DateTime<T>) -> NaiveDate {
+        self.convert(value).date_naive()
+    }
+
+    /// First instant of `date`. When midnight is skipped by a DST change the
+    /// day starts at the end of the gap; when it repeats, at the first one.
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<FixedOffset> {
+        match self {
+            Self::Local => start_of_day_in(&Local, date),
+            Self::Named(tz) => start_of_day_in(tz, date),
+        }
+    }
+
+    /// Last second of `date`, i.e. one second before the next day starts.
+    pub fn end_of_day(&self, date: NaiveDate) -> DateTime<FixedOffset> {
+        let next = date.succ_opt().unwrap_or(date);
+        let end = self.start_of_day(next) - Duration::seconds(1);
+        self.convert(&end)
+    }
+}
+
+/// Calendar unit a range is aligned to; stepping moves by that unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeSnap {
+    Week(WeekStart),
+    Month,
+}
 
 #[derive(Debug, Clone)]
 pub struct DateRange {
-    start: DateTime<Local>,
-    end: DateTime<Local>,
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
     label: String,
+    snap: Option<RangeSnap>,
 }
 
 impl DateRange {
-    pub fn today() -> Self {
-        let today = Local::now().date_naive();
-        let start = local_datetime(today, 0, 0, 0);
-        let end = local_datetime(today, 23, 59, 59);
+    pub fn today(timezone: Timezone) -> Self {
+        let today = timezone.today();
         let label = format!("Today ({})", today.format("%Y-%m-%d"));
-        Self { start, end, label }
+        Self::with_label(today, today, label, timezone)
     }
 
-    pub fn yesterday() -> Self {
-        let day = Local::now().date_naive() - Duration::days(1);
-        let start = local_datetime(day, 0, 0, 0);
-        let end = local_datetime(day, 23, 59, 59);
+    pub fn yesterday(timezone: Timezone) -> Self {
+        let day = timezone.today() - Duration::days(1);
         let label = format!("Yesterday ({})", day.format("%Y-%m-%d"));
-        Self { start, end, label }
+        Self::with_label(day, day, label, timezone)
     }
 
-    pub fn from_bounds(start_date: NaiveDate, end_date: NaiveDate) -> Self {
-        let start = local_datetime(start_date, 0, 0, 0);
-        let end = local_datetime(end_date, 23, 59, 59);
+    pub fn from_bounds(start_date: NaiveDate, end_date: NaiveDate, timezone: Timezone) -> Self {
         let label = if start_date == end_date {
             format!("{}", start_date.format("%Y-%m-%d"))
         } else {
@@ -37,7 +104,131 @@ impl DateRange {
                 end_date.format("%Y-%m-%d")
             )
         };
-        Self { start, end, label }
+        Self::with_label(start_date, end_date, label, timezone)
+    }
+
+    pub fn this_week(timezone: Timezone, week_start: WeekStart) -> Self {
+        Self::week_of(timezone.today(), week_start, timezone).relabel("This week")
+    }
+
+    pub fn last_week(timezone: Timezone, week_start: WeekStart) -> Self {
+        let day = timezone.today() - Duration::weeks(1);
+        Self::week_of(day, week_start, timezone).relabel("Last week")
+    }
+
+    pub fn this_month(timezone: Timezone) -> Self {
+        Self::month_of(timezone.today(), timezone).relabel("This month")
+    }
+
+    pub fn last_month(timezone: Timezone) -> Self {
+        let day = shift_months(timezone.today(), -1).unwrap_or_else(|| timezone.today());
+        Self::month_of(day, timezone).relabel("Last month")
+    }
+
+    /// The week containing `date`, starting on `week_start`.
+    pub fn week_of(date: NaiveDate, week_start: WeekStart, timezone: Timezone) -> Self {
+        let (start_date, end_date) = week_bounds(date, week_start);
+        // A Sunday start belongs to the previous ISO week; number the week by
+        // its Monday instead.
+        let monday = match week_start {
+            WeekStart::Monday => start_date,
+            WeekStart::Sunday => start_date + Duration::days(1),
+        };
+        let iso_week = monday.iso_week();
+        let label = format!(
+            "W{:02} {} ({} → {})",
+            iso_week.week(),
+            iso_week.year(),
+            start_date.format("%Y-%m-%d"),
+            end_date.format("%Y-%m-%d")
+        );
+        let mut range = Self::with_label(start_date, end_date, label, timezone);
+        range.snap = Some(RangeSnap::Week(week_start));
+        range
+    }
+
+    /// The calendar month containing `date`.
+    pub fn month_of(date: NaiveDate, timezone: Timezone) -> Self {
+        let (start_date, end_date) = month_bounds(date);
+        let label = start_date.format("%B %Y").to_string();
+        let mut range = Self::with_label(start_date, end_date, label, timezone);
+        range.snap = Some(RangeSnap::Month);
+        range
+    }
+
+    /// Like `from_bounds`, but snaps to a week or month when the bounds line
+    /// up with one exactly.
+    pub fn from_bounds_snapped(
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        week_start: WeekStart,
+        timezone: Timezone,
+    ) -> Self {
+        if week_bounds(start_date, week_start) == (start_date, end_date) {
+            Self::week_of(start_date, week_start, timezone)
+        } else if month_bounds(start_date) == (start_date, end_date) {
+            Self::month_of(start_date, timezone)
+        } else {
+            Self::from_bounds(start_date, end_date, timezone)
+        }
+    }
+
+    /// The neighbouring week or month for snapped ranges.
+    pub fn step(&self, direction: i32, timezone: Timezone) -> Option<Self> {
+        let start = self.start_date();
+        match self.snap? {
+            RangeSnap::Week(week_start) => {
+                let day = start.checked_add_signed(Duration::weeks(direction.signum() as i64))?;
+                Some(Self::week_of(day, week_start, timezone))
+            }
+            RangeSnap::Month => {
+                let day = shift_months(start, direction.signum())?;
+                Some(Self::month_of(day, timezone))
+            }
+        }
+    }
+
+    /// A week range realigned to `week_start`, keeping the week that holds
+    /// most of its days. Other ranges are returned unchanged.
+    pub fn with_week_start(&self, week_start: WeekStart, timezone: Timezone) -> Self {
+        match self.snap {
+            Some(RangeSnap::Week(current)) if current != week_start => {
+                let middle = self.start_date() + Duration::days(3);
+                Self::week_of(middle, week_start, timezone)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Same calendar days and label, anchored in another timezone.
+    pub fn with_timezone(&self, timezone: Timezone) -> Self {
+        let mut range = Self::with_label(
+            self.start_date(),
+            self.end_date(),
+            self.label.clone(),
+            timezone,
+        );
+        range.snap = self.snap;
+        range
+    }
+
+    fn relabel(mut self, prefix: &str) -> Self {
+        self.label = format!("{prefix} ({})", self.label);
+        self
+    }
+
+    fn with_label(
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        label: String,
+        timezone: Timezone,
+    ) -> Self {
+        Self {
+            start: timezone.start_of_day(start_date),
+            end: timezone.end_of_day(end_date),
+            label,
+            snap: None,
+        }
     }
 
     pub fn as_rfc3339(&self) -> (String, String) {
@@ -48,6 +239,10 @@ impl DateRange {
         &self.label
     }
 
+    pub fn snap(&self) -> Option<RangeSnap> {
+        self.snap
+    }
+
     pub fn start_date(&self) -> NaiveDate {
         self.start.date_naive()
     }
@@ -62,18 +257,247 @@ pub fn parse_date(value: &str) -> Result<NaiveDate, String> {
         .map_err(|_| "Invalid date format. Use YYYY-MM-DD.".to_string())
 }
 
-fn local_datetime(date: NaiveDate, hour: u32, minute: u32, second: u32) -> DateTime<Local> {
-    let result =
-        Local.with_ymd_and_hms(date.year(), date.month(), date.day(), hour, minute, second);
-    result
-        .earliest()
-        .or_else(|| result.latest())
-        .unwrap_or_else(Local::now)
+const RANGE_HINT: &str =
+    "Try YYYY-MM-DD, today, last week, this month, -3d, mon..fri, 2026-W41, Q3 or 2026-10.";
+
+/// Parses a date or range expression into inclusive bounds. Relative forms
+/// are resolved against `today`; `a..b` spans from the start of `a` to the
+/// end of `b`.
+pub fn parse_range_expression(
+    value: &str,
+    today: NaiveDate,
+    week_start: WeekStart,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let normalized = value.trim().to_lowercase();
+    if normalized.is_empty() {
+        return Err("Date is required.".to_string());
+    }
+    let (start, end) = match normalized.split_once("..") {
+        Some((left, right)) => {
+            let (start, _) = parse_range_term(left.trim(), today, week_start)?;
+            let (_, end) = parse_range_term(right.trim(), today, week_start)?;
+            (start, end)
+        }
+        None => parse_range_term(&normalized, today, week_start)?,
+    };
+    if start > end {
+        return Err("Start date cannot be after end date.".to_string());
+    }
+    Ok((start, end))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+pub fn start_of_week(date: NaiveDate, week_start: WeekStart) -> NaiveDate {
+    let offset = match week_start {
+        WeekStart::Monday => date.weekday().num_days_from_monday() as i64,
+        WeekStart::Sunday => date.weekday().num_days_from_sunday() as i64,
+    };
+    date - Duration::days(offset)
+}
+
+pub fn week_bounds(date: NaiveDate, week_start: WeekStart) -> (NaiveDate, NaiveDate) {
+    let start = start_of_week(date, week_start);
+    (start, start + Duration::days(6))
+}
+
+pub fn month_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let start = date.with_day(1).unwrap_or(date);
+    let end = start
+        .checked_add_months(chrono::Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date);
+    (start, end)
+}
+
+fn quarter_bounds(year: i32, quarter: u32) -> Option<(NaiveDate, NaiveDate)> {
+    if !(1..=4).contains(&quarter) {
+        return None;
+    }
+    let start = NaiveDate::from_ymd_opt(year, (quarter - 1) * 3 + 1, 1)?;
+    let end = start
+        .checked_add_months(chrono::Months::new(3))?
+        .pred_opt()?;
+    Some((start, end))
+}
+
+fn year_bounds(year: i32) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        NaiveDate::from_ymd_opt(year, 1, 1)?,
+        NaiveDate::from_ymd_opt(year, 12, 31)?,
+    ))
+}
+
+fn parse_range_term(
+    value: &str,
+    today: NaiveDate,
+    week_start: WeekStart,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let unrecognized = || format!("Unrecognized date \"{value}\". {RANGE_HINT}");
+    if let Ok(date) = parse_date(value) {
+        return Ok((date, date));
+    }
+
+    let day = match value {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        "tomorrow" => today.succ_opt(),
+        _ => None,
+    };
+    if let Some(day) = day {
+        return Ok((day, day));
+    }
+
+    if let Some(day) = parse_day_offset(value, today) {
+        return Ok((day, day));
+    }
+    if let Some(weekday) = parse_weekday(value) {
+        let (week_first, _) = week_bounds(today, week_start);
+        let offset = (7 + weekday.num_days_from_monday() as i64
+            - week_first.weekday().num_days_from_monday() as i64)
+            % 7;
+        let day = week_first + Duration::days(offset);
+        return Ok((day, day));
+    }
+    if let Some((relation, unit)) = value.split_once(' ') {
+        return parse_relative_period(relation, unit.trim(), today, week_start)
+            .ok_or_else(unrecognized);
+    }
+    parse_calendar_period(value, today).ok_or_else(unrecognized)
+}
+
+fn parse_day_offset(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let sign = match value.chars().next()? {
+        '-' => -1,
+        '+' => 1,
+        _ => return None,
+    };
+    let body = &value[1..];
+    let days = if let Some(amount) = body.strip_suffix('d') {
+        amount.parse::<i64>().ok()?
+    } else if let Some(amount) = body.strip_suffix('w') {
+        amount.parse::<i64>().ok()?.checked_mul(7)?
+    } else {
+        return None;
+    };
+    today.checked_add_signed(Duration::days(sign * days))
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    let weekday = match value {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+fn parse_relative_period(
+    relation: &str,
+    unit: &str,
+    today: NaiveDate,
+    week_start: WeekStart,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let step: i32 = match relation {
+        "this" => 0,
+        "last" | "previous" => -1,
+        "next" => 1,
+        _ => return None,
+    };
+    match unit {
+        "week" => {
+            let anchor = today.checked_add_signed(Duration::weeks(step as i64))?;
+            Some(week_bounds(anchor, week_start))
+        }
+        "month" => {
+            let anchor = shift_months(today.with_day(1)?, step)?;
+            Some(month_bounds(anchor))
+        }
+        "quarter" => {
+            let first_month = shift_months(
+                NaiveDate::from_ymd_opt(today.year(), (today.month0() / 3) * 3 + 1, 1)?,
+                step * 3,
+            )?;
+            quarter_bounds(first_month.year(), first_month.month0() / 3 + 1)
+        }
+        "year" => year_bounds(today.year() + step),
+        _ => None,
+    }
+}
+
+fn shift_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    let delta = chrono::Months::new(months.unsigned_abs());
+    if months < 0 {
+        date.checked_sub_months(delta)
+    } else {
+        date.checked_add_months(delta)
+    }
+}
+
+/// `2026-W41`, `Q3`, `2026-Q3`, `2026-10` and `2026`.
+fn parse_calendar_period(value: &str, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    if let Some(quarter) = value.strip_prefix('q') {
+        return quarter_bounds(today.year(), quarter.parse().ok()?);
+    }
+    let Some((year, rest)) = value.split_once('-') else {
+        if value.len() != 4 {
+            return None;
+        }
+        return year_bounds(value.parse().ok()?);
+    };
+    let year: i32 = year.parse().ok().filter(|_| year.len() == 4)?;
+    if let Some(week) = rest.strip_prefix('w') {
+        let start = NaiveDate::from_isoywd_opt(year, week.parse().ok()?, Weekday::Mon)?;
+        return Some((start, start + Duration::days(6)));
+    }
+    if let Some(quarter) = rest.strip_prefix('q') {
+        return quarter_bounds(year, quarter.parse().ok()?);
+    }
+    let month: u32 = rest.parse().ok()?;
+    Some(month_bounds(NaiveDate::from_ymd_opt(year, month, 1)?))
+}
+
+fn start_of_day_in<Z: TimeZone>(tz: &Z, date: NaiveDate) -> DateTime<FixedOffset> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    match tz.from_local_datetime(&midnight) {
+        LocalResult::Single(value) | LocalResult::Ambiguous(value, _) => value.fixed_offset(),
+        LocalResult::None => {
+            // Transitions happen on whole minutes, so the first valid minute
+            // after midnight is the instant the gap ends.
+            (1..=24 * 60)
+                .find_map(|minutes| {
+                    tz.from_local_datetime(&(midnight + Duration::minutes(minutes)))
+                        .earliest()
+                })
+                .map(|value| value.fixed_offset())
+                .unwrap_or_else(|| Utc.from_utc_datetime(&midnight).fixed_offset())
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Datelike;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn named(name: &str) -> Timezone {
+        Timezone::parse(name).unwrap()
+    }
 
     #[test]
     fn parse_date_valid() {
@@ -92,14 +516,212 @@ mod tests {
     fn range_from_bounds_label() {
         let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
-        let range = DateRange::from_bounds(start, end);
+        let range = DateRange::from_bounds(start, end, Timezone::Local);
         assert!(range.label().contains("2026-01-01"));
         assert!(range.label().contains("2026-01-10"));
     }
 
     #[test]
     fn yesterday_label() {
-        let range = DateRange::yesterday();
+        let range = DateRange::yesterday(Timezone::Local);
         assert!(range.label().starts_with("Yesterday"));
     }
+
+    #[test]
+    fn timezone_parse_accepts_iana_names_and_local() {
+        assert_eq!(Timezone::parse(" local "), Some(Timezone::Local));
+        assert_eq!(
+            Timezone::parse("Europe/Brussels").map(|tz| tz.label()),
+            Some("Europe/Brussels".to_string())
+        );
+        assert!(Timezone::parse("Mars/Olympus").is_none());
+    }
+
+    #[test]
+    fn range_bounds_use_timezone_offset() {
+        let day = date(2026, 2, 3);
+        let range = DateRange::from_bounds(day, day, named("America/New_York"));
+        let (start, end) = range.as_rfc3339();
+        assert_eq!(start, "2026-02-03T00:00:00-05:00");
+        assert_eq!(end, "2026-02-03T23:59:59-05:00");
+    }
+
+    #[test]
+    fn spring_forward_day_is_23_hours() {
+        let day = date(2026, 3, 29);
+        let range = DateRange::from_bounds(day, day, named("Europe/Brussels"));
+        let (start, end) = range.as_rfc3339();
+        assert_eq!(start, "2026-03-29T00:00:00+01:00");
+        assert_eq!(end, "2026-03-29T23:59:59+02:00");
+        let length = range.end - range.start + Duration::seconds(1);
+        assert_eq!(length, Duration::hours(23));
+    }
+
+    #[test]
+    fn fall_back_day_is_25_hours() {
+        let day = date(2026, 10, 25);
+        let range = DateRange::from_bounds(day, day, named("Europe/Brussels"));
+        let (start, end) = range.as_rfc3339();
+        assert_eq!(start, "2026-10-25T00:00:00+02:00");
+        assert_eq!(end, "2026-10-25T23:59:59+01:00");
+        let length = range.end - range.start + Duration::seconds(1);
+        assert_eq!(length, Duration::hours(25));
+    }
+
+    #[test]
+    fn skipped_midnight_starts_day_after_gap() {
+        // Chile moves clocks from 00:00 to 01:00 on the first Sunday of September.
+        let day = date(2026, 9, 6);
+        let timezone = named("America/Santiago");
+        let start = timezone.start_of_day(day);
+        assert_eq!(start.to_rfc3339(), "2026-09-06T01:00:00-03:00");
+        let previous_end = timezone.end_of_day(date(2026, 9, 5));
+        assert_eq!(previous_end.to_rfc3339(), "2026-09-05T23:59:59-04:00");
+    }
+
+    #[test]
+    fn date_of_buckets_across_utc_offset_boundaries() {
+        let instant = DateTime::parse_from_rfc3339("2026-02-03T23:30:00Z").unwrap();
+        assert_eq!(named("Europe/Brussels").date_of(&instant), date(2026, 2, 4));
+        assert_eq!(
+            named("America/New_York").date_of(&instant),
+            date(2026, 2, 3)
+        );
+        assert_eq!(named("UTC").date_of(&instant), date(2026, 2, 3));
+
+        let early = DateTime::parse_from_rfc3339("2026-02-04T04:59:59Z").unwrap();
+        assert_eq!(named("America/New_York").date_of(&early), date(2026, 2, 3));
+        let boundary = DateTime::parse_from_rfc3339("2026-02-04T05:00:00Z").unwrap();
+        assert_eq!(
+            named("America/New_York").date_of(&boundary),
+            date(2026, 2, 4)
+        );
+    }
+
+    fn expr(value: &str) -> (NaiveDate, NaiveDate) {
+        // Wednesday.
+        parse_range_expression(value, date(2026, 10, 14), WeekStart::Monday).unwrap()
+    }
+
+    #[test]
+    fn range_expression_relative_periods() {
+        assert_eq!(expr("this week"), (date(2026, 10, 12), date(2026, 10, 18)));
+        assert_eq!(expr("Last Week"), (date(2026, 10, 5), date(2026, 10, 11)));
+        assert_eq!(expr("this month"), (date(2026, 10, 1), date(2026, 10, 31)));
+        assert_eq!(expr("last month"), (date(2026, 9, 1), date(2026, 9, 30)));
+        assert_eq!(expr("last quarter"), (date(2026, 7, 1), date(2026, 9, 30)));
+        assert_eq!(expr("last year"), (date(2025, 1, 1), date(2025, 12, 31)));
+    }
+
+    #[test]
+    fn range_expression_offsets_and_weekdays() {
+        assert_eq!(expr("-3d"), (date(2026, 10, 11), date(2026, 10, 11)));
+        assert_eq!(expr("+1w"), (date(2026, 10, 21), date(2026, 10, 21)));
+        assert_eq!(expr("-3d..today"), (date(2026, 10, 11), date(2026, 10, 14)));
+        assert_eq!(expr("mon..fri"), (date(2026, 10, 12), date(2026, 10, 16)));
+        let sunday_weeks =
+            parse_range_expression("sun..sat", date(2026, 10, 14), WeekStart::Sunday).unwrap();
+        assert_eq!(sunday_weeks, (date(2026, 10, 11), date(2026, 10, 17)));
+    }
+
+    #[test]
+    fn range_expression_calendar_periods() {
+        assert_eq!(expr("2026-W41"), (date(2026, 10, 5), date(2026, 10, 11)));
+        assert_eq!(expr("Q3"), (date(2026, 7, 1), date(2026, 9, 30)));
+        assert_eq!(expr("2025-q4"), (date(2025, 10, 1), date(2025, 12, 31)));
+        assert_eq!(expr("2026-02"), (date(2026, 2, 1), date(2026, 2, 28)));
+        assert_eq!(expr("2026"), (date(2026, 1, 1), date(2026, 12, 31)));
+        assert_eq!(expr("2026-10-05"), (date(2026, 10, 5), date(2026, 10, 5)));
+    }
+
+    #[test]
+    fn range_expression_rejects_unknown_and_reversed_input() {
+        let today = date(2026, 10, 14);
+        assert!(parse_range_expression("someday", today, WeekStart::Monday).is_err());
+        assert!(parse_range_expression("Q5", today, WeekStart::Monday).is_err());
+        assert!(parse_range_expression("2026-W54", today, WeekStart::Monday).is_err());
+        assert!(parse_range_expression("fri..mon", today, WeekStart::Monday).is_err());
+        assert!(parse_range_expression("-3é", today, WeekStart::Monday).is_err());
+        assert!(parse_range_expression("+é", today, WeekStart::Monday).is_err());
+    }
+
+    #[test]
+    fn week_constructors_respect_week_start() {
+        let monday = DateRange::week_of(date(2026, 10, 14), WeekStart::Monday, Timezone::Local);
+        assert_eq!(monday.start_date(), date(2026, 10, 12));
+        assert_eq!(monday.end_date(), date(2026, 10, 18));
+        assert_eq!(monday.label(), "W42 2026 (2026-10-12 → 2026-10-18)");
+        assert_eq!(monday.snap(), Some(RangeSnap::Week(WeekStart::Monday)));
+
+        let sunday = DateRange::week_of(date(2026, 10, 14), WeekStart::Sunday, Timezone::Local);
+        assert_eq!(sunday.start_date(), date(2026, 10, 11));
+        assert_eq!(sunday.end_date(), date(2026, 10, 17));
+        assert_eq!(sunday.label(), "W42 2026 (2026-10-11 → 2026-10-17)");
+
+        let this_week = DateRange::this_week(Timezone::Local, WeekStart::Monday);
+        assert!(this_week.label().starts_with("This week ("));
+    }
+
+    #[test]
+    fn snapped_ranges_step_by_calendar_unit() {
+        let week = DateRange::week_of(date(2026, 12, 30), WeekStart::Monday, Timezone::Local);
+        let next = week.step(1, Timezone::Local).unwrap();
+        assert_eq!(next.start_date(), date(2027, 1, 4));
+        assert_eq!(next.end_date(), date(2027, 1, 10));
+
+        let january = DateRange::month_of(date(2026, 1, 31), Timezone::Local);
+        let february = january.step(1, Timezone::Local).unwrap();
+        assert_eq!(february.start_date(), date(2026, 2, 1));
+        assert_eq!(february.end_date(), date(2026, 2, 28));
+        assert_eq!(february.label(), "February 2026");
+        let december = january.step(-1, Timezone::Local).unwrap();
+        assert_eq!(december.start_date(), date(2025, 12, 1));
+        assert_eq!(december.end_date(), date(2025, 12, 31));
+
+        let loose = DateRange::from_bounds(date(2026, 2, 3), date(2026, 2, 9), Timezone::Local);
+        assert!(loose.step(1, Timezone::Local).is_none());
+    }
+
+    #[test]
+    fn week_ranges_realign_to_a_new_week_start() {
+        let monday = DateRange::week_of(date(2026, 10, 14), WeekStart::Monday, Timezone::Local);
+        let sunday = monday.with_week_start(WeekStart::Sunday, Timezone::Local);
+        assert_eq!(sunday.start_date(), date(2026, 10, 11));
+        assert_eq!(sunday.snap(), Some(RangeSnap::Week(WeekStart::Sunday)));
+
+        let back = sunday.with_week_start(WeekStart::Monday, Timezone::Local);
+        assert_eq!(back.start_date(), date(2026, 10, 12));
+
+        let month = DateRange::month_of(date(2026, 10, 14), Timezone::Local);
+        let unchanged = month.with_week_start(WeekStart::Sunday, Timezone::Local);
+        assert_eq!(unchanged.start_date(), date(2026, 10, 1));
+        assert_eq!(unchanged.snap(), Some(RangeSnap::Month));
+    }
+
+    #[test]
+    fn from_bounds_snapped_detects_aligned_ranges() {
+        let week = DateRange::from_bounds_snapped(
+            date(2026, 10, 11),
+            date(2026, 10, 17),
+            WeekStart::Sunday,
+            Timezone::Local,
+        );
+        assert_eq!(week.snap(), Some(RangeSnap::Week(WeekStart::Sunday)));
+
+        let month = DateRange::from_bounds_snapped(
+            date(2026, 9, 1),
+            date(2026, 9, 30),
+            WeekStart::Monday,
+            Timezone::Local,
+        );
+        assert_eq!(month.snap(), Some(RangeSnap::Month));
+
+        let loose = DateRange::from_bounds_snapped(
+            date(2026, 9, 2),
+            date(2026, 9, 30),
+            WeekStart::Monday,
+            Timezone::Local,
+        );
+        assert_eq!(loose.snap(), None);
+    }
 }
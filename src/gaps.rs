@@ -0,0 +1,282 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::dates::Timezone;
+use crate::models::TimeEntry;
+
+/// Limits for the gap report. Zero turns the matching check off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GapThresholds {
+    /// Working days with less than this share of their expected hours are
+    /// listed.
+    #[serde(default = "default_low_day_percent")]
+    pub low_day_percent: u32,
+    /// Breaks between consecutive entries longer than this are listed.
+    #[serde(default = "default_min_gap_minutes")]
+    pub min_gap_minutes: u32,
+}
+
+impl Default for GapThresholds {
+    fn default() -> Self {
+        Self {
+            low_day_percent: default_low_day_percent(),
+            min_gap_minutes: default_min_gap_minutes(),
+        }
+    }
+}
+
+/// What a day is expected to hold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DayTarget {
+    /// Hours expected after holidays and absences.
+    Hours(f64),
+    /// Not checked for low time, e.g. weekends, holidays or today.
+    Off,
+    /// Not fetched, so nothing is known about the day.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GapKind {
+    LowDay {
+        tracked_seconds: i64,
+        expected_hours: f64,
+    },
+    IntraDay {
+        from: NaiveTime,
+        to: NaiveTime,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GapItem {
+    pub date: NaiveDate,
+    pub kind: GapKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GapReport {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub thresholds: GapThresholds,
+    /// By day; a low day comes before the breaks on it.
+    pub items: Vec<GapItem>,
+    pub unfetched_days: usize,
+}
+
+/// Finds low working days and long breaks between `start` and `end`, in
+/// local time. Entries crossing midnight count towards both days.
+pub fn find_gaps(
+    entries: &[TimeEntry],
+    start: NaiveDate,
+    end: NaiveDate,
+    timezone: Timezone,
+    thresholds: GapThresholds,
+    day_target: impl Fn(NaiveDate) -> DayTarget,
+) -> GapReport {
+    let mut segments: BTreeMap<NaiveDate, Vec<(NaiveDateTime, NaiveDateTime)>> = BTreeMap::new();
+    for entry in entries {
+        let Some((mut cursor, stop)) = local_bounds(entry, timezone) else {
+            continue;
+        };
+        while cursor < stop {
+            let date = cursor.date();
+            let next = date
+                .succ_opt()
+                .map(|next| next.and_time(NaiveTime::MIN))
+                .unwrap_or(stop)
+                .min(stop);
+            if date >= start && date <= end {
+                segments.entry(date).or_default().push((cursor, next));
+            }
+            cursor = next;
+        }
+    }
+
+    let min_gap = i64::from(thresholds.min_gap_minutes) * 60;
+    let mut items = Vec::new();
+    let mut unfetched_days = 0;
+    for date in start.iter_days().take_while(|day| *day <= end) {
+        let target = day_target(date);
+        if target == DayTarget::Unknown {
+            unfetched_days += 1;
+            continue;
+        }
+        let mut day_segments = segments.remove(&date).unwrap_or_default();
+        day_segments.sort();
+
+        let tracked_seconds: i64 = day_segments
+            .iter()
+            .map(|(from, to)| (*to - *from).num_seconds())
+            .sum();
+        if let DayTarget::Hours(expected_hours) = target
+            && expected_hours > 0.0
+            && thresholds.low_day_percent > 0
+        {
+            let limit = expected_hours * 3600.0 * f64::from(thresholds.low_day_percent) / 100.0;
+            if (tracked_seconds as f64) < limit {
+                items.push(GapItem {
+                    date,
+                    kind: GapKind::LowDay {
+                        tracked_seconds,
+                        expected_hours,
+                    },
+                });
+            }
+        }
+
+        if thresholds.min_gap_minutes == 0 {
+            continue;
+        }
+        let mut covered_until: Option<NaiveDateTime> = None;
+        for (from, to) in day_segments {
+            if let Some(until) = covered_until
+                && (from - until).num_seconds() > min_gap
+            {
+                items.push(GapItem {
+                    date,
+                    kind: GapKind::IntraDay {
+                        from: until.time(),
+                        to: from.time(),
+                    },
+                });
+            }
+            covered_until = Some(covered_until.map_or(to, |until| until.max(to)));
+        }
+    }
+
+    GapReport {
+        start,
+        end,
+        thresholds,
+        items,
+        unfetched_days,
+    }
+}
+
+fn local_bounds(entry: &TimeEntry, timezone: Timezone) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let start = DateTime::parse_from_rfc3339(&entry.start).ok()?;
+    let stop = DateTime::parse_from_rfc3339(entry.stop.as_deref()?).ok()?;
+    if stop <= start {
+        return None;
+    }
+    Some((
+        timezone.convert(&start).naive_local(),
+        timezone.convert(&stop).naive_local(),
+    ))
+}
+
+const fn default_low_day_percent() -> u32 {
+    50
+}
+
+const fn default_min_gap_minutes() -> u32 {
+    30
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UTC: Timezone = Timezone::Named(chrono_tz::UTC);
+
+    fn entry(start: &str, stop: &str) -> TimeEntry {
+        TimeEntry {
+            id: 1,
+            description: None,
+            duration: 0,
+            start: start.to_string(),
+            stop: Some(stop.to_string()),
+            project_id: None,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn low_days_use_the_share_of_expected_hours() {
+        let entries = vec![
+            entry("2026-03-02T09:00:00Z", "2026-03-02T12:00:00Z"),
+            entry("2026-03-03T09:00:00Z", "2026-03-03T13:00:00Z"),
+        ];
+        let target = |day: NaiveDate| match day {
+            day if day == date(5) => DayTarget::Off,
+            day if day == date(6) => DayTarget::Unknown,
+            _ => DayTarget::Hours(8.0),
+        };
+        let report = find_gaps(
+            &entries,
+            date(2),
+            date(6),
+            UTC,
+            GapThresholds::default(),
+            target,
+        );
+
+        let low_days: Vec<(NaiveDate, i64)> = report
+            .items
+            .iter()
+            .filter_map(|item| match item.kind {
+                GapKind::LowDay {
+                    tracked_seconds, ..
+                } => Some((item.date, tracked_seconds)),
+                GapKind::IntraDay { .. } => None,
+            })
+            .collect();
+        assert_eq!(low_days, vec![(date(2), 3 * 3600), (date(4), 0)]);
+        assert_eq!(report.unfetched_days, 1);
+    }
+
+    #[test]
+    fn breaks_ignore_overlaps_and_short_pauses() {
+        let entries = vec![
+            entry("2026-03-02T13:00:00Z", "2026-03-02T14:00:00Z"),
+            entry("2026-03-02T08:00:00Z", "2026-03-02T10:00:00Z"),
+            entry("2026-03-02T09:30:00Z", "2026-03-02T11:00:00Z"),
+            entry("2026-03-02T14:20:00Z", "2026-03-02T16:00:00Z"),
+        ];
+        let report = find_gaps(
+            &entries,
+            date(2),
+            date(2),
+            UTC,
+            GapThresholds::default(),
+            |_| DayTarget::Off,
+        );
+
+        assert_eq!(
+            report.items,
+            vec![GapItem {
+                date: date(2),
+                kind: GapKind::IntraDay {
+                    from: time(11, 0),
+                    to: time(13, 0),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn zero_thresholds_turn_checks_off() {
+        let entries = vec![
+            entry("2026-03-02T08:00:00Z", "2026-03-02T09:00:00Z"),
+            entry("2026-03-02T15:00:00Z", "2026-03-02T16:00:00Z"),
+        ];
+        let thresholds = GapThresholds {
+            low_day_percent: 0,
+            min_gap_minutes: 0,
+        };
+        let report = find_gaps(&entries, date(2), date(2), UTC, thresholds, |_| {
+            DayTarget::Hours(8.0)
+        });
+
+        assert!(report.items.is_empty());
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
+use crate::dates::Timezone;
 use crate::models::{Project, TimeEntry};
-use crate::rounding::RoundingConfig;
-use crate::rounding::round_seconds;
+use crate::rollups::{DayWindow, split_seconds_by_day};
+use crate::rounding::{ResolvedRounding, RoundingItem, RoundingPolicy, RoundingScope};
 
 #[derive(Debug, Clone)]
 pub struct GroupedEntry {
@@ -16,6 +17,7 @@ pub struct GroupedProject {
     pub client_name: Option<String>,
     pub display_name: String,
     pub total_hours: f64,
+    pub rounding: ResolvedRounding,
     pub entries: Vec<GroupedEntry>,
 }
 
@@ -23,7 +25,8 @@ pub fn group_entries(
     entries: &[TimeEntry],
     projects: &[Project],
     client_names: &HashMap<u64, String>,
-    rounding: Option<&RoundingConfig>,
+    rounding: &RoundingPolicy,
+    split_window: Option<&DayWindow>,
 ) -> Vec<GroupedProject> {
     let mut project_info: HashMap<Option<u64>, (String, Option<String>)> = HashMap::new();
     for project in projects {
@@ -36,7 +39,7 @@ pub fn group_entries(
     }
     project_info.insert(None, ("No Project".to_string(), None));
 
-    let mut grouped: HashMap<Option<u64>, HashMap<String, i64>> = HashMap::new();
+    let mut grouped: HashMap<Option<u64>, HashMap<String, Vec<i64>>> = HashMap::new();
 
     for entry in entries {
         let project_key = entry.project_id;
@@ -44,8 +47,14 @@ pub fn group_entries(
             .description
             .clone()
             .unwrap_or_else(|| "No description".to_string());
+        let duration = split_window
+            .map(|window| window.clip_seconds(entry, entry.duration))
+            .unwrap_or(entry.duration);
         let project_entries = grouped.entry(project_key).or_default();
-        *project_entries.entry(description).or_insert(0) += entry.duration;
+        project_entries
+            .entry(description)
+            .or_default()
+            .push(duration);
     }
 
     let mut result: Vec<GroupedProject> = grouped
@@ -60,13 +69,21 @@ pub fn group_entries(
                 None => project_name.clone(),
             };
 
+            let line_rounding = rounding.resolve(project_id);
             let mut total_seconds = 0i64;
             let mut entry_list: Vec<GroupedEntry> = entries
                 .into_iter()
-                .map(|(description, duration)| {
-                    let rounded_seconds = rounding
-                        .map(|cfg| round_seconds(duration, cfg))
-                        .unwrap_or(duration);
+                .map(|(description, durations)| {
+                    // Day and period scopes cannot be attributed to a line,
+                    // so lines stay raw and only the totals are rounded.
+                    let rounded_seconds = match rounding.scope() {
+                        RoundingScope::Entry => durations
+                            .iter()
+                            .map(|duration| line_rounding.apply(*duration))
+                            .sum(),
+                        RoundingScope::Line => line_rounding.apply(durations.iter().sum()),
+                        RoundingScope::Day | RoundingScope::Period => durations.iter().sum(),
+                    };
                     total_seconds = total_seconds.saturating_add(rounded_seconds);
                     GroupedEntry {
                         description,
@@ -82,6 +99,7 @@ pub fn group_entries(
                 client_name,
                 display_name,
                 total_hours: total_seconds as f64 / 3600.0,
+                rounding: line_rounding,
                 entries: entry_list,
             }
         })
@@ -101,10 +119,35 @@ pub fn group_entries(
     result
 }
 
+/// Raw and rounded seconds for a whole dashboard range, rounded in the
+/// policy's scope.
+pub fn range_totals(
+    entries: &[TimeEntry],
+    rounding: &RoundingPolicy,
+    timezone: Timezone,
+    split_window: Option<&DayWindow>,
+) -> (i64, i64) {
+    let items: Vec<RoundingItem> = entries
+        .iter()
+        .flat_map(|entry| {
+            split_seconds_by_day(entry, entry.duration, timezone, split_window.is_some())
+                .into_iter()
+                .filter(|(date, _)| {
+                    split_window
+                        .map(|window| *date >= window.start && *date <= window.end)
+                        .unwrap_or(true)
+                })
+                .map(|(date, seconds)| RoundingItem::new(entry, date, seconds))
+        })
+        .collect();
+    let raw = items.iter().map(|item| item.seconds).sum();
+    (raw, rounding.rounded_seconds(&items))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::rounding::{RoundingConfig, RoundingMode};
+    use crate::rounding::{RoundingConfig, RoundingMode, RoundingRules};
     use std::collections::HashMap;
 
     #[test]
@@ -151,7 +194,13 @@ mod tests {
             },
         ];
 
-        let grouped = group_entries(&entries, &projects, &HashMap::new(), None);
+        let grouped = group_entries(
+            &entries,
+            &projects,
+            &HashMap::new(),
+            &RoundingPolicy::default(),
+            None,
+        );
         assert_eq!(grouped.len(), 2);
         let project_a = grouped
             .iter()
@@ -192,9 +241,21 @@ mod tests {
         let rounding = RoundingConfig {
             increment_minutes: 15,
             mode: RoundingMode::Closest,
+            ..RoundingConfig::default()
         };
 
-        let grouped = group_entries(&entries, &projects, &HashMap::new(), Some(&rounding));
+        let grouped = group_entries(
+            &entries,
+            &projects,
+            &HashMap::new(),
+            &RoundingPolicy::new(
+                Some(rounding),
+                RoundingScope::Line,
+                RoundingRules::default(),
+                &[],
+            ),
+            None,
+        );
         assert_eq!(grouped.len(), 1);
         let project_a = &grouped[0];
 
@@ -212,4 +273,135 @@ mod tests {
         assert!((ticket2.total_hours - 0.25).abs() < 0.001);
         assert!((project_a.total_hours - 0.5).abs() < 0.001);
     }
+
+    #[test]
+    fn split_window_keeps_only_the_part_inside_the_range() {
+        let projects = vec![Project {
+            id: 1,
+            name: "On-call".to_string(),
+            client_id: None,
+            client_name: None,
+        }];
+
+        let entries = vec![TimeEntry {
+            id: 1,
+            description: Some("Night shift".to_string()),
+            duration: 4 * 3600,
+            start: "2026-02-02T22:00:00Z".to_string(),
+            stop: Some("2026-02-03T02:00:00Z".to_string()),
+            project_id: Some(1),
+        }];
+
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let window = DayWindow {
+            start: day,
+            end: day,
+            timezone: crate::dates::Timezone::Named(chrono_tz::UTC),
+        };
+
+        let grouped = group_entries(
+            &entries,
+            &projects,
+            &HashMap::new(),
+            &RoundingPolicy::default(),
+            Some(&window),
+        );
+        assert!((grouped[0].total_hours - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn project_rounding_rule_overrides_global_per_line() {
+        let projects = vec![
+            Project {
+                id: 1,
+                name: "Client work".to_string(),
+                client_id: Some(10),
+                client_name: Some("Acme".to_string()),
+            },
+            Project {
+                id: 2,
+                name: "Internal".to_string(),
+                client_id: None,
+                client_name: None,
+            },
+        ];
+        let entries = vec![
+            TimeEntry {
+                id: 1,
+                description: Some("Call".to_string()),
+                duration: 7 * 60,
+                start: "2026-02-03T09:00:00Z".to_string(),
+                stop: Some("2026-02-03T09:07:00Z".to_string()),
+                project_id: Some(1),
+            },
+            TimeEntry {
+                id: 2,
+                description: Some("Planning".to_string()),
+                duration: 7 * 60,
+                start: "2026-02-03T10:00:00Z".to_string(),
+                stop: Some("2026-02-03T10:07:00Z".to_string()),
+                project_id: Some(2),
+            },
+        ];
+        let mut rules = RoundingRules::default();
+        rules.clients.insert(
+            10,
+            Some(RoundingConfig {
+                increment_minutes: 6,
+                mode: RoundingMode::Up,
+                ..RoundingConfig::default()
+            }),
+        );
+        rules.projects.insert(2, None);
+        let policy = RoundingPolicy::new(
+            Some(RoundingConfig::default()),
+            RoundingScope::Line,
+            rules,
+            &projects,
+        );
+
+        let grouped = group_entries(&entries, &projects, &HashMap::new(), &policy, None);
+        let client = grouped
+            .iter()
+            .find(|g| g.project_name == "Client work")
+            .unwrap();
+        let internal = grouped
+            .iter()
+            .find(|g| g.project_name == "Internal")
+            .unwrap();
+        assert!((client.total_hours - 0.2).abs() < 0.001);
+        assert!((internal.total_hours - 7.0 / 60.0).abs() < 0.001);
+        assert_eq!(internal.rounding.config, None);
+    }
+
+    #[test]
+    fn day_scope_keeps_lines_raw_and_rounds_the_total() {
+        let entries: Vec<TimeEntry> = ["A", "B"]
+            .iter()
+            .enumerate()
+            .map(|(index, description)| TimeEntry {
+                id: index as u64 + 1,
+                description: Some(description.to_string()),
+                duration: 10 * 60,
+                start: "2026-02-03T09:00:00Z".to_string(),
+                stop: Some("2026-02-03T09:10:00Z".to_string()),
+                project_id: None,
+            })
+            .collect();
+        let rounding = Some(RoundingConfig {
+            increment_minutes: 15,
+            mode: RoundingMode::Closest,
+            ..RoundingConfig::default()
+        });
+        let utc = Timezone::Named(chrono_tz::UTC);
+
+        let line =
+            RoundingPolicy::new(rounding, RoundingScope::Line, RoundingRules::default(), &[]);
+        let day = RoundingPolicy::new(rounding, RoundingScope::Day, RoundingRules::default(), &[]);
+        let grouped = group_entries(&entries, &[], &HashMap::new(), &day, None);
+
+        assert!((grouped[0].total_hours - 20.0 / 60.0).abs() < 0.001);
+        assert_eq!(range_totals(&entries, &line, utc, None), (1200, 1800));
+        assert_eq!(range_totals(&entries, &day, utc, None), (1200, 900));
+    }
 }
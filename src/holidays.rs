@@ -0,0 +1,410 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Countries with a bundled public holiday rule set.
+pub const COUNTRIES: &[&str] = &["BE", "DE", "FR", "GB", "NL", "US"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// Public holidays of `country` (ISO code, case-insensitive) in `year`, or
+/// `None` when the country has no bundled rules.
+pub fn country_holidays(country: &str, year: i32) -> Option<Vec<Holiday>> {
+    let easter = easter_sunday(year)?;
+    let fixed = |month: u32, day: u32, name: &str| {
+        NaiveDate::from_ymd_opt(year, month, day).map(|date| holiday(date, name))
+    };
+    let easter_offset = |days: i64, name: &str| Some(holiday(easter + Duration::days(days), name));
+    let nth = |month: u32, weekday: Weekday, n: i32, name: &str| {
+        nth_weekday(year, month, weekday, n).map(|date| holiday(date, name))
+    };
+
+    let mut holidays: Vec<Holiday> = match country.to_ascii_uppercase().as_str() {
+        "BE" => vec![
+            fixed(1, 1, "New Year's Day"),
+            easter_offset(1, "Easter Monday"),
+            fixed(5, 1, "Labour Day"),
+            easter_offset(39, "Ascension Day"),
+            easter_offset(50, "Whit Monday"),
+            fixed(7, 21, "National Day"),
+            fixed(8, 15, "Assumption Day"),
+            fixed(11, 1, "All Saints' Day"),
+            fixed(11, 11, "Armistice Day"),
+            fixed(12, 25, "Christmas Day"),
+        ],
+        "DE" => vec![
+            fixed(1, 1, "New Year's Day"),
+            easter_offset(-2, "Good Friday"),
+            easter_offset(1, "Easter Monday"),
+            fixed(5, 1, "Labour Day"),
+            easter_offset(39, "Ascension Day"),
+            easter_offset(50, "Whit Monday"),
+            fixed(10, 3, "German Unity Day"),
+            fixed(12, 25, "Christmas Day"),
+            fixed(12, 26, "Second Day of Christmas"),
+        ],
+        "FR" => vec![
+            fixed(1, 1, "New Year's Day"),
+            easter_offset(1, "Easter Monday"),
+            fixed(5, 1, "Labour Day"),
+            fixed(5, 8, "Victory in Europe Day"),
+            easter_offset(39, "Ascension Day"),
+            easter_offset(50, "Whit Monday"),
+            fixed(7, 14, "Bastille Day"),
+            fixed(8, 15, "Assumption Day"),
+            fixed(11, 1, "All Saints' Day"),
+            fixed(11, 11, "Armistice Day"),
+            fixed(12, 25, "Christmas Day"),
+        ],
+        "GB" => {
+            let mut days = vec![
+                fixed(1, 1, "New Year's Day"),
+                easter_offset(-2, "Good Friday"),
+                easter_offset(1, "Easter Monday"),
+                nth(5, Weekday::Mon, 1, "Early May Bank Holiday"),
+                nth(5, Weekday::Mon, -1, "Spring Bank Holiday"),
+                nth(8, Weekday::Mon, -1, "Summer Bank Holiday"),
+                fixed(12, 25, "Christmas Day"),
+                fixed(12, 26, "Boxing Day"),
+            ];
+            substitute_weekends(&mut days);
+            days
+        }
+        "NL" => {
+            // King's Day moves to Saturday when 27 April is a Sunday.
+            let kings_day = fixed(4, 27, "King's Day").map(|day| {
+                if day.date.weekday() == Weekday::Sun {
+                    holiday(day.date - Duration::days(1), &day.name)
+                } else {
+                    day
+                }
+            });
+            vec![
+                fixed(1, 1, "New Year's Day"),
+                easter_offset(1, "Easter Monday"),
+                kings_day,
+                easter_offset(39, "Ascension Day"),
+                easter_offset(50, "Whit Monday"),
+                fixed(12, 25, "Christmas Day"),
+                fixed(12, 26, "Second Day of Christmas"),
+            ]
+        }
+        "US" => {
+            let observed = |day: Option<Holiday>| {
+                day.map(|day| match day.date.weekday() {
+                    Weekday::Sat => holiday(day.date - Duration::days(1), &day.name),
+                    Weekday::Sun => holiday(day.date + Duration::days(1), &day.name),
+                    _ => day,
+                })
+            };
+            vec![
+                observed(fixed(1, 1, "New Year's Day")),
+                nth(1, Weekday::Mon, 3, "Martin Luther King Jr. Day"),
+                nth(2, Weekday::Mon, 3, "Washington's Birthday"),
+                nth(5, Weekday::Mon, -1, "Memorial Day"),
+                observed(fixed(6, 19, "Juneteenth")),
+                observed(fixed(7, 4, "Independence Day")),
+                nth(9, Weekday::Mon, 1, "Labor Day"),
+                nth(10, Weekday::Mon, 2, "Columbus Day"),
+                observed(fixed(11, 11, "Veterans Day")),
+                nth(11, Weekday::Thu, 4, "Thanksgiving Day"),
+                observed(fixed(12, 25, "Christmas Day")),
+            ]
+        }
+        _ => return None,
+    }
+    .into_iter()
+    .flatten()
+    .collect();
+    holidays.sort_by_key(|day| day.date);
+    Some(holidays)
+}
+
+/// Public holidays from a country's rules, worked out for the year of each
+/// lookup, with imported holidays taking precedence.
+#[derive(Debug, Clone, Default)]
+pub struct HolidayCalendar {
+    country: Option<String>,
+    imported: HashMap<NaiveDate, String>,
+}
+
+impl HolidayCalendar {
+    pub fn new(imported: &[Holiday], country: Option<&str>) -> Self {
+        Self {
+            country: country.map(str::to_string),
+            imported: imported
+                .iter()
+                .map(|day| (day.date, day.name.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn name(&self, date: NaiveDate) -> Option<String> {
+        if let Some(name) = self.imported.get(&date) {
+            return Some(name.clone());
+        }
+        let country = self.country.as_deref()?;
+        country_holidays(country, date.year())?
+            .into_iter()
+            .find(|day| day.date == date)
+            .map(|day| day.name)
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.name(date).is_some()
+    }
+}
+
+/// Reads all-day events from an iCalendar file. Multi-day events yield one
+/// holiday per day; timed events count for the day they start.
+pub fn parse_ics(content: &str) -> Result<Vec<Holiday>, String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        match line.strip_prefix([' ', '\t']) {
+            Some(rest) if !lines.is_empty() => {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(rest);
+                }
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+    if !lines
+        .iter()
+        .any(|line| line.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("Not an iCalendar file (missing BEGIN:VCALENDAR).".to_string());
+    }
+
+    let mut holidays = Vec::new();
+    let mut event: Option<(Option<NaiveDate>, Option<NaiveDate>, String)> = None;
+    for line in &lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = key.split(';').next().unwrap_or(key).to_ascii_uppercase();
+        match (name.as_str(), event.as_mut()) {
+            ("BEGIN", _) if value.eq_ignore_ascii_case("VEVENT") => {
+                event = Some((None, None, String::new()));
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                let Some((Some(start), end, summary)) = event.take() else {
+                    continue;
+                };
+                let end = end
+                    .filter(|end| *end > start)
+                    .unwrap_or(start + Duration::days(1));
+                let name = if summary.is_empty() {
+                    "Holiday".to_string()
+                } else {
+                    summary
+                };
+                for date in start.iter_days().take_while(|date| *date < end) {
+                    holidays.push(holiday(date, &name));
+                }
+            }
+            ("DTSTART", Some(current)) => current.0 = Some(parse_ics_date(value)?),
+            // A timed end only bounds the start day.
+            ("DTEND", Some(current)) if value.len() == 8 => {
+                current.1 = Some(parse_ics_date(value)?)
+            }
+            ("SUMMARY", Some(current)) => current.2 = unescape_ics_text(value),
+            _ => {}
+        }
+    }
+    holidays.sort_by_key(|day| day.date);
+    holidays.dedup_by_key(|day| day.date);
+    Ok(holidays)
+}
+
+fn parse_ics_date(value: &str) -> Result<NaiveDate, String> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("Invalid iCalendar date \"{value}\"."))
+}
+
+fn unescape_ics_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push(' '),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+fn holiday(date: NaiveDate, name: &str) -> Holiday {
+    Holiday {
+        date,
+        name: name.to_string(),
+    }
+}
+
+/// Gregorian Easter Sunday (anonymous Gregorian algorithm).
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// The `n`-th `weekday` of a month; negative `n` counts from the end.
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i32) -> Option<NaiveDate> {
+    if n > 0 {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8)
+    } else {
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+        let last = NaiveDate::from_ymd_opt(next_year, next_month, 1)?.pred_opt()?;
+        let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        Some(last - Duration::days(i64::from(back) + 7 * i64::from(-n - 1)))
+    }
+}
+
+/// Moves weekend holidays to the next weekday that is not already a holiday.
+fn substitute_weekends(days: &mut [Option<Holiday>]) {
+    let mut taken: Vec<NaiveDate> = days.iter().flatten().map(|day| day.date).collect();
+    for day in days.iter_mut().flatten() {
+        if day.date.weekday().number_from_monday() <= 5 {
+            continue;
+        }
+        let mut date = day.date;
+        while date.weekday().number_from_monday() > 5 || taken.contains(&date) {
+            date += Duration::days(1);
+        }
+        taken.push(date);
+        day.date = date;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn dates(country: &str, year: i32) -> Vec<NaiveDate> {
+        country_holidays(country, year)
+            .unwrap()
+            .into_iter()
+            .map(|day| day.date)
+            .collect()
+    }
+
+    #[test]
+    fn easter_matches_known_dates() {
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+        assert_eq!(easter_sunday(2027), Some(date(2027, 3, 28)));
+    }
+
+    #[test]
+    fn belgium_has_ten_holidays_with_movable_feasts() {
+        let days = dates("be", 2026);
+        assert_eq!(days.len(), 10);
+        assert!(days.contains(&date(2026, 4, 6)));
+        assert!(days.contains(&date(2026, 5, 14)));
+        assert!(days.contains(&date(2026, 5, 25)));
+        assert!(days.contains(&date(2026, 7, 21)));
+    }
+
+    #[test]
+    fn weekday_rules_and_substitutes() {
+        let us = dates("US", 2026);
+        assert!(us.contains(&date(2026, 11, 26)));
+        assert!(us.contains(&date(2026, 5, 25)));
+        // 4 July 2026 is a Saturday, observed on Friday.
+        assert!(us.contains(&date(2026, 7, 3)));
+
+        // Christmas 2027 is a Saturday: bank holidays on Mon 27 and Tue 28.
+        let gb = dates("GB", 2027);
+        assert!(gb.contains(&date(2027, 12, 27)));
+        assert!(gb.contains(&date(2027, 12, 28)));
+        assert!(!gb.contains(&date(2027, 12, 25)));
+
+        assert!(country_holidays("XX", 2026).is_none());
+    }
+
+    #[test]
+    fn calendar_prefers_imported_names_and_covers_any_year() {
+        let imported = vec![
+            holiday(date(2026, 12, 25), "Kerstmis"),
+            holiday(date(2026, 12, 24), "Office closed"),
+        ];
+        let calendar = HolidayCalendar::new(&imported, Some("BE"));
+
+        assert_eq!(
+            calendar.name(date(2026, 12, 25)).as_deref(),
+            Some("Kerstmis")
+        );
+        assert_eq!(
+            calendar.name(date(2025, 7, 21)).as_deref(),
+            Some("National Day")
+        );
+        assert!(calendar.contains(date(2026, 12, 24)));
+        assert!(calendar.contains(date(2040, 7, 21)));
+        assert!(!calendar.contains(date(2026, 7, 22)));
+        assert!(!HolidayCalendar::new(&[], None).contains(date(2026, 12, 25)));
+    }
+
+    #[test]
+    fn parse_ics_reads_all_day_and_multi_day_events() {
+        let content = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20261225\r\n\
+DTEND;VALUE=DATE:20261227\r\n\
+SUMMARY:Christmas\\, and\r\n  Boxing Day\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20261111T000000Z\r\n\
+DTEND:20261111T235959Z\r\n\
+SUMMARY:Armistice Day\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let holidays = parse_ics(content).unwrap();
+        assert_eq!(
+            holidays,
+            vec![
+                holiday(date(2026, 11, 11), "Armistice Day"),
+                holiday(date(2026, 12, 25), "Christmas, and Boxing Day"),
+                holiday(date(2026, 12, 26), "Christmas, and Boxing Day"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_ics_rejects_other_files() {
+        assert!(parse_ics("date,name\n2026-01-01,New Year").is_err());
+        assert!(parse_ics("BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:2026\nEND:VEVENT").is_err());
+    }
+}
@@ -9,21 +9,32 @@ use crossterm::terminal::{
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 
+mod absences;
+mod allowance;
 mod app;
+mod balance;
+mod cli;
+mod comparison;
 mod dates;
+mod gaps;
 mod grouping;
+mod holidays;
 mod models;
+mod notifications;
+mod patterns;
+mod refetch;
 mod rollups;
 mod rounding;
+mod schedule;
 mod storage;
 mod theme;
 mod theme_studio;
 mod toggl;
 mod ui;
 mod update;
+mod validation;
 
-use app::{App, AppCommand};
-use dates::DateRange;
+use app::{App, AppCommand, Mode};
 use theme_studio::ThemeStudioExit;
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -32,6 +43,13 @@ fn main() -> Result<(), Box<dyn Error>> {
         println!("timeshit {}", env!("CARGO_PKG_VERSION"));
         return Ok(());
     }
+    if args.first().is_some_and(|arg| arg == "rollup") {
+        if let Err(err) = cli::run_rollup(&args[1..]) {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
     if args.iter().any(|arg| arg == "--theme-studio") {
         match theme_studio::run()? {
             ThemeStudioExit::Closed => {}
@@ -42,7 +60,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         return Ok(());
     }
 
-    let date_range = DateRange::today();
+    let start_in = start_in_arg(&args)?;
     let force_login = false;
 
     let mut stdout = std::io::stdout();
@@ -54,7 +72,10 @@ fn main() -> Result<(), Box<dyn Error>> {
     terminal.clear()?;
 
     let needs_update_check = update::should_check_updates();
-    let mut app = App::new(date_range, force_login, needs_update_check);
+    let mut app = App::new(force_login, needs_update_check);
+    if let Some(mode) = start_in {
+        app.set_start_mode(mode);
+    }
 
     loop {
         if app.needs_update_check() {
@@ -67,6 +88,8 @@ fn main() -> Result<(), Box<dyn Error>> {
             app.refresh_data();
         }
 
+        app.check_notifications();
+
         if app.should_quit {
             break;
         }
@@ -102,6 +125,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     terminal.backend_mut().execute(LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
+    if let Err(err) = app.save_ui_state() {
+        eprintln!("Failed to save session state: {err}");
+    }
     if let Some(message) = app.take_exit_message() {
         println!("{message}");
     }
@@ -109,6 +135,27 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Reads `--start-in rollups|dashboard` (or `--start-in=...`).
+fn start_in_arg(args: &[String]) -> Result<Option<Mode>, String> {
+    let value = match args.iter().position(|arg| arg == "--start-in") {
+        Some(index) => args
+            .get(index + 1)
+            .map(String::as_str)
+            .ok_or("--start-in expects `rollups` or `dashboard`.")?,
+        None => match args.iter().find_map(|arg| arg.strip_prefix("--start-in=")) {
+            Some(value) => value,
+            None => return Ok(None),
+        },
+    };
+    match value {
+        "rollups" => Ok(Some(Mode::Rollups)),
+        "dashboard" => Ok(Some(Mode::Dashboard)),
+        other => Err(format!(
+            "Unknown --start-in value `{other}`; expected `rollups` or `dashboard`."
+        )),
+    }
+}
+
 fn run_theme_studio_session(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     app: &mut App,
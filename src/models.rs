@@ -32,3 +32,10 @@ pub struct TimeEntry {
     #[serde(rename = "project_id")]
     pub project_id: Option<u64>,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Me {
+    pub id: u64,
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
@@ -0,0 +1,241 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+const APP_NAME: &str = "timeshit";
+
+/// Worked and target hours for a day or a span of days.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Progress {
+    pub worked: f64,
+    pub target: f64,
+}
+
+impl Progress {
+    pub fn delta(&self) -> f64 {
+        let delta = self.worked - self.target;
+        if delta.abs() < 0.005 { 0.0 } else { delta }
+    }
+}
+
+/// Progress of the current week, split so days before today can be judged
+/// without the day still in progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WeekProgress {
+    pub before_today: Progress,
+    pub total: Progress,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub summary: String,
+    pub body: String,
+}
+
+/// Remembers what was already announced so each notification fires once a
+/// day. The first check only records the current state, so starting the app
+/// late in the day does not replay the target or end-of-day notifications.
+#[derive(Debug, Default)]
+pub struct NotificationTracker {
+    primed: bool,
+    target_reached_on: Option<NaiveDate>,
+    week_warned_on: Option<NaiveDate>,
+    summary_sent_on: Option<NaiveDate>,
+}
+
+impl NotificationTracker {
+    pub fn check(
+        &mut self,
+        now: NaiveDateTime,
+        end_of_day: Option<NaiveTime>,
+        today: Progress,
+        week: WeekProgress,
+    ) -> Vec<Notification> {
+        let date = now.date();
+        let target_reached = today.target > 0.0 && today.delta() >= 0.0;
+        let summary_due = end_of_day.is_some_and(|time| now.time() >= time);
+        if !self.primed {
+            self.primed = true;
+            if target_reached {
+                self.target_reached_on = Some(date);
+            }
+            if summary_due {
+                self.summary_sent_on = Some(date);
+            }
+        }
+
+        let mut notifications = Vec::new();
+        if target_reached && self.target_reached_on != Some(date) {
+            self.target_reached_on = Some(date);
+            notifications.push(Notification {
+                summary: "Daily target reached".to_string(),
+                body: format!(
+                    "{:.2}h of {:.2}h tracked today.",
+                    today.worked, today.target
+                ),
+            });
+        }
+
+        let behind = week.before_today.delta();
+        if behind < 0.0 && self.week_warned_on != Some(date) {
+            self.week_warned_on = Some(date);
+            notifications.push(Notification {
+                summary: "Week behind target".to_string(),
+                body: format!(
+                    "{:.2}h short before today. {:.2}h of {:.2}h tracked this week.",
+                    -behind, week.total.worked, week.total.target
+                ),
+            });
+        }
+
+        if summary_due && self.summary_sent_on != Some(date) {
+            self.summary_sent_on = Some(date);
+            notifications.push(Notification {
+                summary: "End of day".to_string(),
+                body: format!(
+                    "Today {:.2}h of {:.2}h ({:+.2}h). Week {:.2}h of {:.2}h ({:+.2}h).",
+                    today.worked,
+                    today.target,
+                    today.delta(),
+                    week.total.worked,
+                    week.total.target,
+                    week.total.delta()
+                ),
+            });
+        }
+        notifications
+    }
+}
+
+/// Parses an end-of-day time as `HH:MM`. Empty input turns the summary off.
+pub fn parse_end_of_day(value: &str) -> Result<Option<NaiveTime>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map(Some)
+        .map_err(|_| format!("Invalid time '{value}'. Use HH:MM."))
+}
+
+/// Shows a notification through the freedesktop notification service on
+/// the session bus.
+#[cfg(feature = "notifications")]
+pub fn send(notification: &Notification) -> Result<(), String> {
+    use std::collections::HashMap;
+    use zbus::blocking::Connection;
+    use zbus::zvariant::Value;
+
+    let connection = Connection::session().map_err(|err| err.to_string())?;
+    let actions: Vec<&str> = Vec::new();
+    let hints: HashMap<&str, Value> = HashMap::new();
+    connection
+        .call_method(
+            Some("org.freedesktop.Notifications"),
+            "/org/freedesktop/Notifications",
+            Some("org.freedesktop.Notifications"),
+            "Notify",
+            &(
+                APP_NAME,
+                0u32,
+                "",
+                notification.summary.as_str(),
+                notification.body.as_str(),
+                actions,
+                hints,
+                -1i32,
+            ),
+        )
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "notifications"))]
+pub fn send(_notification: &Notification) -> Result<(), String> {
+    Err(format!(
+        "{APP_NAME} was built without desktop notification support"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 14)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn progress(worked: f64, target: f64) -> Progress {
+        Progress { worked, target }
+    }
+
+    fn on_track() -> WeekProgress {
+        WeekProgress {
+            before_today: progress(16.0, 16.0),
+            total: progress(20.0, 40.0),
+        }
+    }
+
+    #[test]
+    fn target_reached_fires_once_when_crossed() {
+        let mut tracker = NotificationTracker::default();
+        assert!(
+            tracker
+                .check(at(9, 0), None, progress(1.0, 8.0), on_track())
+                .is_empty()
+        );
+
+        let fired = tracker.check(at(17, 0), None, progress(8.0, 8.0), on_track());
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].summary, "Daily target reached");
+        assert!(
+            tracker
+                .check(at(17, 30), None, progress(8.5, 8.0), on_track())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn first_check_does_not_replay_the_day() {
+        let mut tracker = NotificationTracker::default();
+        let end_of_day = NaiveTime::from_hms_opt(17, 0, 0);
+        assert!(
+            tracker
+                .check(at(18, 0), end_of_day, progress(8.0, 8.0), on_track())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn end_of_day_summary_and_week_warning() {
+        let mut tracker = NotificationTracker::default();
+        let end_of_day = NaiveTime::from_hms_opt(17, 0, 0);
+        let behind = WeekProgress {
+            before_today: progress(12.5, 16.0),
+            total: progress(15.0, 40.0),
+        };
+
+        let fired = tracker.check(at(16, 0), end_of_day, progress(2.5, 8.0), behind);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].body.starts_with("3.50h short before today."));
+
+        let fired = tracker.check(at(17, 0), end_of_day, progress(2.5, 8.0), behind);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].summary, "End of day");
+        assert_eq!(
+            fired[0].body,
+            "Today 2.50h of 8.00h (-5.50h). Week 15.00h of 40.00h (-25.00h)."
+        );
+    }
+
+    #[test]
+    fn parse_end_of_day_accepts_hh_mm() {
+        assert_eq!(parse_end_of_day(""), Ok(None));
+        assert_eq!(
+            parse_end_of_day("17:30"),
+            Ok(NaiveTime::from_hms_opt(17, 30, 0))
+        );
+        assert!(parse_end_of_day("5pm").is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use std::collections::BTreeMap;
+
+use crate::dates::Timezone;
+use crate::models::TimeEntry;
+
+/// Tracked time from this hour on counts as evening work.
+pub const EVENING_HOUR: u32 = 18;
+
+/// How a day counts towards untracked streaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapDay {
+    /// Time is expected to be tracked.
+    Workday,
+    /// Weekend, holiday or absence; skipped without ending a streak.
+    Off,
+    /// Not fetched, so nothing is known; ends a streak.
+    Unknown,
+}
+
+/// Consecutive workdays without tracked time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days: usize,
+}
+
+/// When time was tracked over a span of days.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkPattern {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Tracked seconds by weekday (Monday first) and hour of the day.
+    pub heatmap: [[i64; 24]; 7],
+    pub total_seconds: i64,
+    pub evening_seconds: i64,
+    pub tracked_days: usize,
+    /// Average minutes after midnight of the first start on tracked days.
+    pub average_start: Option<u32>,
+    /// Average minutes after midnight of the last stop; stops past midnight
+    /// count beyond 24:00.
+    pub average_end: Option<u32>,
+    pub untracked_workdays: usize,
+    pub longest_untracked: Option<Streak>,
+}
+
+impl WorkPattern {
+    pub fn max_cell(&self) -> i64 {
+        self.heatmap
+            .iter()
+            .flat_map(|hours| hours.iter())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Builds the pattern of finished entries between `start` and `end`, in
+/// local time. Entries are split at hour boundaries and clipped to the span.
+pub fn build_work_pattern(
+    entries: &[TimeEntry],
+    start: NaiveDate,
+    end: NaiveDate,
+    timezone: Timezone,
+    day_kind: impl Fn(NaiveDate) -> GapDay,
+) -> WorkPattern {
+    let mut heatmap = [[0i64; 24]; 7];
+    let mut total_seconds = 0;
+    let mut evening_seconds = 0;
+    let mut day_seconds: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    // First start and last stop per day, in minutes after that midnight.
+    let mut day_bounds: BTreeMap<NaiveDate, (i64, i64)> = BTreeMap::new();
+
+    for entry in entries {
+        let Some((entry_start, entry_stop)) = local_bounds(entry, timezone) else {
+            continue;
+        };
+        let start_date = entry_start.date();
+        if start_date >= start && start_date <= end {
+            let midnight = start_date.and_time(chrono::NaiveTime::MIN);
+            let first = (entry_start - midnight).num_minutes();
+            let last = (entry_stop - midnight).num_minutes();
+            let bounds = day_bounds.entry(start_date).or_insert((first, last));
+            bounds.0 = bounds.0.min(first);
+            bounds.1 = bounds.1.max(last);
+        }
+
+        let mut cursor = entry_start;
+        while cursor < entry_stop {
+            let hour_start = cursor
+                .date()
+                .and_hms_opt(cursor.hour(), 0, 0)
+                .unwrap_or(cursor);
+            let next = (hour_start + Duration::hours(1)).min(entry_stop);
+            let date = cursor.date();
+            if date >= start && date <= end {
+                let seconds = (next - cursor).num_seconds();
+                let weekday = date.weekday().num_days_from_monday() as usize;
+                heatmap[weekday][cursor.hour() as usize] += seconds;
+                total_seconds += seconds;
+                if cursor.hour() >= EVENING_HOUR {
+                    evening_seconds += seconds;
+                }
+                *day_seconds.entry(date).or_default() += seconds;
+            }
+            cursor = next;
+        }
+    }
+
+    let average = |values: Vec<i64>| {
+        (!values.is_empty()).then(|| {
+            let sum: i64 = values.iter().sum();
+            (sum as f64 / values.len() as f64).round().max(0.0) as u32
+        })
+    };
+    let average_start = average(day_bounds.values().map(|bounds| bounds.0).collect());
+    let average_end = average(day_bounds.values().map(|bounds| bounds.1).collect());
+
+    let mut untracked_workdays = 0;
+    let mut longest_untracked: Option<Streak> = None;
+    let mut current: Option<Streak> = None;
+    for day in start.iter_days().take_while(|day| *day <= end) {
+        match day_kind(day) {
+            GapDay::Off => continue,
+            GapDay::Unknown => current = None,
+            GapDay::Workday if day_seconds.get(&day).copied().unwrap_or(0) > 0 => {
+                current = None;
+            }
+            GapDay::Workday => {
+                untracked_workdays += 1;
+                let streak = match current {
+                    Some(streak) => Streak {
+                        end: day,
+                        days: streak.days + 1,
+                        ..streak
+                    },
+                    None => Streak {
+                        start: day,
+                        end: day,
+                        days: 1,
+                    },
+                };
+                current = Some(streak);
+                if longest_untracked.is_none_or(|longest| streak.days > longest.days) {
+                    longest_untracked = Some(streak);
+                }
+            }
+        }
+    }
+
+    WorkPattern {
+        start,
+        end,
+        heatmap,
+        total_seconds,
+        evening_seconds,
+        tracked_days: day_seconds.values().filter(|seconds| **seconds > 0).count(),
+        average_start,
+        average_end,
+        untracked_workdays,
+        longest_untracked,
+    }
+}
+
+/// `HH:MM`, with `+1` when the time falls after midnight of the next day.
+pub fn clock_label(minutes: u32) -> String {
+    let days = minutes / (24 * 60);
+    let minutes = minutes % (24 * 60);
+    let time = format!("{:02}:{:02}", minutes / 60, minutes % 60);
+    if days > 0 {
+        format!("{time}+{days}")
+    } else {
+        time
+    }
+}
+
+fn local_bounds(entry: &TimeEntry, timezone: Timezone) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let start = DateTime::parse_from_rfc3339(&entry.start).ok()?;
+    let stop = DateTime::parse_from_rfc3339(entry.stop.as_deref()?).ok()?;
+    if stop <= start {
+        return None;
+    }
+    Some((
+        timezone.convert(&start).naive_local(),
+        timezone.convert(&stop).naive_local(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UTC: Timezone = Timezone::Named(chrono_tz::UTC);
+
+    fn entry(start: &str, stop: &str) -> TimeEntry {
+        TimeEntry {
+            id: 1,
+            description: None,
+            duration: 0,
+            start: start.to_string(),
+            stop: Some(stop.to_string()),
+            project_id: None,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn heatmap_splits_entries_at_hour_boundaries() {
+        // Monday 2026-03-02.
+        let entries = vec![
+            entry("2026-03-02T08:30:00Z", "2026-03-02T10:15:00Z"),
+            entry("2026-03-02T17:45:00Z", "2026-03-02T19:00:00Z"),
+        ];
+        let pattern = build_work_pattern(&entries, date(2), date(2), UTC, |_| GapDay::Workday);
+
+        assert_eq!(pattern.heatmap[0][8], 30 * 60);
+        assert_eq!(pattern.heatmap[0][9], 3600);
+        assert_eq!(pattern.heatmap[0][10], 15 * 60);
+        assert_eq!(pattern.heatmap[0][17], 15 * 60);
+        assert_eq!(pattern.evening_seconds, 3600);
+        assert_eq!(pattern.average_start, Some(8 * 60 + 30));
+        assert_eq!(pattern.average_end, Some(19 * 60));
+    }
+
+    #[test]
+    fn late_stops_count_past_midnight() {
+        let entries = vec![
+            entry("2026-03-02T09:00:00Z", "2026-03-02T17:00:00Z"),
+            entry("2026-03-03T09:00:00Z", "2026-03-04T01:00:00Z"),
+        ];
+        let pattern = build_work_pattern(&entries, date(2), date(3), UTC, |_| GapDay::Workday);
+
+        assert_eq!(pattern.average_end, Some(21 * 60));
+        assert_eq!(pattern.heatmap[2][0], 0, "clipped to the span");
+        assert_eq!(clock_label(25 * 60 + 5), "01:05+1");
+        assert_eq!(clock_label(9 * 60), "09:00");
+    }
+
+    #[test]
+    fn untracked_streaks_skip_off_days_and_stop_at_unknown_days() {
+        let entries = vec![entry("2026-03-02T09:00:00Z", "2026-03-02T10:00:00Z")];
+        let kind = |day: NaiveDate| match day.day() {
+            7 | 8 => GapDay::Off,
+            11 => GapDay::Unknown,
+            _ => GapDay::Workday,
+        };
+        let pattern = build_work_pattern(&entries, date(2), date(13), UTC, kind);
+
+        assert_eq!(
+            pattern.longest_untracked,
+            Some(Streak {
+                start: date(3),
+                end: date(10),
+                days: 6,
+            })
+        );
+        assert_eq!(pattern.untracked_workdays, 8);
+        assert_eq!(pattern.tracked_days, 1);
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+
+/// How long a fetch made after a day was over keeps that day fresh.
+pub const FRESH_FOR_HOURS: i64 = 24;
+
+/// Cache state of one day that may be refetched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayCoverage {
+    pub date: NaiveDate,
+    /// Latest fetch of any cached range covering the day.
+    pub fetched_at: Option<DateTime<FixedOffset>>,
+    /// End of the day in the bucketing timezone.
+    pub day_end: DateTime<FixedOffset>,
+}
+
+impl DayCoverage {
+    /// Fetched after the day was over, and recently enough that nothing
+    /// is likely to have changed since.
+    pub fn is_fresh(&self, now: DateTime<FixedOffset>) -> bool {
+        self.fetched_at.is_some_and(|fetched_at| {
+            fetched_at >= self.day_end && now - fetched_at < Duration::hours(FRESH_FOR_HOURS)
+        })
+    }
+}
+
+/// Contiguous days fetched with a single range call.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefetchBatch {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub unfetched_days: usize,
+    pub stale_days: usize,
+    /// Oldest fetch among the stale days in the batch.
+    pub oldest_fetch: Option<DateTime<FixedOffset>>,
+}
+
+impl RefetchBatch {
+    pub fn of_day(day: &DayCoverage) -> Self {
+        let mut batch = Self {
+            start: day.date,
+            end: day.date,
+            unfetched_days: 0,
+            stale_days: 0,
+            oldest_fetch: None,
+        };
+        batch.add(day);
+        batch
+    }
+
+    pub fn days(&self) -> usize {
+        self.unfetched_days + self.stale_days
+    }
+
+    fn add(&mut self, day: &DayCoverage) {
+        self.start = self.start.min(day.date);
+        self.end = self.end.max(day.date);
+        match day.fetched_at {
+            None => self.unfetched_days += 1,
+            Some(fetched_at) => {
+                self.stale_days += 1;
+                self.oldest_fetch = Some(
+                    self.oldest_fetch
+                        .map_or(fetched_at, |oldest| oldest.min(fetched_at)),
+                );
+            }
+        }
+    }
+}
+
+/// Covers the days of one period that are not fresh with a single batch,
+/// from the first of them to the last. Fresh and skipped days in between
+/// are fetched along, since one range call costs the same quota as a day.
+pub fn plan_batches(days: &[DayCoverage], now: DateTime<FixedOffset>) -> Vec<RefetchBatch> {
+    let mut batch: Option<RefetchBatch> = None;
+    for day in days.iter().filter(|day| !day.is_fresh(now)) {
+        match batch.as_mut() {
+            Some(batch) => batch.add(day),
+            None => batch = Some(RefetchBatch::of_day(day)),
+        }
+    }
+    batch.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(&format!("2026-03-{day:02}T{hour:02}:00:00Z")).unwrap()
+    }
+
+    fn day(day: u32, fetched_at: Option<DateTime<FixedOffset>>) -> DayCoverage {
+        DayCoverage {
+            date: date(day),
+            fetched_at,
+            day_end: at(day, 23),
+        }
+    }
+
+    fn spans(batches: &[RefetchBatch]) -> Vec<(NaiveDate, NaiveDate)> {
+        batches
+            .iter()
+            .map(|batch| (batch.start, batch.end))
+            .collect()
+    }
+
+    #[test]
+    fn fresh_days_need_a_fetch_after_the_day_ended() {
+        let now = at(10, 12);
+
+        assert!(day(9, Some(at(10, 8))).is_fresh(now));
+        assert!(!day(9, Some(at(9, 18))).is_fresh(now));
+        assert!(!day(5, Some(at(6, 8))).is_fresh(now));
+        assert!(!day(9, None).is_fresh(now));
+    }
+
+    #[test]
+    fn one_batch_bridges_fresh_and_skipped_days() {
+        let now = at(20, 12);
+        let days = vec![
+            day(9, Some(at(10, 8))),
+            day(2, Some(at(5, 8))),
+            day(3, Some(at(5, 8))),
+            day(4, Some(at(20, 8))),
+            day(5, Some(at(6, 8))),
+            day(6, None),
+            day(12, Some(at(20, 8))),
+        ];
+        let batches = plan_batches(&days, now);
+
+        assert_eq!(spans(&batches), vec![(date(2), date(9))]);
+        assert_eq!(batches[0].unfetched_days, 1);
+        assert_eq!(batches[0].stale_days, 4);
+        assert_eq!(batches[0].oldest_fetch, Some(at(5, 8)));
+    }
+
+    #[test]
+    fn fresh_periods_need_no_batch() {
+        let now = at(20, 12);
+        let days = vec![day(2, Some(at(20, 8))), day(3, Some(at(20, 8)))];
+
+        assert!(plan_batches(&days, now).is_empty());
+    }
+}
@@ -1,14 +1,21 @@
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use chrono::{DateTime, Datelike, Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use crate::dates::{Timezone, WeekStart, start_of_week};
 use crate::models::TimeEntry;
-use crate::rounding::{RoundingConfig, round_seconds};
+use crate::rounding::{RoundingItem, RoundingPolicy};
+
+/// Rounded seconds keyed by project id, or by client id once passed
+/// through [`client_breakdown`]. `None` collects entries without one.
+pub type Breakdown = BTreeMap<Option<u64>, i64>;
 
 #[derive(Debug, Clone)]
 pub struct DailyTotal {
     pub date: NaiveDate,
     pub seconds: i64,
+    pub raw_seconds: i64,
+    pub projects: Breakdown,
 }
 
 #[derive(Debug, Clone)]
@@ -18,6 +25,16 @@ pub struct PeriodRollup {
     pub end: NaiveDate,
     pub days: usize,
     pub seconds: i64,
+    pub raw_seconds: i64,
+    pub projects: Breakdown,
+}
+
+/// One row of a breakdown, with its share of the breakdown total.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakdownShare {
+    pub id: Option<u64>,
+    pub seconds: i64,
+    pub percent: f64,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -25,71 +42,391 @@ pub struct Rollups {
     pub daily: Vec<DailyTotal>,
     pub weekly: Vec<PeriodRollup>,
     pub monthly: Vec<PeriodRollup>,
+    pub quarterly: Vec<PeriodRollup>,
     pub yearly: Vec<PeriodRollup>,
+    /// Periods of the selected billing cycle, when one is configured.
+    pub cycle: Vec<PeriodRollup>,
+}
+
+/// How calendar periods are cut: the first day of the week and the month
+/// the fiscal year starts in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodCalendar {
+    pub week_start: WeekStart,
+    pub fiscal_year_start: u32,
+}
+
+impl Default for PeriodCalendar {
+    fn default() -> Self {
+        Self {
+            week_start: WeekStart::Monday,
+            fiscal_year_start: 1,
+        }
+    }
+}
+
+impl PeriodCalendar {
+    /// Fiscal year of `date`, named after the calendar year it starts in.
+    pub fn fiscal_year(&self, date: NaiveDate) -> i32 {
+        if date.month() >= self.fiscal_year_start {
+            date.year()
+        } else {
+            date.year() - 1
+        }
+    }
+
+    /// First and last day of fiscal year `year`.
+    pub fn fiscal_year_bounds(&self, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+        let start = NaiveDate::from_ymd_opt(year, self.fiscal_year_start, 1)?;
+        let end = start
+            .checked_add_months(chrono::Months::new(12))?
+            .pred_opt()?;
+        Some((start, end))
+    }
+
+    /// `2026` for calendar years, `FY2026/27` when the year starts later.
+    pub fn fiscal_year_label(&self, year: i32) -> String {
+        if self.fiscal_year_start == 1 {
+            year.to_string()
+        } else {
+            format!("FY{}/{:02}", year, (year + 1).rem_euclid(100))
+        }
+    }
+
+    /// Calendar year to show for fiscal year `year`: the year of `date` when
+    /// it falls in that fiscal year, otherwise the year the fiscal year
+    /// starts in.
+    pub fn calendar_year_of(&self, year: i32, date: NaiveDate) -> i32 {
+        if self.fiscal_year(date) == year {
+            date.year()
+        } else {
+            year
+        }
+    }
+
+    /// Fiscal year and quarter (1 to 4) of `date`.
+    pub fn fiscal_quarter(&self, date: NaiveDate) -> (i32, u32) {
+        let months_in = (date.month() + 12 - self.fiscal_year_start) % 12;
+        (self.fiscal_year(date), months_in / 3 + 1)
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "lowercase")]
-pub enum WeekStart {
-    #[default]
-    Monday,
-    Sunday,
+/// A custom billing period from the config file, such as two-week sprints or
+/// months running from the 26th to the 25th.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BillingCycle {
+    pub name: String,
+    #[serde(flatten)]
+    pub rule: CycleRule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CycleRule {
+    /// Runs of `weeks` weeks, one of which starts on `anchor`.
+    Weeks { weeks: u32, anchor: NaiveDate },
+    /// Months starting on `start_day` and ending the day before it.
+    Months { start_day: u32 },
+}
+
+impl BillingCycle {
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("Billing cycle name cannot be empty.".to_string());
+        }
+        match self.rule {
+            CycleRule::Weeks { weeks, .. } if !(1..=52).contains(&weeks) => {
+                Err(format!("Billing cycle '{name}' must span 1 to 52 weeks."))
+            }
+            CycleRule::Months { start_day } if !(1..=28).contains(&start_day) => {
+                Err(format!("Billing cycle '{name}' must start on day 1 to 28."))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// First day of the cycle containing `date`.
+    pub fn cycle_start(&self, date: NaiveDate) -> NaiveDate {
+        match self.rule {
+            CycleRule::Weeks { weeks, anchor } => {
+                let length = 7 * i64::from(weeks.max(1));
+                date - Duration::days((date - anchor).num_days().rem_euclid(length))
+            }
+            CycleRule::Months { start_day } => {
+                let month_start = date.with_day(start_day).unwrap_or(date);
+                if date.day() >= start_day {
+                    month_start
+                } else {
+                    month_start
+                        .checked_sub_months(chrono::Months::new(1))
+                        .unwrap_or(month_start)
+                }
+            }
+        }
+    }
+
+    /// Last day of the cycle starting on `start`.
+    pub fn cycle_end(&self, start: NaiveDate) -> NaiveDate {
+        let next = match self.rule {
+            CycleRule::Weeks { weeks, .. } => start + Duration::weeks(i64::from(weeks.max(1))),
+            CycleRule::Months { .. } => start
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap_or(start),
+        };
+        next.pred_opt().unwrap_or(start)
+    }
 }
 
 pub fn build_rollups(
     entries: &[TimeEntry],
     start: NaiveDate,
     end: NaiveDate,
-    rounding: Option<&RoundingConfig>,
-    week_start: WeekStart,
+    rounding: &RoundingPolicy,
+    calendar: PeriodCalendar,
+    timezone: Timezone,
+    split_at_midnight: bool,
 ) -> Rollups {
-    let mut totals: HashMap<NaiveDate, i64> = HashMap::new();
-
-    for entry in entries {
-        let Some(date) = parse_entry_date(entry) else {
-            continue;
-        };
-        if date < start || date > end {
-            continue;
-        }
-        let duration = rounding
-            .map(|cfg| round_seconds(entry.duration, cfg))
-            .unwrap_or(entry.duration);
-        *totals.entry(date).or_insert(0) += duration;
+    let items = collect_day_items(entries, start, end, timezone, split_at_midnight);
+    let daily = build_daily_totals(&items, rounding, start, end);
+    let mut weekly = build_weekly_rollups(&daily, calendar.week_start);
+    let mut monthly = build_monthly_rollups(&daily);
+    let mut quarterly = build_quarterly_rollups(&daily, calendar);
+    let mut yearly = build_yearly_rollups(&daily, calendar);
+    for rollup in weekly
+        .iter_mut()
+        .chain(&mut monthly)
+        .chain(&mut quarterly)
+        .chain(&mut yearly)
+    {
+        fill_period_totals(rollup, &items, rounding);
     }
 
-    let daily = build_daily_totals(&totals, start, end);
-    let weekly = build_weekly_rollups(&daily, week_start);
-    let monthly = build_monthly_rollups(&daily);
-    let yearly = build_yearly_rollups(&daily);
-
     Rollups {
         daily,
         weekly,
         monthly,
+        quarterly,
         yearly,
+        cycle: Vec::new(),
     }
 }
 
-fn parse_entry_date(entry: &TimeEntry) -> Option<NaiveDate> {
-    DateTime::parse_from_rfc3339(&entry.start)
-        .ok()
-        .map(|dt| dt.with_timezone(&Local))
-        .map(|dt| dt.date_naive())
+/// Rollups for the periods of a billing cycle between `start` and `end`.
+pub fn build_cycle_rollups(
+    entries: &[TimeEntry],
+    start: NaiveDate,
+    end: NaiveDate,
+    rounding: &RoundingPolicy,
+    cycle: &BillingCycle,
+    timezone: Timezone,
+    split_at_midnight: bool,
+) -> Vec<PeriodRollup> {
+    let items = collect_day_items(entries, start, end, timezone, split_at_midnight);
+    let daily = build_daily_totals(&items, rounding, start, end);
+    let mut rollups = build_cycle_periods(&daily, cycle);
+    for rollup in &mut rollups {
+        fill_period_totals(rollup, &items, rounding);
+    }
+    rollups
+}
+
+/// A single period covering exactly `start..=end`, with its daily totals.
+pub fn build_range_rollup(
+    entries: &[TimeEntry],
+    start: NaiveDate,
+    end: NaiveDate,
+    rounding: &RoundingPolicy,
+    timezone: Timezone,
+    split_at_midnight: bool,
+) -> (PeriodRollup, Vec<DailyTotal>) {
+    let items = collect_day_items(entries, start, end, timezone, split_at_midnight);
+    let daily = build_daily_totals(&items, rounding, start, end);
+    let mut rollup = PeriodRollup {
+        label: format!("{} → {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d")),
+        start,
+        end,
+        days: daily.len(),
+        seconds: 0,
+        raw_seconds: daily.iter().map(|day| day.raw_seconds).sum(),
+        projects: Breakdown::new(),
+    };
+    fill_period_totals(&mut rollup, &items, rounding);
+    (rollup, daily)
+}
+
+fn collect_day_items(
+    entries: &[TimeEntry],
+    start: NaiveDate,
+    end: NaiveDate,
+    timezone: Timezone,
+    split_at_midnight: bool,
+) -> BTreeMap<NaiveDate, Vec<RoundingItem>> {
+    let mut items: BTreeMap<NaiveDate, Vec<RoundingItem>> = BTreeMap::new();
+    for entry in entries {
+        for (date, seconds) in
+            split_seconds_by_day(entry, entry.duration, timezone, split_at_midnight)
+        {
+            if date < start || date > end {
+                continue;
+            }
+            items
+                .entry(date)
+                .or_default()
+                .push(RoundingItem::new(entry, date, seconds));
+        }
+    }
+    items
+}
+
+/// Rounds the items inside `rollup` as a whole, so period rounding applies.
+fn fill_period_totals(
+    rollup: &mut PeriodRollup,
+    items: &BTreeMap<NaiveDate, Vec<RoundingItem>>,
+    rounding: &RoundingPolicy,
+) {
+    let period_items = || {
+        items
+            .range(rollup.start..=rollup.end)
+            .flat_map(|(_, items)| items)
+    };
+    rollup.seconds = rounding.rounded_seconds(period_items());
+    rollup.projects = project_breakdown(period_items(), rounding);
+}
+
+/// Rounds each project's items on their own, so project rules apply.
+fn project_breakdown<'a>(
+    items: impl IntoIterator<Item = &'a RoundingItem>,
+    rounding: &RoundingPolicy,
+) -> Breakdown {
+    let mut by_project: BTreeMap<Option<u64>, Vec<&RoundingItem>> = BTreeMap::new();
+    for item in items {
+        by_project.entry(item.project_id).or_default().push(item);
+    }
+    by_project
+        .into_iter()
+        .map(|(project_id, items)| (project_id, rounding.rounded_seconds(items)))
+        .filter(|(_, seconds)| *seconds != 0)
+        .collect()
+}
+
+/// Folds a project breakdown into clients using `project_clients`.
+pub fn client_breakdown(projects: &Breakdown, project_clients: &HashMap<u64, u64>) -> Breakdown {
+    let mut clients = Breakdown::new();
+    for (project_id, seconds) in projects {
+        let client_id = project_id.and_then(|id| project_clients.get(&id).copied());
+        *clients.entry(client_id).or_default() += seconds;
+    }
+    clients
+}
+
+/// The `limit` largest rows of `breakdown`, biggest first, plus the seconds
+/// left over in the rest.
+pub fn top_shares(breakdown: &Breakdown, limit: usize) -> (Vec<BreakdownShare>, i64) {
+    let total: i64 = breakdown.values().sum();
+    let mut rows: Vec<(Option<u64>, i64)> = breakdown
+        .iter()
+        .map(|(id, seconds)| (*id, *seconds))
+        .collect();
+    rows.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(&right.0)));
+    let other = rows.iter().skip(limit).map(|(_, seconds)| seconds).sum();
+    let shares = rows
+        .into_iter()
+        .take(limit)
+        .map(|(id, seconds)| BreakdownShare {
+            id,
+            seconds,
+            percent: if total > 0 {
+                seconds as f64 * 100.0 / total as f64
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    (shares, other)
+}
+
+/// Calendar window that entries crossing midnight are clipped to.
+#[derive(Debug, Clone, Copy)]
+pub struct DayWindow {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub timezone: Timezone,
+}
+
+impl DayWindow {
+    /// Part of `seconds` that falls inside the window.
+    pub fn clip_seconds(&self, entry: &TimeEntry, seconds: i64) -> i64 {
+        split_seconds_by_day(entry, seconds, self.timezone, true)
+            .into_iter()
+            .filter(|(date, _)| *date >= self.start && *date <= self.end)
+            .map(|(_, seconds)| seconds)
+            .sum()
+    }
+}
+
+/// Attributes `seconds` of `entry` to calendar days. Without splitting the
+/// whole amount goes to the start date; with splitting it is spread over the
+/// days between start and stop, proportionally to the wall-clock time spent in
+/// each one.
+pub fn split_seconds_by_day(
+    entry: &TimeEntry,
+    seconds: i64,
+    timezone: Timezone,
+    split_at_midnight: bool,
+) -> Vec<(NaiveDate, i64)> {
+    let Ok(start) = DateTime::parse_from_rfc3339(&entry.start) else {
+        return Vec::new();
+    };
+    let start_date = timezone.date_of(&start);
+    let stop = entry
+        .stop
+        .as_deref()
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .unwrap_or_else(|| start + Duration::seconds(entry.duration.max(0)));
+    let span = (stop - start).num_seconds();
+    if !split_at_midnight || span <= 0 || seconds <= 0 {
+        return vec![(start_date, seconds)];
+    }
+
+    let mut pieces = Vec::new();
+    let mut day = start_date;
+    let mut allocated = 0i64;
+    loop {
+        let Some(next_day) = day.succ_opt() else {
+            pieces.push((day, seconds - allocated));
+            break;
+        };
+        let next_start = timezone.start_of_day(next_day);
+        if stop <= next_start {
+            pieces.push((day, seconds - allocated));
+            break;
+        }
+        let elapsed = (next_start - start).num_seconds();
+        let share = (seconds as i128 * elapsed as i128 + span as i128 / 2) / span as i128;
+        let share = share as i64;
+        pieces.push((day, share - allocated));
+        allocated = share;
+        day = next_day;
+    }
+    pieces
 }
 
 fn build_daily_totals(
-    totals: &HashMap<NaiveDate, i64>,
+    items: &BTreeMap<NaiveDate, Vec<RoundingItem>>,
+    rounding: &RoundingPolicy,
     start: NaiveDate,
     end: NaiveDate,
 ) -> Vec<DailyTotal> {
     let mut daily = Vec::new();
     let mut current = start;
     while current <= end {
-        let seconds = *totals.get(&current).unwrap_or(&0);
+        let day_items = items.get(&current).map(Vec::as_slice).unwrap_or_default();
         daily.push(DailyTotal {
             date: current,
-            seconds,
+            seconds: rounding.rounded_seconds(day_items),
+            raw_seconds: day_items.iter().map(|item| item.seconds).sum(),
+            projects: project_breakdown(day_items, rounding),
         });
         current = current.succ_opt().unwrap_or(current + Duration::days(1));
     }
@@ -122,13 +459,15 @@ fn build_weekly_rollups(daily: &[DailyTotal], week_start: WeekStart) -> Vec<Peri
                 end: day.date,
                 days: 0,
                 seconds: 0,
+                raw_seconds: 0,
+                projects: Breakdown::new(),
             });
         }
 
         if let Some(rollup) = current_rollup.as_mut() {
             rollup.end = day.date;
             rollup.days += 1;
-            rollup.seconds += day.seconds;
+            rollup.raw_seconds += day.raw_seconds;
             let week = start_of_week(rollup.start, week_start).iso_week();
             rollup.label = format!(
                 "W{:02} {} ({} → {})",
@@ -147,14 +486,6 @@ fn build_weekly_rollups(daily: &[DailyTotal], week_start: WeekStart) -> Vec<Peri
     rollups
 }
 
-fn start_of_week(date: NaiveDate, week_start: WeekStart) -> NaiveDate {
-    let offset = match week_start {
-        WeekStart::Monday => date.weekday().num_days_from_monday() as i64,
-        WeekStart::Sunday => date.weekday().num_days_from_sunday() as i64,
-    };
-    date - Duration::days(offset)
-}
-
 fn build_monthly_rollups(daily: &[DailyTotal]) -> Vec<PeriodRollup> {
     let mut rollups = Vec::new();
     let mut current_key: Option<(i32, u32)> = None;
@@ -174,13 +505,15 @@ fn build_monthly_rollups(daily: &[DailyTotal]) -> Vec<PeriodRollup> {
                 end: day.date,
                 days: 0,
                 seconds: 0,
+                raw_seconds: 0,
+                projects: Breakdown::new(),
             });
         }
 
         if let Some(rollup) = current_rollup.as_mut() {
             rollup.end = day.date;
             rollup.days += 1;
-            rollup.seconds += day.seconds;
+            rollup.raw_seconds += day.raw_seconds;
             rollup.label = day.date.format("%b %Y").to_string();
         }
     }
@@ -192,32 +525,113 @@ fn build_monthly_rollups(daily: &[DailyTotal]) -> Vec<PeriodRollup> {
     rollups
 }
 
-fn build_yearly_rollups(daily: &[DailyTotal]) -> Vec<PeriodRollup> {
+fn build_quarterly_rollups(daily: &[DailyTotal], calendar: PeriodCalendar) -> Vec<PeriodRollup> {
+    let mut rollups = Vec::new();
+    let mut current_key: Option<(i32, u32)> = None;
+    let mut current_rollup: Option<PeriodRollup> = None;
+
+    for day in daily {
+        let key = calendar.fiscal_quarter(day.date);
+        if current_key.map(|value| value != key).unwrap_or(true) {
+            if let Some(rollup) = current_rollup.take() {
+                rollups.push(rollup);
+            }
+            let (year, quarter) = key;
+            current_key = Some(key);
+            current_rollup = Some(PeriodRollup {
+                label: format!("Q{quarter} {}", calendar.fiscal_year_label(year)),
+                start: day.date,
+                end: day.date,
+                days: 0,
+                seconds: 0,
+                raw_seconds: 0,
+                projects: Breakdown::new(),
+            });
+        }
+
+        if let Some(rollup) = current_rollup.as_mut() {
+            rollup.end = day.date;
+            rollup.days += 1;
+            rollup.raw_seconds += day.raw_seconds;
+        }
+    }
+
+    if let Some(rollup) = current_rollup {
+        rollups.push(rollup);
+    }
+
+    rollups
+}
+
+fn build_yearly_rollups(daily: &[DailyTotal], calendar: PeriodCalendar) -> Vec<PeriodRollup> {
     let mut rollups = Vec::new();
     let mut current_key: Option<i32> = None;
     let mut current_rollup: Option<PeriodRollup> = None;
 
     for day in daily {
-        let key = day.date.year();
+        let key = calendar.fiscal_year(day.date);
         if current_key.map(|value| value != key).unwrap_or(true) {
             if let Some(rollup) = current_rollup.take() {
                 rollups.push(rollup);
             }
             current_key = Some(key);
             current_rollup = Some(PeriodRollup {
-                label: key.to_string(),
+                label: calendar.fiscal_year_label(key),
                 start: day.date,
                 end: day.date,
                 days: 0,
                 seconds: 0,
+                raw_seconds: 0,
+                projects: Breakdown::new(),
             });
         }
 
         if let Some(rollup) = current_rollup.as_mut() {
             rollup.end = day.date;
             rollup.days += 1;
-            rollup.seconds += day.seconds;
-            rollup.label = day.date.year().to_string();
+            rollup.raw_seconds += day.raw_seconds;
+        }
+    }
+
+    if let Some(rollup) = current_rollup {
+        rollups.push(rollup);
+    }
+
+    rollups
+}
+
+fn build_cycle_periods(daily: &[DailyTotal], cycle: &BillingCycle) -> Vec<PeriodRollup> {
+    let mut rollups = Vec::new();
+    let mut current_key: Option<NaiveDate> = None;
+    let mut current_rollup: Option<PeriodRollup> = None;
+
+    for day in daily {
+        let key = cycle.cycle_start(day.date);
+        if current_key.map(|value| value != key).unwrap_or(true) {
+            if let Some(rollup) = current_rollup.take() {
+                rollups.push(rollup);
+            }
+            let label = format!(
+                "{} → {}",
+                key.format("%Y-%m-%d"),
+                cycle.cycle_end(key).format("%Y-%m-%d")
+            );
+            current_key = Some(key);
+            current_rollup = Some(PeriodRollup {
+                label,
+                start: day.date,
+                end: day.date,
+                days: 0,
+                seconds: 0,
+                raw_seconds: 0,
+                projects: Breakdown::new(),
+            });
+        }
+
+        if let Some(rollup) = current_rollup.as_mut() {
+            rollup.end = day.date;
+            rollup.days += 1;
+            rollup.raw_seconds += day.raw_seconds;
         }
     }
 
@@ -231,7 +645,9 @@ fn build_yearly_rollups(daily: &[DailyTotal]) -> Vec<PeriodRollup> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::rounding::{RoundingConfig, RoundingMode};
+    use crate::rounding::{RoundingConfig, RoundingMode, RoundingRules, RoundingScope};
+
+    const UTC: Timezone = Timezone::Named(chrono_tz::UTC);
 
     fn entry(start: &str, duration: i64) -> TimeEntry {
         TimeEntry {
@@ -244,6 +660,16 @@ mod tests {
         }
     }
 
+    fn span_entry(start: &str, stop: &str) -> TimeEntry {
+        let duration = (DateTime::parse_from_rfc3339(stop).unwrap()
+            - DateTime::parse_from_rfc3339(start).unwrap())
+        .num_seconds();
+        TimeEntry {
+            stop: Some(stop.to_string()),
+            ..entry(start, duration)
+        }
+    }
+
     #[test]
     fn build_rollups_includes_empty_days() {
         let entries = vec![
@@ -253,7 +679,15 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
         let end = NaiveDate::from_ymd_opt(2026, 2, 5).unwrap();
 
-        let rollups = build_rollups(&entries, start, end, None, WeekStart::Monday);
+        let rollups = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
 
         assert_eq!(rollups.daily.len(), 3);
         assert_eq!(rollups.daily[0].seconds, 3600);
@@ -266,6 +700,76 @@ mod tests {
         assert_eq!(rollups.yearly.len(), 1);
     }
 
+    #[test]
+    fn build_rollups_breaks_days_and_periods_down_by_project() {
+        let entries = vec![
+            TimeEntry {
+                project_id: Some(7),
+                ..entry("2026-02-03T10:00:00Z", 3600)
+            },
+            TimeEntry {
+                id: 2,
+                project_id: Some(8),
+                ..entry("2026-02-03T12:00:00Z", 1800)
+            },
+            TimeEntry {
+                id: 3,
+                project_id: Some(7),
+                ..entry("2026-02-04T10:00:00Z", 900)
+            },
+            TimeEntry {
+                id: 4,
+                ..entry("2026-02-04T12:00:00Z", 600)
+            },
+        ];
+        let start = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 2, 4).unwrap();
+
+        let rollups = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
+
+        assert_eq!(
+            rollups.daily[0].projects,
+            Breakdown::from([(Some(7), 3600), (Some(8), 1800)])
+        );
+        assert_eq!(
+            rollups.daily[1].projects,
+            Breakdown::from([(None, 600), (Some(7), 900)])
+        );
+        assert_eq!(
+            rollups.weekly[0].projects,
+            Breakdown::from([(None, 600), (Some(7), 4500), (Some(8), 1800)])
+        );
+    }
+
+    #[test]
+    fn client_breakdown_folds_projects_and_top_shares_keeps_the_largest() {
+        let projects = Breakdown::from([
+            (None, 600),
+            (Some(7), 4500),
+            (Some(8), 1800),
+            (Some(9), 300),
+        ]);
+        let project_clients = HashMap::from([(7, 70), (9, 70)]);
+
+        let clients = client_breakdown(&projects, &project_clients);
+        assert_eq!(clients, Breakdown::from([(None, 2400), (Some(70), 4800)]));
+
+        let (shares, other) = top_shares(&projects, 2);
+        assert_eq!(shares.len(), 2);
+        assert_eq!((shares[0].id, shares[0].seconds), (Some(7), 4500));
+        assert_eq!((shares[1].id, shares[1].seconds), (Some(8), 1800));
+        assert!((shares[0].percent - 62.5).abs() < 1e-9);
+        assert_eq!(other, 900);
+    }
+
     #[test]
     fn build_rollups_respects_rounding() {
         let entries = vec![
@@ -277,22 +781,101 @@ mod tests {
         let rounding = RoundingConfig {
             increment_minutes: 15,
             mode: RoundingMode::Closest,
+            ..RoundingConfig::default()
         };
 
-        let rollups = build_rollups(&entries, start, end, Some(&rounding), WeekStart::Monday);
+        let rollups = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::new(
+                Some(rounding),
+                RoundingScope::Line,
+                RoundingRules::default(),
+                &[],
+            ),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
 
         assert_eq!(rollups.daily.len(), 1);
         assert_eq!(rollups.daily[0].seconds, 30 * 60);
     }
 
+    #[test]
+    fn period_scope_rounds_the_period_total_once() {
+        let entries = vec![
+            entry("2026-02-03T10:00:00Z", 10 * 60),
+            entry("2026-02-04T10:00:00Z", 10 * 60),
+        ];
+        let start = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 2, 4).unwrap();
+        let rounding = |scope| {
+            RoundingPolicy::new(
+                Some(RoundingConfig {
+                    increment_minutes: 15,
+                    mode: RoundingMode::Up,
+                    ..RoundingConfig::default()
+                }),
+                scope,
+                RoundingRules::default(),
+                &[],
+            )
+        };
+
+        let by_day = build_rollups(
+            &entries,
+            start,
+            end,
+            &rounding(RoundingScope::Day),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
+        let by_period = build_rollups(
+            &entries,
+            start,
+            end,
+            &rounding(RoundingScope::Period),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
+
+        assert_eq!(by_day.weekly[0].seconds, 2 * 15 * 60);
+        assert_eq!(by_period.daily[0].seconds, 15 * 60);
+        assert_eq!(by_period.weekly[0].seconds, 30 * 60);
+        assert_eq!(by_period.weekly[0].raw_seconds, 20 * 60);
+    }
+
     #[test]
     fn weekly_rollups_respect_sunday_start() {
         let entries = vec![entry("2026-02-01T10:00:00Z", 3600)];
         let start = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2026, 2, 2).unwrap();
 
-        let monday = build_rollups(&entries, start, end, None, WeekStart::Monday);
-        let sunday = build_rollups(&entries, start, end, None, WeekStart::Sunday);
+        let monday = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
+        let sunday = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar {
+                week_start: WeekStart::Sunday,
+                ..PeriodCalendar::default()
+            },
+            UTC,
+            false,
+        );
 
         assert_eq!(monday.weekly.len(), 2);
         assert_eq!(sunday.weekly.len(), 1);
@@ -307,10 +890,276 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
         let end = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
 
-        let rollups = build_rollups(&entries, start, end, None, WeekStart::Monday);
+        let rollups = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
 
         assert_eq!(rollups.yearly.len(), 2);
         assert_eq!(rollups.yearly[0].label, "2025");
         assert_eq!(rollups.yearly[1].label, "2026");
     }
+
+    #[test]
+    fn entries_bucket_by_timezone_not_utc_date() {
+        let entries = vec![
+            entry("2026-02-03T23:30:00Z", 3600),
+            entry("2026-02-04T04:30:00Z", 1800),
+        ];
+        let start = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 2, 4).unwrap();
+
+        let brussels = Timezone::Named(chrono_tz::Europe::Brussels);
+        let new_york = Timezone::Named(chrono_tz::America::New_York);
+        let east = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            brussels,
+            false,
+        );
+        let west = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            new_york,
+            false,
+        );
+
+        assert_eq!(east.daily[0].seconds, 0);
+        assert_eq!(east.daily[1].seconds, 5400);
+        assert_eq!(west.daily[0].seconds, 5400);
+        assert_eq!(west.daily[1].seconds, 0);
+    }
+
+    #[test]
+    fn entries_around_dst_change_stay_on_their_day() {
+        // 2026-10-25 has 25 hours in Brussels: it runs from 22:00Z to 23:00Z the next day.
+        let entries = vec![
+            entry("2026-10-24T22:30:00Z", 600),
+            entry("2026-10-25T00:30:00Z", 1200),
+            entry("2026-10-25T01:30:00Z", 1800),
+            entry("2026-10-25T22:30:00Z", 2400),
+            entry("2026-10-25T23:30:00Z", 3000),
+        ];
+        let start = NaiveDate::from_ymd_opt(2026, 10, 24).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 10, 26).unwrap();
+        let brussels = Timezone::Named(chrono_tz::Europe::Brussels);
+
+        let rollups = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            brussels,
+            false,
+        );
+
+        assert_eq!(rollups.daily[0].seconds, 0);
+        assert_eq!(rollups.daily[1].seconds, 600 + 1200 + 1800 + 2400);
+        assert_eq!(rollups.daily[2].seconds, 3000);
+    }
+
+    #[test]
+    fn split_at_midnight_spreads_entry_over_days() {
+        let entries = vec![span_entry("2026-02-03T22:00:00Z", "2026-02-04T02:00:00Z")];
+        let start = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 2, 4).unwrap();
+
+        let whole = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            UTC,
+            false,
+        );
+        let split = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            PeriodCalendar::default(),
+            UTC,
+            true,
+        );
+
+        assert_eq!(whole.daily[0].seconds, 4 * 3600);
+        assert_eq!(whole.daily[1].seconds, 0);
+        assert_eq!(split.daily[0].seconds, 2 * 3600);
+        assert_eq!(split.daily[1].seconds, 2 * 3600);
+    }
+
+    #[test]
+    fn split_pieces_are_proportional_and_sum_to_rounded_duration() {
+        let entry = span_entry("2026-02-03T23:00:00Z", "2026-02-04T02:00:00Z");
+        let pieces = split_seconds_by_day(&entry, 4 * 3600, UTC, true);
+
+        let total: i64 = pieces.iter().map(|(_, seconds)| seconds).sum();
+        assert_eq!(total, 4 * 3600);
+        assert_eq!(pieces[0].1, 4 * 3600 / 3);
+        assert_eq!(pieces[1].0, NaiveDate::from_ymd_opt(2026, 2, 4).unwrap());
+    }
+
+    #[test]
+    fn split_follows_timezone_midnight() {
+        // 22:00–02:00 in Brussels is 21:00Z–01:00Z.
+        let entry = span_entry("2026-02-03T21:00:00Z", "2026-02-04T01:00:00Z");
+        let brussels = Timezone::Named(chrono_tz::Europe::Brussels);
+
+        let pieces = split_seconds_by_day(&entry, entry.duration, brussels, true);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].1, 2 * 3600);
+        assert_eq!(pieces[1].1, 2 * 3600);
+
+        let utc_pieces = split_seconds_by_day(&entry, entry.duration, UTC, true);
+        assert_eq!(utc_pieces[0].1, 3 * 3600);
+        assert_eq!(utc_pieces[1].1, 3600);
+    }
+
+    #[test]
+    fn day_window_clips_entry_to_window() {
+        let entry = span_entry("2026-02-03T22:00:00Z", "2026-02-05T02:00:00Z");
+        let window = DayWindow {
+            start: NaiveDate::from_ymd_opt(2026, 2, 4).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 2, 4).unwrap(),
+            timezone: UTC,
+        };
+        assert_eq!(window.clip_seconds(&entry, entry.duration), 24 * 3600);
+    }
+
+    #[test]
+    fn calendar_year_of_a_fiscal_year_follows_the_date_inside_it() {
+        let calendar = PeriodCalendar {
+            fiscal_year_start: 4,
+            ..PeriodCalendar::default()
+        };
+        let february = NaiveDate::from_ymd_opt(2027, 2, 10).unwrap();
+
+        assert_eq!(calendar.calendar_year_of(2026, february), 2027);
+        assert_eq!(calendar.calendar_year_of(2025, february), 2025);
+        assert_eq!(
+            PeriodCalendar::default().calendar_year_of(2027, february),
+            2027
+        );
+    }
+
+    #[test]
+    fn fiscal_year_start_shapes_quarters_and_years() {
+        let entries = vec![
+            entry("2026-03-31T10:00:00Z", 3600),
+            entry("2026-04-01T10:00:00Z", 1800),
+            entry("2026-07-01T10:00:00Z", 900),
+        ];
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 12, 31).unwrap();
+        let calendar = PeriodCalendar {
+            fiscal_year_start: 4,
+            ..PeriodCalendar::default()
+        };
+
+        let rollups = build_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            calendar,
+            UTC,
+            false,
+        );
+
+        let quarters: Vec<(&str, i64)> = rollups
+            .quarterly
+            .iter()
+            .map(|rollup| (rollup.label.as_str(), rollup.seconds))
+            .collect();
+        assert_eq!(
+            quarters,
+            vec![
+                ("Q4 FY2025/26", 3600),
+                ("Q1 FY2026/27", 1800),
+                ("Q2 FY2026/27", 900),
+                ("Q3 FY2026/27", 0),
+            ]
+        );
+        assert_eq!(rollups.yearly.len(), 2);
+        assert_eq!(
+            rollups.yearly[1].start,
+            NaiveDate::from_ymd_opt(2026, 4, 1).unwrap()
+        );
+        assert_eq!(
+            calendar.fiscal_year_bounds(2026).unwrap().1,
+            NaiveDate::from_ymd_opt(2027, 3, 31).unwrap()
+        );
+        assert_eq!(PeriodCalendar::default().fiscal_quarter(end), (2026, 4));
+    }
+
+    #[test]
+    fn month_cycles_run_from_start_day_to_the_day_before() {
+        let cycle: BillingCycle =
+            serde_json::from_str(r#"{"name":"Acme","kind":"months","start_day":26}"#).unwrap();
+        let date = |month, day| NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+
+        assert_eq!(cycle.cycle_start(date(3, 25)), date(2, 26));
+        assert_eq!(cycle.cycle_start(date(3, 26)), date(3, 26));
+        assert_eq!(cycle.cycle_end(date(2, 26)), date(3, 25));
+        assert_eq!(
+            cycle.cycle_start(NaiveDate::from_ymd_opt(2026, 1, 10).unwrap()),
+            NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()
+        );
+        assert!(
+            BillingCycle {
+                rule: CycleRule::Months { start_day: 31 },
+                ..cycle
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn week_cycles_repeat_around_the_anchor() {
+        let cycle = BillingCycle {
+            name: "Sprint".to_string(),
+            rule: CycleRule::Weeks {
+                weeks: 2,
+                anchor: NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(),
+            },
+        };
+        let entries = vec![
+            entry("2026-01-02T10:00:00Z", 3600),
+            entry("2026-01-05T10:00:00Z", 1800),
+            entry("2026-01-18T10:00:00Z", 1800),
+            entry("2026-01-19T10:00:00Z", 900),
+        ];
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+
+        let rollups = build_cycle_rollups(
+            &entries,
+            start,
+            end,
+            &RoundingPolicy::default(),
+            &cycle,
+            UTC,
+            false,
+        );
+
+        let seconds: Vec<i64> = rollups.iter().map(|rollup| rollup.seconds).collect();
+        assert_eq!(seconds, vec![3600, 3600, 900]);
+        assert_eq!(rollups[0].label, "2025-12-22 → 2026-01-04");
+        assert_eq!(rollups[0].start, start);
+        assert_eq!(rollups[1].days, 14);
+    }
 }
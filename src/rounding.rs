@@ -1,6 +1,10 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+use crate::models::{Project, TimeEntry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RoundingMode {
     Closest,
@@ -8,10 +12,29 @@ pub enum RoundingMode {
     Down,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RoundingConfig {
     pub increment_minutes: u32,
     pub mode: RoundingMode,
+    /// Durations shorter than this are dropped before rounding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discard_under_minutes: Option<u32>,
+    /// Smallest amount charged for any duration that is kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_minutes: Option<u32>,
+    /// Largest amount charged per rounding bucket (see `rounding_scope`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cap_minutes: Option<u32>,
+}
+
+impl RoundingMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Closest => "closest",
+            Self::Up => "up",
+            Self::Down => "down",
+        }
+    }
 }
 
 impl Default for RoundingConfig {
@@ -19,24 +42,242 @@ impl Default for RoundingConfig {
         Self {
             increment_minutes: 15,
             mode: RoundingMode::Closest,
+            discard_under_minutes: None,
+            minimum_minutes: None,
+            cap_minutes: None,
         }
     }
 }
 
-pub fn round_seconds(seconds: i64, cfg: &RoundingConfig) -> i64 {
-    if cfg.increment_minutes == 0 {
-        return seconds;
+/// What a rounding rule is applied to. Totals for a day, a dashboard range or
+/// a rollup period all sum the rounded buckets that fall inside them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingScope {
+    /// Each time entry on its own.
+    Entry,
+    /// Entries sharing project and description.
+    #[default]
+    Line,
+    /// Everything on one day that shares a rule.
+    Day,
+    /// Everything in the viewed range or period that shares a rule.
+    Period,
+}
+
+impl RoundingScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Entry => "entry",
+            Self::Line => "line",
+            Self::Day => "day",
+            Self::Period => "period",
+        }
     }
+}
 
-    let increment_seconds = i64::from(cfg.increment_minutes) * 60;
-    if increment_seconds <= 0 {
-        return seconds;
+/// Rounding overrides keyed by Toggl client or project id. A `null` rule
+/// leaves matching work unrounded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundingRules {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub clients: HashMap<u64, Option<RoundingConfig>>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub projects: HashMap<u64, Option<RoundingConfig>>,
+}
+
+impl RoundingRules {
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty() && self.projects.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingSource {
+    Global,
+    Client,
+    Project,
+}
+
+/// The rounding rule in effect for one entry and where it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedRounding {
+    pub config: Option<RoundingConfig>,
+    pub source: RoundingSource,
+}
+
+impl ResolvedRounding {
+    pub fn apply(&self, seconds: i64) -> i64 {
+        self.config
+            .map(|cfg| round_seconds(seconds, &cfg))
+            .unwrap_or(seconds)
+    }
+
+    pub fn label(&self) -> String {
+        let rule = match self.config {
+            Some(cfg) => {
+                let mut rule = format!(
+                    "{:.2}h {}",
+                    cfg.increment_minutes as f64 / 60.0,
+                    cfg.mode.label()
+                );
+                if let Some(minutes) = cfg.discard_under_minutes {
+                    rule.push_str(&format!(", drop <{minutes}m"));
+                }
+                if let Some(minutes) = cfg.minimum_minutes {
+                    rule.push_str(&format!(", min {minutes}m"));
+                }
+                if let Some(minutes) = cfg.cap_minutes {
+                    rule.push_str(&format!(", max {minutes}m"));
+                }
+                rule
+            }
+            None => "unrounded".to_string(),
+        };
+        match self.source {
+            RoundingSource::Global => rule,
+            RoundingSource::Client => format!("{rule} (client rule)"),
+            RoundingSource::Project => format!("{rule} (project rule)"),
+        }
     }
+}
 
+/// Global rounding plus overrides, resolved per entry: a project rule wins
+/// over its client's rule, which wins over the global setting.
+#[derive(Debug, Clone, Default)]
+pub struct RoundingPolicy {
+    global: Option<RoundingConfig>,
+    scope: RoundingScope,
+    rules: RoundingRules,
+    project_clients: HashMap<u64, u64>,
+}
+
+impl RoundingPolicy {
+    pub fn new(
+        global: Option<RoundingConfig>,
+        scope: RoundingScope,
+        rules: RoundingRules,
+        projects: &[Project],
+    ) -> Self {
+        let project_clients = projects
+            .iter()
+            .filter_map(|project| project.client_id.map(|client_id| (project.id, client_id)))
+            .collect();
+        Self {
+            global,
+            scope,
+            rules,
+            project_clients,
+        }
+    }
+
+    pub fn scope(&self) -> RoundingScope {
+        self.scope
+    }
+
+    /// Sums `items`, rounding each scope bucket with the rule resolved for it.
+    pub fn rounded_seconds<'a>(&self, items: impl IntoIterator<Item = &'a RoundingItem>) -> i64 {
+        let mut buckets: HashMap<RoundingBucket<'a>, (ResolvedRounding, i64)> = HashMap::new();
+        for item in items {
+            let rule = self.resolve(item.project_id);
+            let key = match self.scope {
+                RoundingScope::Entry => RoundingBucket::Entry(item.entry_id),
+                RoundingScope::Line => RoundingBucket::Line(item.project_id, &item.description),
+                RoundingScope::Day => RoundingBucket::Day(item.date, rule.config),
+                RoundingScope::Period => RoundingBucket::Period(rule.config),
+            };
+            buckets.entry(key).or_insert((rule, 0)).1 += item.seconds;
+        }
+        buckets
+            .into_values()
+            .map(|(rule, seconds)| rule.apply(seconds))
+            .sum()
+    }
+
+    pub fn resolve(&self, project_id: Option<u64>) -> ResolvedRounding {
+        if let Some(config) = project_id.and_then(|id| self.rules.projects.get(&id)) {
+            return ResolvedRounding {
+                config: *config,
+                source: RoundingSource::Project,
+            };
+        }
+        let client_id = project_id.and_then(|id| self.project_clients.get(&id));
+        if let Some(config) = client_id.and_then(|id| self.rules.clients.get(id)) {
+            return ResolvedRounding {
+                config: *config,
+                source: RoundingSource::Client,
+            };
+        }
+        ResolvedRounding {
+            config: self.global,
+            source: RoundingSource::Global,
+        }
+    }
+}
+
+/// An entry's share of one calendar day, as fed to [`RoundingPolicy`].
+#[derive(Debug, Clone)]
+pub struct RoundingItem {
+    pub date: NaiveDate,
+    pub entry_id: u64,
+    pub project_id: Option<u64>,
+    pub description: String,
+    pub seconds: i64,
+}
+
+impl RoundingItem {
+    pub fn new(entry: &TimeEntry, date: NaiveDate, seconds: i64) -> Self {
+        Self {
+            date,
+            entry_id: entry.id,
+            project_id: entry.project_id,
+            description: entry
+                .description
+                .clone()
+                .unwrap_or_else(|| "No description".to_string()),
+            seconds,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RoundingBucket<'a> {
+    Entry(u64),
+    Line(Option<u64>, &'a str),
+    Day(NaiveDate, Option<RoundingConfig>),
+    Period(Option<RoundingConfig>),
+}
+
+pub fn round_seconds(seconds: i64, cfg: &RoundingConfig) -> i64 {
     let sign = if seconds < 0 { -1 } else { 1 };
     let abs_seconds = seconds.abs();
 
-    let rounded = match cfg.mode {
+    if let Some(minutes) = cfg.discard_under_minutes
+        && abs_seconds < i64::from(minutes) * 60
+    {
+        return 0;
+    }
+
+    let mut rounded = round_to_increment(abs_seconds, cfg);
+    if let Some(minutes) = cfg.minimum_minutes
+        && abs_seconds > 0
+    {
+        rounded = rounded.max(i64::from(minutes) * 60);
+    }
+    if let Some(minutes) = cfg.cap_minutes {
+        rounded = rounded.min(i64::from(minutes) * 60);
+    }
+
+    rounded.saturating_mul(sign)
+}
+
+fn round_to_increment(abs_seconds: i64, cfg: &RoundingConfig) -> i64 {
+    let increment_seconds = i64::from(cfg.increment_minutes) * 60;
+    if increment_seconds <= 0 {
+        return abs_seconds;
+    }
+
+    match cfg.mode {
         RoundingMode::Down => (abs_seconds / increment_seconds) * increment_seconds,
         RoundingMode::Up => {
             if abs_seconds % increment_seconds == 0 {
@@ -60,9 +301,7 @@ pub fn round_seconds(seconds: i64, cfg: &RoundingConfig) -> i64 {
                 lower
             }
         }
-    };
-
-    rounded.saturating_mul(sign)
+    }
 }
 
 #[cfg(test)]
@@ -74,6 +313,7 @@ mod tests {
         let cfg = RoundingConfig {
             increment_minutes: 0,
             mode: RoundingMode::Closest,
+            ..RoundingConfig::default()
         };
         assert_eq!(round_seconds(123, &cfg), 123);
         assert_eq!(round_seconds(-123, &cfg), -123);
@@ -84,6 +324,7 @@ mod tests {
         let cfg = RoundingConfig {
             increment_minutes: 15,
             mode: RoundingMode::Up,
+            ..RoundingConfig::default()
         };
         assert_eq!(round_seconds(1, &cfg), 900);
         assert_eq!(round_seconds(900, &cfg), 900);
@@ -95,6 +336,7 @@ mod tests {
         let cfg = RoundingConfig {
             increment_minutes: 15,
             mode: RoundingMode::Down,
+            ..RoundingConfig::default()
         };
         assert_eq!(round_seconds(899, &cfg), 0);
         assert_eq!(round_seconds(900, &cfg), 900);
@@ -106,6 +348,7 @@ mod tests {
         let cfg = RoundingConfig {
             increment_minutes: 15,
             mode: RoundingMode::Closest,
+            ..RoundingConfig::default()
         };
         assert_eq!(round_seconds(449, &cfg), 0);
         assert_eq!(round_seconds(450, &cfg), 900);
@@ -119,8 +362,166 @@ mod tests {
         let cfg = RoundingConfig {
             increment_minutes: 15,
             mode: RoundingMode::Closest,
+            ..RoundingConfig::default()
         };
         assert_eq!(round_seconds(-450, &cfg), -900);
         assert_eq!(round_seconds(-449, &cfg), 0);
     }
+
+    #[test]
+    fn round_seconds_discards_short_durations() {
+        let cfg = RoundingConfig {
+            increment_minutes: 15,
+            mode: RoundingMode::Up,
+            discard_under_minutes: Some(2),
+            ..RoundingConfig::default()
+        };
+        assert_eq!(round_seconds(119, &cfg), 0);
+        assert_eq!(round_seconds(120, &cfg), 900);
+        assert_eq!(round_seconds(-119, &cfg), 0);
+    }
+
+    #[test]
+    fn round_seconds_applies_minimum_charge() {
+        let cfg = RoundingConfig {
+            increment_minutes: 6,
+            mode: RoundingMode::Down,
+            minimum_minutes: Some(15),
+            ..RoundingConfig::default()
+        };
+        assert_eq!(round_seconds(0, &cfg), 0);
+        assert_eq!(round_seconds(60, &cfg), 900);
+        assert_eq!(round_seconds(1000, &cfg), 900);
+        assert_eq!(round_seconds(1200, &cfg), 1080);
+    }
+
+    #[test]
+    fn round_seconds_caps_each_line() {
+        let cfg = RoundingConfig {
+            increment_minutes: 15,
+            mode: RoundingMode::Up,
+            cap_minutes: Some(240),
+            ..RoundingConfig::default()
+        };
+        assert_eq!(round_seconds(3 * 3600 + 1, &cfg), 3 * 3600 + 900);
+        assert_eq!(round_seconds(5 * 3600, &cfg), 4 * 3600);
+    }
+
+    #[test]
+    fn round_seconds_discard_wins_over_minimum() {
+        let cfg = RoundingConfig {
+            increment_minutes: 0,
+            mode: RoundingMode::Closest,
+            discard_under_minutes: Some(2),
+            minimum_minutes: Some(15),
+            cap_minutes: None,
+        };
+        assert_eq!(round_seconds(90, &cfg), 0);
+        assert_eq!(round_seconds(150, &cfg), 900);
+        assert_eq!(round_seconds(1234, &cfg), 1234);
+    }
+
+    fn project(id: u64, client_id: Option<u64>) -> Project {
+        Project {
+            id,
+            name: format!("Project {id}"),
+            client_id,
+            client_name: None,
+        }
+    }
+
+    #[test]
+    fn rounding_policy_prefers_project_then_client_then_global() {
+        let global = RoundingConfig::default();
+        let six_up = RoundingConfig {
+            increment_minutes: 6,
+            mode: RoundingMode::Up,
+            ..RoundingConfig::default()
+        };
+        let mut rules = RoundingRules::default();
+        rules.clients.insert(10, Some(six_up));
+        rules.projects.insert(2, None);
+        let projects = vec![project(1, Some(10)), project(2, Some(10)), project(3, None)];
+        let policy = RoundingPolicy::new(Some(global), RoundingScope::Line, rules, &projects);
+
+        let client = policy.resolve(Some(1));
+        assert_eq!(client.source, RoundingSource::Client);
+        assert_eq!(client.apply(61), 360);
+
+        let unrounded = policy.resolve(Some(2));
+        assert_eq!(unrounded.source, RoundingSource::Project);
+        assert_eq!(unrounded.apply(61), 61);
+
+        assert_eq!(policy.resolve(Some(3)).source, RoundingSource::Global);
+        assert_eq!(policy.resolve(None).apply(61), 0);
+    }
+
+    #[test]
+    fn rounding_rules_round_trip_through_json() {
+        let mut rules = RoundingRules::default();
+        rules.projects.insert(42, None);
+        rules.clients.insert(7, Some(RoundingConfig::default()));
+        let json = serde_json::to_string(&rules).unwrap();
+        let parsed: RoundingRules = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, rules);
+    }
+
+    fn item(entry_id: u64, day: u32, description: &str, seconds: i64) -> RoundingItem {
+        RoundingItem {
+            date: NaiveDate::from_ymd_opt(2026, 2, day).unwrap(),
+            entry_id,
+            project_id: Some(1),
+            description: description.to_string(),
+            seconds,
+        }
+    }
+
+    #[test]
+    fn rounding_scope_decides_what_is_rounded_together() {
+        // Three 10-minute entries: two share a line on day 3, one on day 4.
+        let items = vec![
+            item(1, 3, "Ticket", 600),
+            item(2, 3, "Ticket", 600),
+            item(3, 4, "Ticket", 600),
+        ];
+        let rounded = |scope| {
+            RoundingPolicy::new(
+                Some(RoundingConfig {
+                    increment_minutes: 15,
+                    mode: RoundingMode::Up,
+                    ..RoundingConfig::default()
+                }),
+                scope,
+                RoundingRules::default(),
+                &[],
+            )
+            .rounded_seconds(&items)
+        };
+
+        assert_eq!(rounded(RoundingScope::Entry), 3 * 900);
+        assert_eq!(rounded(RoundingScope::Line), 1800);
+        assert_eq!(rounded(RoundingScope::Day), 1800 + 900);
+        assert_eq!(rounded(RoundingScope::Period), 1800);
+    }
+
+    #[test]
+    fn day_and_period_scopes_round_each_rule_separately() {
+        let mut rules = RoundingRules::default();
+        rules.projects.insert(2, None);
+        let policy = RoundingPolicy::new(
+            Some(RoundingConfig {
+                increment_minutes: 15,
+                mode: RoundingMode::Up,
+                ..RoundingConfig::default()
+            }),
+            RoundingScope::Period,
+            rules,
+            &[],
+        );
+        let mut unrounded = item(2, 3, "Internal", 600);
+        unrounded.project_id = Some(2);
+        let items = vec![item(1, 3, "Ticket", 600), unrounded];
+
+        assert_eq!(policy.rounded_seconds(&items), 900 + 600);
+    }
 }
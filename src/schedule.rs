@@ -0,0 +1,295 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Target hours for each day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct WeekdayHours {
+    #[serde(default)]
+    pub mon: f64,
+    #[serde(default)]
+    pub tue: f64,
+    #[serde(default)]
+    pub wed: f64,
+    #[serde(default)]
+    pub thu: f64,
+    #[serde(default)]
+    pub fri: f64,
+    #[serde(default)]
+    pub sat: f64,
+    #[serde(default)]
+    pub sun: f64,
+}
+
+impl WeekdayHours {
+    pub fn for_weekday(&self, weekday: Weekday) -> f64 {
+        match weekday {
+            Weekday::Mon => self.mon,
+            Weekday::Tue => self.tue,
+            Weekday::Wed => self.wed,
+            Weekday::Thu => self.thu,
+            Weekday::Fri => self.fri,
+            Weekday::Sat => self.sat,
+            Weekday::Sun => self.sun,
+        }
+    }
+
+    pub fn weekly_total(&self) -> f64 {
+        self.mon + self.tue + self.wed + self.thu + self.fri + self.sat + self.sun
+    }
+}
+
+/// A weekly schedule that applies from `from` until the next change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleChange {
+    pub from: NaiveDate,
+    pub hours: WeekdayHours,
+}
+
+/// Daily target hours over time. Days before the first schedule change use
+/// the flat `target_hours` on weekdays, and on weekends too when
+/// `include_weekends` is set.
+#[derive(Debug, Clone, Default)]
+pub struct WorkSchedule {
+    pub target_hours: f64,
+    pub include_weekends: bool,
+    changes: Vec<ScheduleChange>,
+}
+
+impl WorkSchedule {
+    pub fn new(
+        target_hours: f64,
+        include_weekends: bool,
+        mut changes: Vec<ScheduleChange>,
+    ) -> Self {
+        changes.sort_by_key(|change| change.from);
+        Self {
+            target_hours,
+            include_weekends,
+            changes,
+        }
+    }
+
+    pub fn hours_for(&self, day: NaiveDate) -> f64 {
+        match self.change_for(day) {
+            Some(change) => change.hours.for_weekday(day.weekday()),
+            None if self.include_weekends || day.weekday().number_from_monday() <= 5 => {
+                self.target_hours
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Describes the schedule change in effect on `day`, e.g.
+    /// "36.00h/week since 2026-09-01".
+    pub fn change_label(&self, day: NaiveDate) -> Option<String> {
+        self.change_for(day).map(|change| {
+            format!(
+                "{:.2}h/week since {}",
+                change.hours.weekly_total(),
+                change.from.format("%Y-%m-%d")
+            )
+        })
+    }
+
+    pub fn hours_between(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        start
+            .iter_days()
+            .take_while(|day| *day <= end)
+            .map(|day| self.hours_for(day))
+            .sum()
+    }
+
+    fn change_for(&self, day: NaiveDate) -> Option<&ScheduleChange> {
+        self.changes.iter().rev().find(|change| change.from <= day)
+    }
+}
+
+/// Part of a day covered by a vacation or sick mark. Marked days without
+/// an amount are absent for the whole day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AbsenceAmount {
+    Fraction(f64),
+    Hours(f64),
+}
+
+impl AbsenceAmount {
+    /// Parses "0.5", "50%" or "2h". Returns `None` for a whole day.
+    pub fn parse(value: &str) -> Result<Option<Self>, String> {
+        let value = value.trim().to_lowercase();
+        let invalid = || "Use a fraction (0.5), a percentage (50%) or hours (2h).".to_string();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        let amount = if let Some(hours) = value.strip_suffix('h') {
+            let hours: f64 = hours.trim().parse().map_err(|_| invalid())?;
+            if !(hours > 0.0 && hours <= 24.0) {
+                return Err("Hours must be between 0 and 24.".to_string());
+            }
+            Self::Hours(hours)
+        } else {
+            let (number, scale) = match value.strip_suffix('%') {
+                Some(percent) => (percent.trim(), 100.0),
+                None => (value.as_str(), 1.0),
+            };
+            let fraction = number.parse::<f64>().map_err(|_| invalid())? / scale;
+            if !(fraction > 0.0 && fraction <= 1.0) {
+                return Err(invalid());
+            }
+            if fraction == 1.0 {
+                return Ok(None);
+            }
+            Self::Fraction(fraction)
+        };
+        Ok(Some(amount))
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Self::Fraction(fraction) => format!("{:.0}%", fraction * 100.0),
+            Self::Hours(hours) => format!("{hours:.2}h"),
+        }
+    }
+
+    /// Target for the day: the scheduled hours not covered by the absence,
+    /// plus the covered share of the full-day absence target. Explicit hours
+    /// count as-is, up to the full-day target.
+    pub fn target_hours(self, scheduled_hours: f64, full_day_target_hours: f64) -> f64 {
+        match self {
+            Self::Fraction(fraction) => {
+                scheduled_hours * (1.0 - fraction) + full_day_target_hours * fraction
+            }
+            Self::Hours(hours) => {
+                (scheduled_hours - hours).max(0.0) + hours.min(full_day_target_hours)
+            }
+        }
+    }
+
+    /// Share of a day with `scheduled_hours` that the absence covers.
+    pub fn fraction_of(self, scheduled_hours: f64) -> f64 {
+        match self {
+            Self::Fraction(fraction) => fraction,
+            Self::Hours(_) if scheduled_hours <= 0.0 => 0.0,
+            Self::Hours(hours) => (hours / scheduled_hours).min(1.0),
+        }
+    }
+
+    pub fn credit_hours(self, full_day_credit_hours: f64) -> f64 {
+        match self {
+            Self::Fraction(fraction) => full_day_credit_hours * fraction,
+            Self::Hours(hours) => hours.min(full_day_credit_hours),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn part_time() -> WeekdayHours {
+        WeekdayHours {
+            mon: 8.0,
+            tue: 8.0,
+            wed: 8.0,
+            thu: 8.0,
+            fri: 4.0,
+            ..WeekdayHours::default()
+        }
+    }
+
+    #[test]
+    fn flat_target_applies_without_schedule_changes() {
+        let schedule = WorkSchedule::new(8.0, false, Vec::new());
+        assert_eq!(schedule.hours_for(date(2026, 9, 4)), 8.0);
+        assert_eq!(schedule.hours_for(date(2026, 9, 5)), 0.0);
+
+        let with_weekends = WorkSchedule::new(8.0, true, Vec::new());
+        assert_eq!(with_weekends.hours_for(date(2026, 9, 5)), 8.0);
+    }
+
+    #[test]
+    fn schedule_changes_take_effect_from_their_date() {
+        let schedule = WorkSchedule::new(
+            8.0,
+            false,
+            vec![
+                ScheduleChange {
+                    from: date(2026, 12, 1),
+                    hours: WeekdayHours {
+                        mon: 8.0,
+                        ..WeekdayHours::default()
+                    },
+                },
+                ScheduleChange {
+                    from: date(2026, 9, 1),
+                    hours: part_time(),
+                },
+            ],
+        );
+
+        // Friday before and after the change, then a Friday under the last one.
+        assert_eq!(schedule.hours_for(date(2026, 8, 28)), 8.0);
+        assert_eq!(schedule.hours_for(date(2026, 9, 4)), 4.0);
+        assert_eq!(schedule.hours_for(date(2026, 12, 4)), 0.0);
+        assert_eq!(schedule.change_label(date(2026, 8, 28)), None);
+        assert_eq!(
+            schedule.change_label(date(2026, 9, 4)).as_deref(),
+            Some("36.00h/week since 2026-09-01")
+        );
+        // Mon 2026-08-31 at 8h, then Tue–Fri under the part-time schedule.
+        assert_eq!(
+            schedule.hours_between(date(2026, 8, 31), date(2026, 9, 6)),
+            8.0 + 28.0
+        );
+    }
+
+    #[test]
+    fn absence_amount_parses_fractions_percentages_and_hours() {
+        assert_eq!(
+            AbsenceAmount::parse("0.5"),
+            Ok(Some(AbsenceAmount::Fraction(0.5)))
+        );
+        assert_eq!(
+            AbsenceAmount::parse(" 25% "),
+            Ok(Some(AbsenceAmount::Fraction(0.25)))
+        );
+        assert_eq!(
+            AbsenceAmount::parse("2H"),
+            Ok(Some(AbsenceAmount::Hours(2.0)))
+        );
+        assert_eq!(AbsenceAmount::parse(""), Ok(None));
+        assert_eq!(AbsenceAmount::parse("100%"), Ok(None));
+        assert!(AbsenceAmount::parse("2").is_err());
+        assert!(AbsenceAmount::parse("0h").is_err());
+    }
+
+    #[test]
+    fn partial_absence_blends_schedule_and_full_day_hours() {
+        let half = AbsenceAmount::Fraction(0.5);
+        assert_eq!(half.target_hours(8.0, 8.0), 8.0);
+        assert_eq!(half.target_hours(8.0, 0.0), 4.0);
+        assert_eq!(half.credit_hours(7.6), 3.8);
+
+        let doctor = AbsenceAmount::Hours(2.0);
+        assert_eq!(doctor.target_hours(8.0, 8.0), 8.0);
+        assert_eq!(doctor.target_hours(8.0, 0.0), 6.0);
+        assert_eq!(doctor.credit_hours(8.0), 2.0);
+        assert_eq!(AbsenceAmount::Hours(10.0).credit_hours(8.0), 8.0);
+        assert_eq!(doctor.fraction_of(8.0), 0.25);
+        assert_eq!(doctor.fraction_of(0.0), 0.0);
+    }
+
+    #[test]
+    fn schedule_change_parses_with_missing_days_as_zero() {
+        let change: ScheduleChange = serde_json::from_str(
+            r#"{"from":"2026-09-01","hours":{"mon":8,"tue":8,"wed":8,"thu":8,"fri":4}}"#,
+        )
+        .unwrap();
+        assert_eq!(change.hours, part_time());
+        assert_eq!(change.hours.weekly_total(), 36.0);
+    }
+}
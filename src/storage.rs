@@ -1,16 +1,24 @@
 use chrono::{Local, NaiveDate};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
-use crate::models::{Client as TogglClientModel, Project, TimeEntry, Workspace};
-use crate::rollups::WeekStart;
-use crate::rounding::RoundingConfig;
+use crate::absences::{AbsenceType, SICK, VACATION, validate_absence_type};
+use crate::allowance::VacationAllowance;
+use crate::dates::WeekStart;
+use crate::gaps::GapThresholds;
+use crate::holidays::{Holiday, HolidayCalendar};
+use crate::models::{Client as TogglClientModel, Me, Project, TimeEntry, Workspace};
+use crate::rollups::BillingCycle;
+use crate::rounding::{RoundingConfig, RoundingRules, RoundingScope};
+use crate::schedule::{AbsenceAmount, ScheduleChange};
 use crate::theme::{
     CustomTheme, ThemePalette, ThemePreference, ThemeSelection, find_custom_theme,
     sorted_custom_themes, validate_theme_name,
@@ -29,6 +37,8 @@ pub struct CacheFile {
     #[serde(default)]
     pub workspaces: Option<CachedData<Vec<Workspace>>>,
     #[serde(default)]
+    pub me: Option<CachedData<Me>>,
+    #[serde(default)]
     pub projects: HashMap<u64, CachedData<Vec<Project>>>,
     #[serde(default)]
     pub clients: HashMap<u64, CachedData<Vec<TogglClientModel>>>,
@@ -115,10 +125,41 @@ struct Config {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     custom_themes: Vec<CustomTheme>,
     target_hours: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    work_schedule: Vec<ScheduleChange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    split_entries_at_midnight: Option<bool>,
+    // Longer entries are flagged as likely forgotten timers; 0 disables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_entry_hours: Option<f64>,
+    // Reopen the last dashboard range instead of today.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    restore_date_range: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ui_state: Option<UiState>,
+    // Overrides the timezone from the Toggl profile when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timezone: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     rounding: Option<RoundingConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    rounding_scope: Option<RoundingScope>,
+    #[serde(default, skip_serializing_if = "RoundingRules::is_empty")]
+    rounding_rules: RoundingRules,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     rollup_preferences: Option<RollupPreferences>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time_bank: Option<TimeBank>,
+    // Hand-edited; parsed entry by entry in `read_billing_cycles`.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    billing_cycles: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    gap_report: Option<GapThresholds>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    notifications_enabled: Option<bool>,
+    // "HH:MM" local time for the end-of-day summary notification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_of_day_summary: Option<String>,
     // Backward-compatible legacy field; merged into vacation_days on read.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     non_working_days: Vec<String>,
@@ -126,27 +167,50 @@ struct Config {
     vacation_days: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     sick_days: Vec<String>,
-    // Legacy field; used as fallback for both target and credit hours.
+    // Days marked with a user-defined absence type, by type key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    absence_days: BTreeMap<NaiveDate, String>,
+    // Every absence type, vacation and sick included; parsed entry by entry
+    // in `read_absence_types`.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    absence_types: Value,
+    // Partial-day amounts for absence days.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    partial_days: BTreeMap<NaiveDate, AbsenceAmount>,
+    // Hand-edited; parsed on its own in `read_vacation_allowance`.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    vacation_allowance: Value,
+    // Legacy vacation and sick fields, migrated into `absence_types` on read
+    // and cleared on the next absence type write.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     vacation_day_hours: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     vacation_day_target_hours: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     vacation_day_credit_hours: Option<f64>,
-    // Legacy field; used as fallback for both target and credit hours.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     sick_day_hours: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     sick_day_target_hours: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     sick_day_credit_hours: Option<f64>,
-    // Legacy field; used as fallback for both specific toggles.
+    // Older still; fallback for both specific toggles.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     credit_special_days_as_worked: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     credit_vacation_days_as_worked: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     credit_sick_days_as_worked: Option<bool>,
+    // ISO country code of the bundled holiday rules to apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    holiday_country: Option<String>,
+    // Holidays imported from an .ics file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    holidays: Vec<Holiday>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    holiday_target_hours: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    holiday_credit_hours: Option<f64>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -155,6 +219,12 @@ pub struct RollupPreferences {
     pub include_weekends: bool,
     #[serde(default)]
     pub week_start: WeekStart,
+    /// Month (1-12) the fiscal year starts in.
+    #[serde(default = "default_fiscal_year_start")]
+    pub fiscal_year_start: u32,
+    /// Whether `z` in rollups also saves `include_weekends`.
+    #[serde(default)]
+    pub remember_weekend_toggle: bool,
 }
 
 impl Default for RollupPreferences {
@@ -162,10 +232,32 @@ impl Default for RollupPreferences {
         Self {
             include_weekends: default_rollup_include_weekends(),
             week_start: WeekStart::Monday,
+            fiscal_year_start: default_fiscal_year_start(),
+            remember_weekend_toggle: false,
         }
     }
 }
 
+/// Where the last session left off. Saved on exit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UiState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollup_view: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollup_year: Option<i32>,
+    /// Only kept while `restore_date_range` is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_range: Option<(NaiveDate, NaiveDate)>,
+}
+
+/// Running overtime balance that starts from `opening_hours` on `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeBank {
+    pub start: NaiveDate,
+    #[serde(default)]
+    pub opening_hours: f64,
+}
+
 fn config_path() -> Option<PathBuf> {
     let mut path = dirs::home_dir()?;
     path.push(".toggl2tsc.json");
@@ -184,7 +276,7 @@ pub fn read_theme_settings() -> ThemeSettings {
 pub fn write_theme_selection(
     selection: &ThemeSelection,
 ) -> Result<ThemeSettings, ThemeConfigError> {
-    let mut config = read_config().unwrap_or_default();
+    let mut config = read_config_for_update()?;
     let custom_themes = normalize_custom_themes(&config.custom_themes);
     match selection {
         ThemeSelection::Builtin { theme } => {
@@ -205,7 +297,7 @@ pub fn write_theme_selection(
 }
 
 pub fn save_custom_theme(draft: ThemeDraft) -> Result<ThemeSettings, ThemeConfigError> {
-    let mut config = read_config().unwrap_or_default();
+    let mut config = read_config_for_update()?;
     let mut custom_themes = normalize_custom_themes(&config.custom_themes);
     let normalized_name = validate_theme_name(&draft.name).map_err(ThemeConfigError::Validation)?;
     let normalized_palette = draft
@@ -255,7 +347,7 @@ pub fn save_custom_theme(draft: ThemeDraft) -> Result<ThemeSettings, ThemeConfig
 }
 
 pub fn delete_custom_theme(id: &str) -> Result<ThemeSettings, ThemeConfigError> {
-    let mut config = read_config().unwrap_or_default();
+    let mut config = read_config_for_update()?;
     let mut custom_themes = normalize_custom_themes(&config.custom_themes);
     let previous_len = custom_themes.len();
     custom_themes.retain(|theme| theme.id != id);
@@ -368,37 +460,159 @@ pub fn read_target_hours() -> Option<f64> {
 }
 
 pub fn write_target_hours(value: f64) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
+    let mut config = read_config_for_update()?;
     config.target_hours = Some(value);
     write_config(&config)
 }
 
+pub fn read_work_schedule() -> Vec<ScheduleChange> {
+    read_config()
+        .map(|config| config.work_schedule)
+        .unwrap_or_default()
+}
+
+pub fn read_timezone_override() -> Option<String> {
+    read_config().and_then(|config| config.timezone)
+}
+
+pub fn write_timezone_override(value: Option<String>) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.timezone = value;
+    write_config(&config)
+}
+
 pub fn read_rounding() -> Option<RoundingConfig> {
     read_config().and_then(|config| config.rounding)
 }
 
 pub fn write_rounding(value: Option<RoundingConfig>) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
+    let mut config = read_config_for_update()?;
     config.rounding = value;
     write_config(&config)
 }
 
-pub fn read_rollup_preferences() -> RollupPreferences {
+pub fn read_rounding_scope() -> RoundingScope {
     read_config()
+        .and_then(|config| config.rounding_scope)
+        .unwrap_or_default()
+}
+
+pub fn write_rounding_scope(value: RoundingScope) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.rounding_scope = Some(value);
+    write_config(&config)
+}
+
+pub fn read_rounding_rules() -> RoundingRules {
+    read_config()
+        .map(|config| config.rounding_rules)
+        .unwrap_or_default()
+}
+
+pub fn read_rollup_preferences() -> RollupPreferences {
+    let mut preferences = read_config()
         .and_then(|config| config.rollup_preferences)
+        .unwrap_or_default();
+    if !(1..=12).contains(&preferences.fiscal_year_start) {
+        preferences.fiscal_year_start = default_fiscal_year_start();
+    }
+    preferences
+}
+
+pub fn read_gap_thresholds() -> GapThresholds {
+    read_config()
+        .and_then(|config| config.gap_report)
         .unwrap_or_default()
 }
 
+pub fn write_gap_thresholds(value: GapThresholds) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.gap_report = Some(value);
+    write_config(&config)
+}
+
+pub fn read_time_bank() -> Option<TimeBank> {
+    read_config().and_then(|config| config.time_bank)
+}
+
+pub fn write_time_bank(value: Option<TimeBank>) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.time_bank = value;
+    write_config(&config)
+}
+
+/// Valid billing cycles from the config file, plus one error per invalid
+/// cycle that was left out.
+pub fn read_billing_cycles() -> (Vec<BillingCycle>, Vec<String>) {
+    let config = read_config().unwrap_or_default();
+    let (cycles, mut errors) =
+        parse_lenient_list::<BillingCycle>(&config.billing_cycles, "billing_cycles");
+    let cycles = cycles
+        .into_iter()
+        .filter(|cycle| match cycle.validate() {
+            Ok(()) => true,
+            Err(err) => {
+                errors.push(err);
+                false
+            }
+        })
+        .collect();
+    (cycles, errors)
+}
+
+pub fn read_notifications_enabled() -> bool {
+    read_config()
+        .and_then(|config| config.notifications_enabled)
+        .unwrap_or(false)
+}
+
+pub fn write_notifications_enabled(value: bool) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.notifications_enabled = Some(value);
+    write_config(&config)
+}
+
+pub fn read_end_of_day_summary() -> Option<String> {
+    read_config().and_then(|config| config.end_of_day_summary)
+}
+
+pub fn write_end_of_day_summary(value: Option<String>) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.end_of_day_summary = value;
+    write_config(&config)
+}
+
 pub fn write_rollup_preferences(value: RollupPreferences) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
+    let mut config = read_config_for_update()?;
     config.rollup_preferences = Some(value);
     write_config(&config)
 }
 
+/// Absences and holidays by day. `types` is not persisted here; the app
+/// fills it from `read_absence_types`.
 #[derive(Debug, Clone, Default)]
 pub struct SpecialDays {
-    pub vacation_days: HashSet<NaiveDate>,
-    pub sick_days: HashSet<NaiveDate>,
+    /// Absence type key for each marked day.
+    pub absences: HashMap<NaiveDate, String>,
+    pub partial_days: HashMap<NaiveDate, AbsenceAmount>,
+    pub holidays: HolidayCalendar,
+    pub types: Vec<AbsenceType>,
+}
+
+impl SpecialDays {
+    pub fn is_absent(&self, day: NaiveDate) -> bool {
+        self.absences.contains_key(&day)
+    }
+
+    pub fn is_absent_as(&self, day: NaiveDate, key: &str) -> bool {
+        self.absences.get(&day).is_some_and(|value| value == key)
+    }
+
+    /// The absence type marked on `day`, if it is still configured.
+    pub fn absence(&self, day: NaiveDate) -> Option<&AbsenceType> {
+        let key = self.absences.get(&day)?;
+        self.types.iter().find(|absence| absence.key == *key)
+    }
 }
 
 pub fn read_special_days() -> SpecialDays {
@@ -411,116 +625,302 @@ pub fn read_special_days() -> SpecialDays {
     vacation_days.extend(parse_day_list(&config.non_working_days));
     let sick_days = parse_day_list(&config.sick_days);
 
-    for day in &sick_days {
-        vacation_days.remove(day);
-    }
+    let mut absences: HashMap<NaiveDate, String> = vacation_days
+        .into_iter()
+        .map(|day| (day, VACATION.to_string()))
+        .collect();
+    absences.extend(sick_days.into_iter().map(|day| (day, SICK.to_string())));
+    absences.extend(config.absence_days);
+    let partial_days = config
+        .partial_days
+        .into_iter()
+        .filter(|(day, _)| absences.contains_key(day))
+        .collect();
+
+    let holidays = HolidayCalendar::new(&config.holidays, config.holiday_country.as_deref());
 
     SpecialDays {
-        vacation_days,
-        sick_days,
+        absences,
+        partial_days,
+        holidays,
+        types: Vec::new(),
     }
 }
 
-pub fn write_special_days(
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
+pub fn read_vacation_allowance() -> Result<Option<VacationAllowance>, String> {
+    config_vacation_allowance(&read_config().unwrap_or_default())
+}
+
+fn config_vacation_allowance(config: &Config) -> Result<Option<VacationAllowance>, String> {
+    if config.vacation_allowance.is_null() {
+        return Ok(None);
+    }
+    let allowance = VacationAllowance::deserialize(&config.vacation_allowance)
+        .map_err(|err| format!("`vacation_allowance`: {err}."))?;
+    allowance.validate()?;
+    Ok(Some(allowance))
+}
+
+/// Every absence type, vacation and sick first. Entries that do not parse
+/// or validate are left out and reported; vacation and sick missing from
+/// the list come from the legacy per-type fields.
+pub fn read_absence_types() -> (Vec<AbsenceType>, Vec<String>) {
+    config_absence_types(&read_config().unwrap_or_default())
+}
+
+fn config_absence_types(config: &Config) -> (Vec<AbsenceType>, Vec<String>) {
+    let (mut listed, mut errors) =
+        parse_lenient_list::<AbsenceType>(&config.absence_types, "absence_types");
+    let mut types: Vec<AbsenceType> = Vec::with_capacity(listed.len() + 2);
+    for legacy in legacy_absence_types(config) {
+        let builtin = listed
+            .iter()
+            .position(|absence| absence.key.trim().eq_ignore_ascii_case(&legacy.key))
+            .map(|index| listed.remove(index))
+            .and_then(|absence| match validate_absence_type(&absence, &types) {
+                Ok(absence) => Some(absence),
+                Err(err) => {
+                    errors.push(err);
+                    None
+                }
+            });
+        types.push(builtin.unwrap_or(legacy));
+    }
+    for absence in listed {
+        match validate_absence_type(&absence, &types) {
+            Ok(absence) => types.push(absence),
+            Err(err) => errors.push(err),
+        }
+    }
+    (types, errors)
+}
+
+/// Vacation and sick as configured before they moved into `absence_types`.
+fn legacy_absence_types(config: &Config) -> [AbsenceType; 2] {
+    let credit_default = config
+        .credit_special_days_as_worked
+        .unwrap_or(default_credit_special_days_as_worked());
+    let vacation_target = config_vacation_day_target_hours(config).unwrap_or(8.0);
+    let sick_target = config_sick_day_target_hours(config).unwrap_or(8.0);
+    [
+        AbsenceType::vacation(
+            vacation_target,
+            config_vacation_day_credit_hours(config).unwrap_or(vacation_target),
+            config
+                .credit_vacation_days_as_worked
+                .unwrap_or(credit_default),
+        ),
+        AbsenceType::sick(
+            sick_target,
+            config_sick_day_credit_hours(config).unwrap_or(sick_target),
+            config.credit_sick_days_as_worked.unwrap_or(credit_default),
+        ),
+    ]
+}
+
+/// Saves `absence` over the `absence_types` entry with the same key. Entries
+/// that do not parse are kept as written, and the legacy vacation and sick
+/// fields are folded into the list on the first write.
+pub fn write_absence_type(absence: &AbsenceType) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    write_absence_type_to_config(&mut config, absence)?;
+    write_config(&config)
+}
+
+fn write_absence_type_to_config(
+    config: &mut Config,
+    absence: &AbsenceType,
 ) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
-    config.vacation_days = format_day_list(vacation_days);
-    config.sick_days = format_day_list(sick_days);
-    // Keep legacy field in sync for backward compatibility.
-    config.non_working_days = config.vacation_days.clone();
+    let (types, _) = config_absence_types(config);
+    let mut entries = match config.absence_types.take() {
+        Value::Null => Vec::new(),
+        Value::Array(entries) => entries,
+        other => {
+            config.absence_types = other;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "`absence_types` must be a list; leaving it untouched",
+            ));
+        }
+    };
+    let has_key = |entry: &Value, key: &str| {
+        entry
+            .get("key")
+            .and_then(Value::as_str)
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case(key))
+    };
+    let to_value = |absence: &AbsenceType| {
+        serde_json::to_value(absence).map_err(|err| io::Error::other(err.to_string()))
+    };
+    for (index, builtin) in types.iter().take(2).enumerate() {
+        if !entries.iter().any(|entry| has_key(entry, &builtin.key)) {
+            entries.insert(index, to_value(builtin)?);
+        }
+    }
+    match entries
+        .iter_mut()
+        .find(|entry| has_key(entry, &absence.key))
+    {
+        Some(entry) => *entry = to_value(absence)?,
+        None => entries.push(to_value(absence)?),
+    }
+    config.absence_types = Value::Array(entries);
+    config.vacation_day_hours = None;
+    config.vacation_day_target_hours = None;
+    config.vacation_day_credit_hours = None;
+    config.sick_day_hours = None;
+    config.sick_day_target_hours = None;
+    config.sick_day_credit_hours = None;
+    config.credit_special_days_as_worked = None;
+    config.credit_vacation_days_as_worked = None;
+    config.credit_sick_days_as_worked = None;
+    Ok(())
+}
+
+pub fn read_holiday_country() -> Option<String> {
+    read_config().and_then(|config| config.holiday_country)
+}
+
+pub fn write_holiday_country(value: Option<String>) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.holiday_country = value;
     write_config(&config)
 }
 
-fn config_vacation_day_target_hours(config: &Config) -> Option<f64> {
-    config.vacation_day_target_hours.or(config.target_hours)
+pub fn read_imported_holidays() -> Vec<Holiday> {
+    read_config()
+        .map(|config| config.holidays)
+        .unwrap_or_default()
 }
 
-fn config_vacation_day_credit_hours(config: &Config) -> Option<f64> {
-    config
-        .vacation_day_credit_hours
-        .or(config.vacation_day_hours)
+pub fn write_imported_holidays(value: Vec<Holiday>) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.holidays = value;
+    write_config(&config)
 }
 
-fn config_sick_day_target_hours(config: &Config) -> Option<f64> {
-    config.sick_day_target_hours.or(config.target_hours)
+pub fn read_max_entry_hours() -> Option<f64> {
+    read_config().and_then(|config| config.max_entry_hours)
 }
 
-fn config_sick_day_credit_hours(config: &Config) -> Option<f64> {
-    config.sick_day_credit_hours.or(config.sick_day_hours)
+pub fn write_max_entry_hours(value: f64) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.max_entry_hours = Some(value);
+    write_config(&config)
 }
 
-pub fn read_vacation_day_target_hours() -> Option<f64> {
-    read_config().and_then(|config| config_vacation_day_target_hours(&config))
+pub fn read_holiday_target_hours() -> Option<f64> {
+    read_config().and_then(|config| config.holiday_target_hours)
 }
 
-pub fn write_vacation_day_target_hours(value: f64) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
-    config.vacation_day_target_hours = Some(value);
+pub fn write_holiday_target_hours(value: f64) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.holiday_target_hours = Some(value);
     write_config(&config)
 }
 
-pub fn read_vacation_day_credit_hours() -> Option<f64> {
-    read_config().and_then(|config| config_vacation_day_credit_hours(&config))
+pub fn read_holiday_credit_hours() -> Option<f64> {
+    read_config().and_then(|config| config.holiday_credit_hours)
 }
 
-pub fn write_vacation_day_credit_hours(value: f64) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
-    config.vacation_day_credit_hours = Some(value);
+pub fn write_holiday_credit_hours(value: f64) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.holiday_credit_hours = Some(value);
     write_config(&config)
 }
 
-pub fn read_sick_day_target_hours() -> Option<f64> {
-    read_config().and_then(|config| config_sick_day_target_hours(&config))
+pub fn write_special_days(special_days: &SpecialDays) -> Result<(), io::Error> {
+    let days_marked = |key: &str| -> HashSet<NaiveDate> {
+        special_days
+            .absences
+            .iter()
+            .filter(|(_, value)| *value == key)
+            .map(|(day, _)| *day)
+            .collect()
+    };
+    let mut config = read_config_for_update()?;
+    config.vacation_days = format_day_list(&days_marked(VACATION));
+    config.sick_days = format_day_list(&days_marked(SICK));
+    config.absence_days = special_days
+        .absences
+        .iter()
+        .filter(|(_, key)| *key != VACATION && *key != SICK)
+        .map(|(day, key)| (*day, key.clone()))
+        .collect();
+    config.partial_days = special_days
+        .partial_days
+        .iter()
+        .map(|(day, amount)| (*day, *amount))
+        .collect();
+    // Keep legacy field in sync for backward compatibility.
+    config.non_working_days = config.vacation_days.clone();
+    write_config(&config)
 }
 
-pub fn write_sick_day_target_hours(value: f64) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
-    config.sick_day_target_hours = Some(value);
-    write_config(&config)
+fn config_vacation_day_target_hours(config: &Config) -> Option<f64> {
+    config.vacation_day_target_hours.or(config.target_hours)
 }
 
-pub fn read_sick_day_credit_hours() -> Option<f64> {
-    read_config().and_then(|config| config_sick_day_credit_hours(&config))
+fn config_vacation_day_credit_hours(config: &Config) -> Option<f64> {
+    config
+        .vacation_day_credit_hours
+        .or(config.vacation_day_hours)
 }
 
-pub fn write_sick_day_credit_hours(value: f64) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
-    config.sick_day_credit_hours = Some(value);
-    write_config(&config)
+fn config_sick_day_target_hours(config: &Config) -> Option<f64> {
+    config.sick_day_target_hours.or(config.target_hours)
 }
 
-pub fn read_credit_vacation_days_as_worked() -> bool {
-    let default = default_credit_special_days_as_worked();
-    let Some(config) = read_config() else {
-        return default;
-    };
-    let fallback = config.credit_special_days_as_worked.unwrap_or(default);
-    config.credit_vacation_days_as_worked.unwrap_or(fallback)
+fn config_sick_day_credit_hours(config: &Config) -> Option<f64> {
+    config.sick_day_credit_hours.or(config.sick_day_hours)
+}
+
+pub fn read_split_entries_at_midnight() -> bool {
+    read_config()
+        .and_then(|config| config.split_entries_at_midnight)
+        .unwrap_or(false)
 }
 
-pub fn write_credit_vacation_days_as_worked(value: bool) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
-    config.credit_vacation_days_as_worked = Some(value);
+pub fn write_split_entries_at_midnight(value: bool) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.split_entries_at_midnight = Some(value);
     write_config(&config)
 }
 
-pub fn read_credit_sick_days_as_worked() -> bool {
-    let default = default_credit_special_days_as_worked();
-    let Some(config) = read_config() else {
-        return default;
-    };
-    let fallback = config.credit_special_days_as_worked.unwrap_or(default);
-    config.credit_sick_days_as_worked.unwrap_or(fallback)
+pub fn read_restore_date_range() -> bool {
+    read_config()
+        .and_then(|config| config.restore_date_range)
+        .unwrap_or(false)
 }
 
-pub fn write_credit_sick_days_as_worked(value: bool) -> Result<(), io::Error> {
-    let mut config = read_config().unwrap_or_default();
-    config.credit_sick_days_as_worked = Some(value);
+pub fn write_restore_date_range(value: bool) -> Result<(), io::Error> {
+    let mut config = read_config_for_update()?;
+    config.restore_date_range = Some(value);
     write_config(&config)
 }
 
+pub fn read_ui_state() -> UiState {
+    read_config()
+        .and_then(|config| config.ui_state)
+        .unwrap_or_default()
+}
+
+pub fn write_ui_state(value: UiState) -> Result<(), io::Error> {
+    let path = config_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Home directory not found"))?;
+    write_ui_state_to_path(&path, value)
+}
+
+/// Runs on every exit, so it skips the write when nothing changed.
+fn write_ui_state_to_path(path: &Path, value: UiState) -> Result<(), io::Error> {
+    let mut config = read_config_for_update_from_path(path)?;
+    if config.ui_state.as_ref() == Some(&value) {
+        return Ok(());
+    }
+    config.ui_state = Some(value);
+    write_config_to_path(path, &config)
+}
+
 fn parse_day_list(values: &[String]) -> HashSet<NaiveDate> {
     values
         .iter()
@@ -542,6 +942,55 @@ fn read_config() -> Option<Config> {
     read_config_from_path(&path)
 }
 
+/// Why the config file could not be read, when it exists but does not parse.
+pub fn config_error() -> Option<String> {
+    let path = config_path()?;
+    read_config_for_update_from_path(&path)
+        .err()
+        .map(|err| err.to_string())
+}
+
+/// The config to change and write back. A file that exists but does not
+/// parse is an error rather than defaults, so a typo never wipes it.
+fn read_config_for_update() -> Result<Config, io::Error> {
+    let path = config_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Home directory not found"))?;
+    read_config_for_update_from_path(&path)
+}
+
+fn read_config_for_update_from_path(path: &Path) -> Result<Config, io::Error> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+        Err(err) => return Err(err),
+    };
+    serde_json::from_str(&contents).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Config file could not be parsed ({err}); leaving it untouched"),
+        )
+    })
+}
+
+/// Parses a hand-edited list one entry at a time, so a bad entry is left
+/// out with an error instead of failing the whole config.
+fn parse_lenient_list<T: DeserializeOwned>(value: &Value, field: &str) -> (Vec<T>, Vec<String>) {
+    let entries = match value {
+        Value::Null => return (Vec::new(), Vec::new()),
+        Value::Array(entries) => entries,
+        _ => return (Vec::new(), vec![format!("`{field}` must be a list.")]),
+    };
+    let mut parsed = Vec::new();
+    let mut errors = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        match T::deserialize(entry) {
+            Ok(item) => parsed.push(item),
+            Err(err) => errors.push(format!("`{field}` entry {}: {err}.", index + 1)),
+        }
+    }
+    (parsed, errors)
+}
+
 fn write_config(config: &Config) -> Result<(), io::Error> {
     let path = config_path()
         .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Home directory not found"))?;
@@ -582,6 +1031,7 @@ pub fn new_cache(token_hash: String) -> CacheFile {
         version: 1,
         token_hash,
         workspaces: None,
+        me: None,
         projects: HashMap::new(),
         clients: HashMap::new(),
         time_entries: HashMap::new(),
@@ -660,6 +1110,10 @@ const fn default_rollup_include_weekends() -> bool {
     false
 }
 
+const fn default_fiscal_year_start() -> u32 {
+    1
+}
+
 const fn default_credit_special_days_as_worked() -> bool {
     true
 }
@@ -716,6 +1170,92 @@ mod tests {
         assert_eq!(encoded, vec!["2026-02-10", "2026-02-12"]);
     }
 
+    #[test]
+    fn write_ui_state_leaves_unparsable_config_untouched() {
+        let path =
+            std::env::temp_dir().join(format!("toggl2tsc-ui-state-{}.json", std::process::id()));
+        fs::write(&path, "{ \"api_token\": \"secret\", typo }").unwrap();
+        let state = UiState {
+            rollup_year: Some(2026),
+            ..UiState::default()
+        };
+
+        assert!(write_ui_state_to_path(&path, state.clone()).is_err());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{ \"api_token\": \"secret\", typo }"
+        );
+
+        fs::remove_file(&path).unwrap();
+        write_ui_state_to_path(&path, state.clone()).unwrap();
+        let saved = read_config_from_path(&path).unwrap();
+        assert_eq!(saved.ui_state, Some(state));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn lenient_lists_keep_the_entries_that_parse() {
+        let value = serde_json::json!([
+            { "name": "Sprint", "kind": "weeks", "weeks": 2, "anchor": "2026-01-05" },
+            { "name": "Broken", "kind": "fortnights" },
+            { "name": "Acme", "kind": "months", "start_day": 26 }
+        ]);
+        let (cycles, errors) = parse_lenient_list::<BillingCycle>(&value, "billing_cycles");
+
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[1].name, "Acme");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("`billing_cycles` entry 2:"));
+
+        let (cycles, errors) =
+            parse_lenient_list::<BillingCycle>(&serde_json::json!({}), "billing_cycles");
+        assert!(cycles.is_empty());
+        assert_eq!(errors, vec!["`billing_cycles` must be a list.".to_string()]);
+    }
+
+    #[test]
+    fn absence_types_migrate_legacy_fields_and_keep_bad_entries_on_write() {
+        let mut config = Config {
+            target_hours: Some(8.0),
+            vacation_day_hours: Some(7.6),
+            credit_sick_days_as_worked: Some(false),
+            absence_types: serde_json::json!([
+                { "key": "training", "name": "Training", "glyph": "T", "target_hours": 8.0 },
+                { "key": "comp", "name": "Comp", "glyph": 7 }
+            ]),
+            ..Config::default()
+        };
+        let (types, errors) = config_absence_types(&config);
+        let keys: Vec<&str> = types.iter().map(|absence| absence.key.as_str()).collect();
+        assert_eq!(keys, ["vacation", "sick", "training"]);
+        assert_eq!(types[0].credit_hours, 7.6);
+        assert!(!types[1].credit_as_worked);
+        assert_eq!(errors.len(), 1);
+
+        let sick = AbsenceType {
+            credit_as_worked: true,
+            ..types[1].clone()
+        };
+        write_absence_type_to_config(&mut config, &sick).unwrap();
+        assert_eq!(config.vacation_day_hours, None);
+        assert_eq!(config.credit_sick_days_as_worked, None);
+        assert_eq!(config.absence_types.as_array().unwrap().len(), 4);
+        let (migrated, _) = config_absence_types(&config);
+        assert_eq!(migrated[0], types[0]);
+        assert!(migrated[1].credit_as_worked);
+    }
+
+    #[test]
+    fn a_bad_vacation_allowance_leaves_the_rest_of_the_config() {
+        let config: Config = serde_json::from_str(
+            r#"{ "target_hours": 7.5, "vacation_allowance": { "days_per_year": "many" } }"#,
+        )
+        .unwrap();
+        assert_eq!(config.target_hours, Some(7.5));
+        let err = config_vacation_allowance(&config).unwrap_err();
+        assert!(err.starts_with("`vacation_allowance`:"));
+    }
+
     #[test]
     fn read_special_days_merges_legacy_non_working() {
         let config = Config {
@@ -861,4 +1401,25 @@ mod tests {
             ThemeSelection::custom("theme-aurora")
         );
     }
+
+    #[test]
+    fn ui_state_round_trips_and_old_preferences_still_load() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let config = Config {
+            ui_state: Some(UiState {
+                rollup_view: Some("monthly".to_string()),
+                rollup_year: Some(2025),
+                date_range: Some((day, day)),
+            }),
+            ..Config::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let decoded: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.ui_state, config.ui_state);
+
+        let preferences: RollupPreferences =
+            serde_json::from_str(r#"{"include_weekends":true}"#).unwrap();
+        assert!(preferences.include_weekends);
+        assert!(!preferences.remember_weekend_toggle);
+    }
 }
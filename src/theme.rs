@@ -276,7 +276,7 @@ fn cycle_theme_selection(
     values[next_index].clone()
 }
 
-fn normalize_hex(input: &str) -> Result<String, String> {
+pub fn normalize_hex(input: &str) -> Result<String, String> {
     let trimmed = input.trim();
     let Some(rest) = trimmed.strip_prefix('#') else {
         return Err("Colors must use #RRGGBB format.".to_string());
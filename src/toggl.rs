@@ -3,7 +3,7 @@ use base64::engine::general_purpose::STANDARD;
 use reqwest::blocking::Client;
 use serde::de::DeserializeOwned;
 
-use crate::models::{Client as TogglClientModel, Project, TimeEntry, Workspace};
+use crate::models::{Client as TogglClientModel, Me, Project, TimeEntry, Workspace};
 
 #[derive(Debug, Clone)]
 pub enum TogglError {
@@ -37,6 +37,11 @@ impl TogglClient {
         self.fetch(url.to_string())
     }
 
+    pub fn fetch_me(&self) -> Result<Me, TogglError> {
+        let url = "https://api.track.toggl.com/api/v9/me".to_string();
+        self.fetch(url)
+    }
+
     pub fn fetch_workspaces(&self) -> Result<Vec<Workspace>, TogglError> {
         let url = "https://api.track.toggl.com/api/v9/workspaces".to_string();
         self.fetch(url)
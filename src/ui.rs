@@ -1,28 +1,43 @@
-use chrono::{Datelike, Duration, Local, NaiveDate};
+use chrono::{Datelike, Duration, NaiveDate};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
-    Block, BorderType, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, Wrap,
+    Bar, BarChart, BarGroup, Block, BorderType, Borders, Cell, Clear, List, ListItem, Paragraph,
+    Row, Sparkline, Table, Wrap,
 };
 use std::collections::{HashMap, HashSet};
 
+use crate::absences::AbsenceType;
 use crate::app::{
-    App, DashboardFocus, DateInputMode, Mode, RollupFocus, RollupView, SettingsFocus, SettingsItem,
+    App, DashboardFocus, DateInputMode, Mode, RollupBreakdown, RollupFocus, RollupPane, RollupView,
+    SettingsFocus, SettingsItem,
 };
-use crate::rollups::WeekStart;
-use crate::rollups::{DailyTotal, PeriodRollup};
+use crate::balance::{
+    BalanceContext, hours_from_seconds, normalize_delta, period_scope_adjustment,
+};
+use crate::comparison::PeriodFigures;
+use crate::dates::{RangeSnap, WeekStart};
+use crate::gaps::GapKind;
+use crate::patterns::{EVENING_HOUR, clock_label};
+use crate::rollups::{Breakdown, DailyTotal, PeriodRollup, top_shares};
+use crate::storage::SpecialDays;
 use crate::theme::{
     CustomTheme, ThemePalette, ThemePreference, ThemeSelection, theme_selection_label,
 };
 use crate::update;
+use crate::validation::IssueKind;
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
     let size = frame.area();
     let theme = theme_from(&app.theme, app.custom_themes());
     draw_background(frame, size, &theme);
-    if matches!(app.mode, Mode::Rollups | Mode::RefetchConfirm) {
+    if matches!(
+        app.mode,
+        Mode::Rollups | Mode::RefetchConfirm | Mode::AbsenceInput
+    ) || (app.mode == Mode::Gaps && app.gap_return_mode() == Mode::Rollups)
+    {
         draw_rollups(frame, app, size, &theme);
     } else {
         draw_dashboard(frame, app, size, &theme);
@@ -41,6 +56,9 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         Mode::DateInput(mode) => draw_date_input(frame, app, size, mode, &theme),
         Mode::Settings => draw_settings(frame, app, size, &theme),
         Mode::RefetchConfirm => draw_refetch_confirm(frame, app, size, &theme),
+        Mode::AbsenceInput => draw_absence_input(frame, app, size, &theme),
+        Mode::Gaps => draw_gap_report(frame, app, size, &theme),
+        Mode::Issues => draw_issues(frame, app, size, &theme),
         Mode::Dashboard | Mode::Rollups => {}
     }
 
@@ -151,8 +169,14 @@ fn draw_dashboard(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         vec![ListItem::new(Line::from("No entries")).style(theme.panel_style())]
     };
 
+    let entries_title = match current_project {
+        Some(project) if project.rounding.config.is_some() || app.rounding.is_some() => {
+            format!("Entries · rounding {}", project.rounding.label())
+        }
+        _ => "Entries".to_string(),
+    };
     let entries_list = List::new(entry_items)
-        .block(panel_block("Entries", theme))
+        .block(panel_block(&entries_title, theme))
         .highlight_style(entry_highlight_style)
         .highlight_symbol(entry_highlight_symbol);
 
@@ -169,7 +193,9 @@ fn draw_dashboard(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
 }
 
 fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
-    let special_day_hours = SpecialDayHours::from_app(app);
+    let schedule = app.work_schedule();
+    let special_days = app.special_days().clone();
+    let balance = BalanceContext::new(&schedule, &special_days, app.holiday_hours());
     let content = area.inner(Margin {
         vertical: 1,
         horizontal: 2,
@@ -184,7 +210,8 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         ])
         .split(content);
 
-    let header = rollups_header_line(app, theme);
+    let mut header = vec![rollups_header_line(app, theme)];
+    header.extend(vacation_balance_line(app, theme));
     let header_block = Paragraph::new(header).alignment(Alignment::Left).block(
         Block::default()
             .borders(Borders::BOTTOM)
@@ -201,7 +228,9 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
     let periods = match app.rollup_view {
         RollupView::Weekly => &app.rollups.weekly,
         RollupView::Monthly => &app.rollups.monthly,
+        RollupView::Quarterly => &app.rollups.quarterly,
         RollupView::Yearly => &app.rollups.yearly,
+        RollupView::Cycle => &app.rollups.cycle,
     };
 
     let period_items: Vec<ListItem> = if periods.is_empty() {
@@ -210,23 +239,9 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         periods
             .iter()
             .map(|period| {
-                let hours = period_effective_hours(
-                    period,
-                    &app.rollups.daily,
-                    app.credit_vacation_days_as_worked(),
-                    app.credit_sick_days_as_worked(),
-                    app.vacation_days(),
-                    app.sick_days(),
-                    special_day_hours,
-                );
-                let (target, _) = period_target_hours(
-                    period,
-                    app.target_hours,
-                    app.rollups_include_weekends,
-                    app.vacation_days(),
-                    app.sick_days(),
-                    special_day_hours,
-                );
+                let hours = balance.period_effective_hours(period, &app.rollups.daily)
+                    + period_scope_adjustment(period, &app.rollups.daily);
+                let (target, _) = balance.period_target_hours(period);
                 let overtime = normalize_delta(hours - target);
                 let overtime_style = delta_style(overtime, theme);
                 let missing_days = app.rollup_period_missing_days(period);
@@ -267,7 +282,11 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
     let period_title = match app.rollup_view {
         RollupView::Weekly => "Weeks",
         RollupView::Monthly => "Months",
+        RollupView::Quarterly => "Quarters",
         RollupView::Yearly => "Years",
+        RollupView::Cycle => app
+            .selected_billing_cycle()
+            .map_or("Cycles", |cycle| cycle.name.as_str()),
     };
 
     let period_list = List::new(period_items)
@@ -275,17 +294,34 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         .highlight_style(period_highlight_style)
         .highlight_symbol(period_highlight_symbol);
 
+    let left_sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(if app.time_bank().is_some() { 7 } else { 0 }),
+        ])
+        .split(body[0]);
+
     match app.rollup_view {
         RollupView::Weekly => {
-            frame.render_stateful_widget(period_list, body[0], &mut app.rollup_week_state)
+            frame.render_stateful_widget(period_list, left_sections[0], &mut app.rollup_week_state)
         }
         RollupView::Monthly => {
-            frame.render_stateful_widget(period_list, body[0], &mut app.rollup_month_state)
+            frame.render_stateful_widget(period_list, left_sections[0], &mut app.rollup_month_state)
         }
+        RollupView::Quarterly => frame.render_stateful_widget(
+            period_list,
+            left_sections[0],
+            &mut app.rollup_quarter_state,
+        ),
         RollupView::Yearly => {
-            frame.render_stateful_widget(period_list, body[0], &mut app.rollup_year_state)
+            frame.render_stateful_widget(period_list, left_sections[0], &mut app.rollup_year_state)
+        }
+        RollupView::Cycle => {
+            frame.render_stateful_widget(period_list, left_sections[0], &mut app.rollup_cycle_state)
         }
     };
+    draw_time_bank(frame, app, left_sections[1], &balance, theme);
 
     let right_sections = Layout::default()
         .direction(Direction::Vertical)
@@ -299,43 +335,16 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         .and_then(|index| daily.get(index).copied());
 
     let summary_lines = if let Some(period) = app.rollup_selected_period() {
-        let total_hours = period_effective_hours(
-            period,
-            &app.rollups.daily,
-            app.credit_vacation_days_as_worked(),
-            app.credit_sick_days_as_worked(),
-            app.vacation_days(),
-            app.sick_days(),
-            special_day_hours,
-        );
-        let (target_hours, target_days) = period_target_hours(
-            period,
-            app.target_hours,
-            app.rollups_include_weekends,
-            app.vacation_days(),
-            app.sick_days(),
-            special_day_hours,
-        );
-        let overtime = period_overtime_hours(
-            period,
-            &app.rollups.daily,
-            app.target_hours,
-            app.rollups_include_weekends,
-            app.vacation_days(),
-            app.sick_days(),
-            special_day_hours,
-            app.credit_vacation_days_as_worked(),
-            app.credit_sick_days_as_worked(),
-        );
-        let (worked_hours, worked_days) = period_worked_totals_until(
+        let scope_adjustment = period_scope_adjustment(period, &app.rollups.daily);
+        let total_hours =
+            balance.period_effective_hours(period, &app.rollups.daily) + scope_adjustment;
+        let (target_hours, target_days) = balance.period_target_hours(period);
+        let overtime = balance.period_overtime_hours(period, &app.rollups.daily);
+        let overtime = normalize_delta(overtime + scope_adjustment);
+        let (worked_hours, worked_days) = balance.period_worked_totals_until(
             period,
             &app.rollups.daily,
             app.date_range.end_date(),
-            app.credit_vacation_days_as_worked(),
-            app.credit_sick_days_as_worked(),
-            app.vacation_days(),
-            app.sick_days(),
-            special_day_hours,
         );
         let avg = if worked_days > 0 {
             worked_hours / worked_days as f64
@@ -348,7 +357,10 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
                 period.label.clone(),
                 Style::default().add_modifier(Modifier::BOLD),
             )),
-            Line::from(format!("Total: {:.2}h", total_hours)),
+            Line::from(vec![
+                Span::raw(format!("Total: {:.2}h", total_hours)),
+                rounding_delta_span(period.raw_seconds, period.seconds, theme),
+            ]),
             Line::from(format!(
                 "Target: {:.2}h ({} target days)",
                 target_hours, target_days
@@ -374,24 +386,9 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         ];
 
         if let Some(day) = selected_day {
-            let hours = effective_hours_for_day(
-                day.date,
-                hours_from_seconds(day.seconds),
-                app.credit_vacation_days_as_worked(),
-                app.credit_sick_days_as_worked(),
-                app.vacation_days(),
-                app.sick_days(),
-                special_day_hours,
-            );
-            let day_target = target_hours_for_day(
-                day.date,
-                app.target_hours,
-                app.rollups_include_weekends,
-                app.vacation_days(),
-                app.sick_days(),
-                special_day_hours,
-            );
-            let day_delta = normalize_delta(hours - day_target);
+            let worked_hours = hours_from_seconds(day.seconds);
+            let hours = balance.effective_hours(day.date, worked_hours);
+            let day_delta = balance.overtime_hours(day.date, worked_hours);
             let label = day.date.format("%a %Y-%m-%d").to_string();
             let special = special_day_suffix(app, day.date);
             let fetch_tag = if app.is_rollup_day_fetched(day.date) {
@@ -416,24 +413,31 @@ fn draw_rollups(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         .alignment(Alignment::Left)
         .block(panel_block("Summary", theme))
         .wrap(Wrap { trim: true });
-    frame.render_widget(summary, right_sections[0]);
-
-    if let Some(period) = app.rollup_selected_period() {
+    let summary_sections = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(right_sections[0]);
+    frame.render_widget(summary, summary_sections[0]);
+    let breakdown_day = selected_day.filter(|_| matches!(app.rollup_focus, RollupFocus::Days));
+    draw_rollup_breakdown(frame, app, breakdown_day, summary_sections[1], theme);
+
+    if app.rollup_pane == RollupPane::Comparison {
+        draw_rollup_comparison(frame, app, right_sections[1], theme);
+    } else if app.rollup_pane == RollupPane::Patterns {
+        draw_rollup_patterns(frame, app, right_sections[1], theme);
+    } else if let Some(period) = app.rollup_selected_period() {
         let calendar = build_calendar_lines(
             &daily,
             period,
             selected_day.map(|day| day.date),
             app.rollup_focus,
             app.rollup_view,
-            app.target_hours,
+            &balance,
             app.rollups_include_weekends,
-            app.vacation_days(),
-            app.sick_days(),
-            special_day_hours,
-            app.credit_vacation_days_as_worked(),
-            app.credit_sick_days_as_worked(),
+            &special_days,
             app.rollup_fetched_days(),
             app.rollups_week_start,
+            app.timezone.today(),
             theme,
         );
         let viewport_height = right_sections[1].height.saturating_sub(2) as usize;
@@ -475,7 +479,9 @@ fn rollups_header_line(app: &App, theme: &Theme) -> Line<'static> {
     let view_label = match app.rollup_view {
         RollupView::Weekly => "Weekly",
         RollupView::Monthly => "Monthly",
+        RollupView::Quarterly => "Quarterly",
         RollupView::Yearly => "Yearly",
+        RollupView::Cycle => "Billing cycle",
     };
     let weekends = if app.rollups_include_weekends {
         "On"
@@ -486,11 +492,19 @@ fn rollups_header_line(app: &App, theme: &Theme) -> Line<'static> {
         WeekStart::Monday => "Mon",
         WeekStart::Sunday => "Sun",
     };
+    let calendar = app.period_calendar();
+    let focus_year = calendar.fiscal_year_label(app.rollup_year_cursor());
     let year_info = match app.rollup_view {
-        RollupView::Weekly | RollupView::Monthly => format!("{}", app.rollup_year_cursor()),
+        RollupView::Weekly | RollupView::Monthly | RollupView::Quarterly | RollupView::Cycle => {
+            focus_year
+        }
         RollupView::Yearly => {
             let (start, end) = app.rollup_year_window();
-            format!("{start}..{end} (focus {})", app.rollup_year_cursor())
+            format!(
+                "{}..{} (focus {focus_year})",
+                calendar.fiscal_year_label(start),
+                calendar.fiscal_year_label(end)
+            )
         }
     };
     Line::from(vec![
@@ -522,6 +536,414 @@ fn rollups_header_line(app: &App, theme: &Theme) -> Line<'static> {
     ])
 }
 
+fn vacation_balance_line(app: &App, theme: &Theme) -> Option<Line<'static>> {
+    let balance = app.vacation_balance(app.vacation_allowance_year())?;
+    let remaining = balance.remaining();
+    let remaining_style = if remaining < 0.0 {
+        Style::default()
+            .fg(theme.error)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    let mut details = format!("of {:.1}", balance.allowance);
+    if balance.carried_over > 0.0 {
+        details.push_str(&format!(", +{:.1} carried", balance.carried_over));
+    }
+    if balance.expired > 0.0 {
+        details.push_str(&format!(", -{:.1} expired", balance.expired));
+    }
+    Some(Line::from(vec![
+        Span::styled(format!("Vacation {}", balance.year), theme.muted_style()),
+        Span::raw(": "),
+        Span::styled(
+            format!("{:.1}", balance.used),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" used", theme.muted_style()),
+        Span::raw(" · "),
+        Span::styled(
+            format!("{:.1}", balance.planned),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" planned", theme.muted_style()),
+        Span::raw(" · "),
+        Span::styled(format!("{remaining:.1}"), remaining_style),
+        Span::styled(format!(" left ({details} days)"), theme.muted_style()),
+    ]))
+}
+
+fn draw_time_bank(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    balance: &BalanceContext,
+    theme: &Theme,
+) {
+    let Some(bank) = app.time_bank() else {
+        return;
+    };
+    let block = panel_block("Time bank", theme);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let balances = balance.running_balance(
+        bank.opening_hours,
+        app.time_bank_daily(),
+        app.time_bank_counted_days(),
+    );
+    let balance = normalize_delta(
+        balances
+            .last()
+            .map(|(_, balance)| *balance)
+            .unwrap_or(bank.opening_hours),
+    );
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{balance:+.2}h"),
+            delta_style(balance, theme).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  since {} ({:+.2}h opening)",
+                bank.start.format("%Y-%m-%d"),
+                bank.opening_hours
+            ),
+            theme.muted_style(),
+        ),
+    ])];
+    let missing_days = app.time_bank_missing_days();
+    if missing_days > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("!{missing_days}d not fetched, not counted yet"),
+            Style::default().fg(theme.highlight),
+        )));
+    }
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(lines.len() as u16), Constraint::Min(0)])
+        .split(inner);
+    frame.render_widget(Paragraph::new(lines), sections[0]);
+
+    // One sample per week keeps long spans readable; shift so the lowest
+    // balance sits at the baseline.
+    let mut samples: Vec<f64> = balances
+        .iter()
+        .step_by(7)
+        .map(|(_, value)| *value)
+        .collect();
+    if balances.len() % 7 != 1
+        && let Some((_, last)) = balances.last()
+    {
+        samples.push(*last);
+    }
+    let floor = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let data: Vec<u64> = samples
+        .iter()
+        .map(|value| ((value - floor) * 100.0).round() as u64)
+        .collect();
+    let width = sections[1].width as usize;
+    let sparkline = Sparkline::default()
+        .data(&data[data.len().saturating_sub(width)..])
+        .style(Style::default().fg(theme.accent));
+    frame.render_widget(sparkline, sections[1]);
+}
+
+/// The selected period next to the previous one and the same period last
+/// year, with a bar chart of the periods leading up to it.
+fn draw_rollup_comparison(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
+    let Some(comparison) = app.rollup_comparison().cloned() else {
+        let empty =
+            Paragraph::new(Line::from("No rollup data.")).block(panel_block("Comparison", theme));
+        frame.render_widget(empty, area);
+        return;
+    };
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(9)])
+        .split(area);
+
+    // The yearly view has no separate last-year column.
+    let mut others = vec![comparison.previous.as_ref()];
+    if comparison.last_year.is_some() {
+        others.push(comparison.last_year.as_ref());
+    }
+    let value_cell = |value: Option<String>| Cell::from(value.unwrap_or_else(|| "-".to_string()));
+    let delta_cell = |current: f64, other: Option<f64>| match other {
+        Some(other) => {
+            let delta = normalize_delta(current - other);
+            Cell::from(Span::styled(
+                format!("{delta:+.2}h"),
+                delta_style(delta, theme),
+            ))
+        }
+        None => Cell::from(""),
+    };
+    let figure_row = |label: String, value: &dyn Fn(&PeriodFigures) -> f64, signed: bool| {
+        let format_value = |hours: f64| {
+            if signed {
+                format!("{hours:+.2}h")
+            } else {
+                format!("{hours:.2}h")
+            }
+        };
+        let current = value(&comparison.current);
+        let mut cells = vec![Cell::from(label), Cell::from(format_value(current))];
+        for other in &others {
+            let other = other.map(value);
+            cells.push(value_cell(other.map(format_value)));
+            cells.push(delta_cell(current, other));
+        }
+        Row::new(cells)
+    };
+
+    let mut from = vec![
+        Cell::from(Span::styled("From", theme.muted_style())),
+        Cell::from(comparison.current.start.format("%Y-%m-%d").to_string()),
+    ];
+    for other in &others {
+        from.push(value_cell(
+            other.map(|figures| figures.start.format("%Y-%m-%d").to_string()),
+        ));
+        from.push(Cell::from(""));
+    }
+    let mut rows = vec![
+        Row::new(from),
+        figure_row("Total".to_string(), &|figures| figures.hours, false),
+        figure_row("Overtime".to_string(), &|figures| figures.overtime, true),
+    ];
+    let fixed_rows = rows.len() + 2;
+    let client_limit = (sections[0].height as usize)
+        .saturating_sub(fixed_rows + 2)
+        .max(1);
+    for client in comparison.client_rows(client_limit) {
+        let seconds = move |figures: &PeriodFigures| {
+            hours_from_seconds(figures.clients.get(&client).copied().unwrap_or(0))
+        };
+        rows.push(figure_row(app.client_label(client), &seconds, false));
+    }
+    let missing: Vec<usize> = std::iter::once(Some(&comparison.current))
+        .chain(others.iter().copied())
+        .map(|figures| figures.map_or(0, |figures| figures.missing_days))
+        .collect();
+    if missing.iter().any(|days| *days > 0) {
+        let missing_style = Style::default().fg(theme.highlight);
+        let mut cells = vec![Cell::from(Span::styled("Not fetched", missing_style))];
+        for (index, days) in missing.iter().enumerate() {
+            cells.push(Cell::from(Span::styled(format!("{days}d"), missing_style)));
+            if index > 0 {
+                cells.push(Cell::from(""));
+            }
+        }
+        rows.push(Row::new(cells));
+    }
+
+    let mut header = vec![Cell::from(""), Cell::from("Selected")];
+    let mut widths = vec![Constraint::Min(12), Constraint::Length(11)];
+    for label in ["Previous", "Last year"].into_iter().take(others.len()) {
+        header.extend([Cell::from(label), Cell::from("")]);
+        widths.extend([Constraint::Length(11), Constraint::Length(9)]);
+    }
+    let header = Row::new(header).style(theme.muted_style().add_modifier(Modifier::BOLD));
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(panel_block("Comparison", theme));
+    frame.render_widget(table, sections[0]);
+
+    let last = comparison.trend.len().saturating_sub(1);
+    let bars: Vec<Bar> = comparison
+        .trend
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            let style = if index == last {
+                Style::default().fg(theme.accent)
+            } else {
+                theme.muted_style()
+            };
+            // Tenths of an hour, since bar values are whole numbers.
+            Bar::default()
+                .value((point.hours.max(0.0) * 10.0).round() as u64)
+                .text_value(format!("{:.0}", point.hours))
+                .label(Line::from(point.label.clone()))
+                .style(style)
+        })
+        .collect();
+    let inner_width = sections[1].width.saturating_sub(2);
+    let bar_count = bars.len().max(1) as u16;
+    let bar_width = (inner_width / bar_count).saturating_sub(1).clamp(1, 6);
+    let chart = BarChart::default()
+        .block(panel_block("Trend (hours)", theme))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .value_style(Style::default().add_modifier(Modifier::BOLD));
+    frame.render_widget(chart, sections[1]);
+}
+
+/// Hour-of-day by weekday heatmap of the selected period, with start/end
+/// averages and the longest run of workdays without tracked time.
+fn draw_rollup_patterns(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
+    let block = panel_block("Work patterns", theme);
+    let Some(pattern) = app.rollup_pattern().cloned() else {
+        frame.render_widget(Paragraph::new("No rollup data.").block(block), area);
+        return;
+    };
+
+    let clock = |minutes: Option<u32>| minutes.map_or("-".to_string(), clock_label);
+    let total_hours = hours_from_seconds(pattern.total_seconds);
+    let evening_hours = hours_from_seconds(pattern.evening_seconds);
+    let evening_share = if pattern.total_seconds > 0 {
+        pattern.evening_seconds as f64 * 100.0 / pattern.total_seconds as f64
+    } else {
+        0.0
+    };
+    let streak = match pattern.longest_untracked {
+        Some(streak) => Span::styled(
+            format!(
+                "{}d ({} → {})",
+                streak.days,
+                streak.start.format("%Y-%m-%d"),
+                streak.end.format("%Y-%m-%d")
+            ),
+            Style::default().fg(theme.highlight),
+        ),
+        None => Span::styled("none", theme.muted_style()),
+    };
+    let mut lines = vec![
+        Line::from(format!(
+            "Avg start {} · Avg end {} · {} tracked day(s)",
+            clock(pattern.average_start),
+            clock(pattern.average_end),
+            pattern.tracked_days
+        )),
+        Line::from(format!(
+            "From {EVENING_HOUR}:00: {evening_hours:.2}h ({evening_share:.0}%) of {total_hours:.2}h"
+        )),
+        Line::from(vec![
+            Span::raw(format!(
+                "Untracked workdays: {} · Longest streak: ",
+                pattern.untracked_workdays
+            )),
+            streak,
+        ]),
+        Line::from(""),
+    ];
+
+    let header: String = (0..24)
+        .step_by(3)
+        .map(|hour| format!("{hour:<6}"))
+        .collect();
+    lines.push(Line::from(Span::styled(
+        format!("    {header}"),
+        theme.muted_style(),
+    )));
+    let max = pattern.max_cell();
+    let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    for (weekday, hours) in weekdays.iter().zip(pattern.heatmap.iter()) {
+        let mut spans = vec![Span::styled(format!("{weekday} "), theme.muted_style())];
+        for (hour, seconds) in hours.iter().enumerate() {
+            let cell = match (*seconds, max) {
+                (0, _) | (_, 0) => "··",
+                (seconds, max) if seconds * 4 <= max => "░░",
+                (seconds, max) if seconds * 2 <= max => "▒▒",
+                (seconds, max) if seconds * 4 <= max * 3 => "▓▓",
+                _ => "██",
+            };
+            let style = if *seconds == 0 {
+                theme.muted_style()
+            } else if hour as u32 >= EVENING_HOUR {
+                Style::default().fg(theme.highlight)
+            } else {
+                Style::default().fg(theme.accent)
+            };
+            spans.push(Span::styled(cell, style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Top projects or clients for the selected period, or for the selected
+/// day while the calendar has focus.
+fn draw_rollup_breakdown(
+    frame: &mut Frame,
+    app: &App,
+    day: Option<&DailyTotal>,
+    area: Rect,
+    theme: &Theme,
+) {
+    let kind = match app.rollup_breakdown {
+        RollupBreakdown::Projects => "Top projects",
+        RollupBreakdown::Clients => "Top clients",
+    };
+    let (title, breakdown) = match day {
+        Some(day) => (
+            format!("{kind} · {}", day.date.format("%a %m-%d")),
+            app.rollup_breakdown_of(&day.projects),
+        ),
+        None => (
+            kind.to_string(),
+            app.rollup_selected_period()
+                .map(|period| app.rollup_breakdown_of(&period.projects))
+                .unwrap_or_default(),
+        ),
+    };
+    let block = panel_block(&title, theme);
+    let rows = block.inner(area).height as usize;
+    let total: i64 = breakdown.values().sum();
+    if total <= 0 {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "No tracked time",
+            theme.muted_style(),
+        )))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    // Leave the last row for the remainder when it does not all fit.
+    let limit = if breakdown.len() > rows {
+        rows.saturating_sub(1)
+    } else {
+        rows
+    };
+    let (shares, other) = top_shares(&breakdown, limit);
+    let mut lines: Vec<Line> = shares
+        .iter()
+        .map(|share| {
+            breakdown_line(
+                &app.rollup_breakdown_label(share.id),
+                share.seconds,
+                share.percent,
+                theme,
+            )
+        })
+        .collect();
+    if other > 0 {
+        let percent = other as f64 * 100.0 / total as f64;
+        let label = format!("{} more", breakdown.len() - shares.len());
+        lines.push(breakdown_line(&label, other, percent, theme));
+    }
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn breakdown_line(label: &str, seconds: i64, percent: f64, theme: &Theme) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{percent:>5.1}%"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {:>7.2}h  ", hours_from_seconds(seconds)),
+            theme.muted_style(),
+        ),
+        Span::raw(label.to_string()),
+    ])
+}
+
 fn rollups_footer_line(app: &mut App, theme: &Theme) -> Line<'static> {
     let status = app.visible_status().unwrap_or_default();
     Line::from(vec![
@@ -535,16 +957,32 @@ fn rollups_footer_line(app: &mut App, theme: &Theme) -> Line<'static> {
         Span::raw(" · "),
         Span::styled("m monthly", theme.muted_style()),
         Span::raw(" · "),
+        Span::styled("Q quarterly", theme.muted_style()),
+        Span::raw(" · "),
         Span::styled("y yearly", theme.muted_style()),
         Span::raw(" · "),
+        Span::styled("b billing cycle", theme.muted_style()),
+        Span::raw(" · "),
         Span::styled("[ / ] year", theme.muted_style()),
         Span::raw(" · "),
         Span::styled("z weekends", theme.muted_style()),
         Span::raw(" · "),
+        Span::styled("p projects/clients", theme.muted_style()),
+        Span::raw(" · "),
+        Span::styled("c compare", theme.muted_style()),
+        Span::raw(" · "),
+        Span::styled("v patterns", theme.muted_style()),
+        Span::raw(" · "),
+        Span::styled("f gaps", theme.muted_style()),
+        Span::raw(" · "),
         Span::styled("k vacation day", theme.muted_style()),
         Span::raw(" · "),
         Span::styled("j sick day", theme.muted_style()),
         Span::raw(" · "),
+        Span::styled("t absence type", theme.muted_style()),
+        Span::raw(" · "),
+        Span::styled("a partial day", theme.muted_style()),
+        Span::raw(" · "),
         Span::styled("g studio", theme.muted_style()),
         Span::raw(" · "),
         Span::styled("R refetch scope", theme.muted_style()),
@@ -563,7 +1001,7 @@ fn rollups_footer_line(app: &mut App, theme: &Theme) -> Line<'static> {
 }
 
 fn header_line(app: &App, theme: &Theme) -> Line<'static> {
-    let special_day_hours = SpecialDayHours::from_app(app);
+    let holiday_hours = app.holiday_hours();
     let workspace = app
         .selected_workspace
         .as_ref()
@@ -574,22 +1012,17 @@ fn header_line(app: &App, theme: &Theme) -> Line<'static> {
         .map(|dt| dt.format("%H:%M:%S").to_string())
         .unwrap_or_else(|| "Never".to_string());
     let active_day = app.date_range.end_date();
-    let special_day = if app.is_vacation_day(active_day) {
+    let special_day = if let Some(name) = app.holiday_name(active_day) {
         Some(format_special_day_label(
-            "Vacation",
-            special_day_hours.vacation_target_hours,
-            special_day_hours.vacation_credit_hours,
-            false,
-        ))
-    } else if app.is_sick_day(active_day) {
-        Some(format_special_day_label(
-            "Sick",
-            special_day_hours.sick_target_hours,
-            special_day_hours.sick_credit_hours,
+            &format!("Holiday: {name}"),
+            holiday_hours.target_hours,
+            holiday_hours.credit_hours,
             false,
         ))
     } else {
-        None
+        app.special_days()
+            .absence(active_day)
+            .map(|absence| absence_label(app, active_day, &absence.name, absence, false))
     };
 
     let mut spans = vec![
@@ -607,6 +1040,19 @@ fn header_line(app: &App, theme: &Theme) -> Line<'static> {
         Span::raw(app.date_range.label().to_string()),
     ];
 
+    let issues = app.entry_issues().len();
+    if issues > 0 {
+        spans.extend([
+            Span::raw("  "),
+            Span::styled(
+                format!("⚠ {issues} issue{} (i)", if issues == 1 { "" } else { "s" }),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]);
+    }
+
     if let Some(day_label) = special_day {
         spans.extend([
             Span::raw("  "),
@@ -632,7 +1078,10 @@ fn header_line(app: &App, theme: &Theme) -> Line<'static> {
 }
 
 fn footer_line(app: &mut App, theme: &Theme) -> Line<'static> {
-    let total_style = if app.total_hours < app.target_hours {
+    let target_hours = app
+        .work_schedule()
+        .hours_between(app.date_range.start_date(), app.date_range.end_date());
+    let total_style = if app.total_hours < target_hours {
         Style::default()
             .fg(theme.error)
             .add_modifier(Modifier::BOLD)
@@ -643,16 +1092,28 @@ fn footer_line(app: &mut App, theme: &Theme) -> Line<'static> {
     };
 
     let status = app.visible_status().unwrap_or_default();
+    let step_label = match app.date_range.snap() {
+        Some(RangeSnap::Week(_)) => "week",
+        Some(RangeSnap::Month) => "month",
+        None => "period",
+    };
     Line::from(vec![
         Span::styled(format!("Total {:.2}h", app.total_hours), total_style),
+        rounding_delta_span(
+            (app.raw_hours * 3600.0).round() as i64,
+            (app.total_hours * 3600.0).round() as i64,
+            theme,
+        ),
         Span::raw("   "),
         Span::styled("h help", theme.muted_style()),
         Span::raw(" · "),
         Span::styled("o rollups", theme.muted_style()),
         Span::raw(" · "),
+        Span::styled("f gaps", theme.muted_style()),
+        Span::raw(" · "),
         Span::styled("[/]", theme.muted_style()),
         Span::raw(" "),
-        Span::styled("period", theme.muted_style()),
+        Span::styled(step_label, theme.muted_style()),
         Span::raw(" · "),
         Span::styled("k vacation day", theme.muted_style()),
         Span::raw(" · "),
@@ -851,11 +1312,11 @@ fn draw_workspace_select(frame: &mut Frame, app: &mut App, area: Rect, theme: &T
 }
 
 fn draw_date_input(frame: &mut Frame, app: &App, area: Rect, mode: DateInputMode, theme: &Theme) {
-    let block = centered_rect(60, 30, area);
+    let block = centered_rect(60, 40, area);
     frame.render_widget(Clear, block);
 
     let label = match mode {
-        DateInputMode::Range => "Select date range (YYYY-MM-DD)",
+        DateInputMode::Range => "Select date range",
     };
 
     let start_value = if app.is_date_start_active() {
@@ -887,6 +1348,15 @@ fn draw_date_input(frame: &mut Frame, app: &App, area: Rect, mode: DateInputMode
             end_value,
         ]),
         Line::from(""),
+        Line::from(Span::styled(
+            "YYYY-MM-DD, today, -3d, mon..fri, last week, this month, 2026-W41, Q3, 2026-10",
+            theme.muted_style(),
+        )),
+        Line::from(Span::styled(
+            "Leave End empty to use the whole Start span.",
+            theme.muted_style(),
+        )),
+        Line::from(""),
         Line::from("Tab to switch field • Enter apply • Esc cancel"),
     ];
 
@@ -912,15 +1382,19 @@ fn draw_refetch_confirm(frame: &mut Frame, app: &App, area: Rect, theme: &Theme)
     let lines = if let Some(plan) = app.refetch_plan_view() {
         let warning = if plan.estimated_calls > plan.remaining_calls {
             format!(
-                "Warning: needs ~{} call(s), only {} local calls remain.",
+                "Warning: needs {} call(s), only {} local calls remain. Never-fetched days go first.",
                 plan.estimated_calls, plan.remaining_calls
             )
         } else {
             format!(
-                "This may use up to {} API call(s). Remaining local budget: {}.",
+                "Uses {} API call(s). Remaining local budget: {}.",
                 plan.estimated_calls, plan.remaining_calls
             )
         };
+        let mut batches = plan.batches.iter().take(4).cloned().collect::<Vec<_>>();
+        if plan.batches.len() > 4 {
+            batches.push(format!("+{} more", plan.batches.len() - 4));
+        }
 
         vec![
             Line::from(Span::styled(
@@ -930,7 +1404,15 @@ fn draw_refetch_confirm(frame: &mut Frame, app: &App, area: Rect, theme: &Theme)
             Line::from(""),
             Line::from(format!("Scope: {}", plan.scope_label)),
             Line::from(format!("Range: {} → {}", plan.start, plan.end)),
-            Line::from(format!("Days: {}", plan.days)),
+            Line::from(format!(
+                "Days: {} never fetched, {} stale",
+                plan.unfetched_days, plan.stale_days
+            )),
+            Line::from(format!(
+                "Skipped: {} fresh, {} weekend/future",
+                plan.fresh_days, plan.skipped_days
+            )),
+            Line::from(format!("Calls: {}", batches.join(", "))),
             Line::from(""),
             Line::from(Span::styled(warning, Style::default().fg(theme.error))),
             Line::from(Span::styled(
@@ -953,7 +1435,217 @@ fn draw_refetch_confirm(frame: &mut Frame, app: &App, area: Rect, theme: &Theme)
 
     let paragraph = Paragraph::new(lines)
         .alignment(Alignment::Left)
-        .block(panel_block("Confirm Refetch", theme))
+        .block(panel_block("Confirm Refetch", theme))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, block);
+}
+
+fn draw_gap_report(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
+    let block = centered_rect(70, 70, area);
+    frame.render_widget(Clear, block);
+    let Some(report) = app.gap_report() else {
+        return;
+    };
+
+    let thresholds = report.thresholds;
+    let mut summary = vec![
+        Span::styled(
+            format!(
+                "{} → {}",
+                report.start.format("%Y-%m-%d"),
+                report.end.format("%Y-%m-%d")
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            "   under {}% of expected · breaks over {} min",
+            thresholds.low_day_percent, thresholds.min_gap_minutes
+        )),
+    ];
+    if report.unfetched_days > 0 {
+        summary.push(Span::styled(
+            format!(" · {} day(s) not fetched", report.unfetched_days),
+            Style::default().fg(theme.error),
+        ));
+    }
+
+    let items: Vec<ListItem> = report
+        .items
+        .iter()
+        .map(|item| {
+            let date = Span::styled(
+                format!("{}  ", item.date.format("%a %Y-%m-%d")),
+                theme.muted_style(),
+            );
+            let detail = match item.kind {
+                GapKind::LowDay {
+                    tracked_seconds,
+                    expected_hours,
+                } => Span::styled(
+                    format!(
+                        "{:.2}h of {:.2}h tracked",
+                        hours_from_seconds(tracked_seconds),
+                        expected_hours
+                    ),
+                    Style::default().fg(theme.error),
+                ),
+                GapKind::IntraDay { from, to } => Span::raw(format!(
+                    "break {} – {} ({} min)",
+                    from.format("%H:%M"),
+                    to.format("%H:%M"),
+                    (to - from).num_minutes()
+                )),
+            };
+            ListItem::new(Line::from(vec![date, detail]))
+        })
+        .collect();
+    let empty = items.is_empty();
+
+    let inner = panel_block("Gaps", theme).inner(block);
+    frame.render_widget(panel_block("Gaps", theme), block);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+    frame.render_widget(
+        Paragraph::new(Line::from(summary)).wrap(Wrap { trim: true }),
+        layout[0],
+    );
+    if empty {
+        frame.render_widget(
+            Paragraph::new("No low days or long breaks in this range.")
+                .style(Style::default().fg(theme.success)),
+            layout[1],
+        );
+    } else {
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(theme.accent)
+                    .fg(theme.accent_contrast())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▍ ");
+        frame.render_stateful_widget(list, layout[1], &mut app.gap_state);
+    }
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "Up/Down select • Enter open day • Esc close",
+            theme.muted_style(),
+        )),
+        layout[2],
+    );
+}
+
+fn draw_issues(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
+    let block = centered_rect(76, 70, area);
+    frame.render_widget(Clear, block);
+
+    let max_hours = app.max_entry_hours();
+    let items: Vec<ListItem> = app
+        .entry_issues()
+        .iter()
+        .map(|issue| {
+            let detail = match &issue.kind {
+                IssueKind::Overlap { seconds, .. } => {
+                    format!("overlaps an earlier entry by {} min", seconds / 60)
+                }
+                IssueKind::TooLong { seconds } => format!(
+                    "runs {:.2}h, over the {:.2}h limit",
+                    hours_from_seconds(*seconds),
+                    max_hours
+                ),
+                IssueKind::NoProject => "no project".to_string(),
+                IssueKind::NoDescription => "no description".to_string(),
+                IssueKind::AbsenceDay { absence } => format!("tracked on a {absence} day"),
+            };
+            let description = issue
+                .description
+                .as_deref()
+                .map(str::trim)
+                .filter(|description| !description.is_empty())
+                .unwrap_or("(no description)");
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{}  ", issue.start.format("%a %Y-%m-%d %H:%M")),
+                    theme.muted_style(),
+                ),
+                Span::raw(format!("{description}  ")),
+                Span::styled(detail, Style::default().fg(theme.error)),
+            ]))
+        })
+        .collect();
+
+    let inner = panel_block("Entry Issues", theme).inner(block);
+    frame.render_widget(panel_block("Entry Issues", theme), block);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.accent)
+                .fg(theme.accent_contrast())
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▍ ");
+    frame.render_stateful_widget(list, layout[0], &mut app.issue_state);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "Up/Down select • Enter open day • Esc close",
+            theme.muted_style(),
+        )),
+        layout[1],
+    );
+}
+
+fn draw_absence_input(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let block = centered_rect(60, 36, area);
+    frame.render_widget(Clear, block);
+
+    let day = app
+        .absence_input_day()
+        .map(|day| day.format("%a %Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let mut lines = vec![
+        Line::from(format!("Absence on {day}")),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Amount: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                app.absence_input_value().to_string(),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "0.5 or 50% for part of the day, 2h for explicit hours.",
+            theme.muted_style(),
+        )),
+        Line::from(Span::styled(
+            "Leave empty for the whole day.",
+            theme.muted_style(),
+        )),
+        Line::from(""),
+        Line::from("Enter apply • Esc cancel"),
+    ];
+
+    if let Some(status) = &app.status {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            status.clone(),
+            Style::default().fg(theme.error),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Left)
+        .block(panel_block("Partial Day", theme))
         .wrap(Wrap { trim: true });
     frame.render_widget(paragraph, block);
 }
@@ -1001,14 +1693,6 @@ fn draw_toast(frame: &mut Frame, area: Rect, message: &str, is_error: bool, them
     frame.render_widget(paragraph, rect);
 }
 
-fn hours_from_seconds(seconds: i64) -> f64 {
-    seconds as f64 / 3600.0
-}
-
-fn normalize_delta(value: f64) -> f64 {
-    if value.abs() < 0.005 { 0.0 } else { value }
-}
-
 fn delta_style(value: f64, theme: &Theme) -> Style {
     if value > 0.0 {
         Style::default().fg(theme.success)
@@ -1019,25 +1703,6 @@ fn delta_style(value: f64, theme: &Theme) -> Style {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct SpecialDayHours {
-    vacation_target_hours: f64,
-    vacation_credit_hours: f64,
-    sick_target_hours: f64,
-    sick_credit_hours: f64,
-}
-
-impl SpecialDayHours {
-    fn from_app(app: &App) -> Self {
-        Self {
-            vacation_target_hours: app.vacation_day_target_hours(),
-            vacation_credit_hours: app.vacation_day_credit_hours(),
-            sick_target_hours: app.sick_day_target_hours(),
-            sick_credit_hours: app.sick_day_credit_hours(),
-        }
-    }
-}
-
 fn format_special_day_label(
     label: &str,
     target_hours: f64,
@@ -1057,204 +1722,54 @@ fn format_special_day_label(
 }
 
 fn special_day_suffix(app: &App, day: NaiveDate) -> String {
-    let special_day_hours = SpecialDayHours::from_app(app);
-    if app.is_sick_day(day) {
-        return format_special_day_label(
-            "sick",
-            special_day_hours.sick_target_hours,
-            special_day_hours.sick_credit_hours,
-            true,
-        );
-    }
-    if app.is_vacation_day(day) {
+    let holiday_hours = app.holiday_hours();
+    if let Some(name) = app.holiday_name(day) {
         return format_special_day_label(
-            "vacation",
-            special_day_hours.vacation_target_hours,
-            special_day_hours.vacation_credit_hours,
+            &format!("holiday: {name}"),
+            holiday_hours.target_hours,
+            holiday_hours.credit_hours,
             true,
         );
     }
-    String::new()
-}
-
-fn special_day_credit_hours(
-    day: NaiveDate,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-) -> f64 {
-    if credit_sick_days_as_worked && sick_days.contains(&day) {
-        return special_day_hours.sick_credit_hours;
-    }
-    if credit_vacation_days_as_worked && vacation_days.contains(&day) {
-        return special_day_hours.vacation_credit_hours;
+    match app.special_days().absence(day) {
+        Some(absence) => absence_label(app, day, &absence.name.to_lowercase(), absence, true),
+        None => String::new(),
     }
-    0.0
 }
 
-fn effective_hours_for_day(
+/// Labels an absence day, scaling the hours for partial days.
+fn absence_label(
+    app: &App,
     day: NaiveDate,
-    worked_hours: f64,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-) -> f64 {
-    let credit = special_day_credit_hours(
-        day,
-        credit_vacation_days_as_worked,
-        credit_sick_days_as_worked,
-        vacation_days,
-        sick_days,
-        special_day_hours,
-    );
-    worked_hours.max(credit)
-}
-
-fn period_effective_hours(
-    period: &PeriodRollup,
-    daily: &[DailyTotal],
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-) -> f64 {
-    daily
-        .iter()
-        .filter(|day| day.date >= period.start && day.date <= period.end)
-        .map(|day| {
-            effective_hours_for_day(
-                day.date,
-                hours_from_seconds(day.seconds),
-                credit_vacation_days_as_worked,
-                credit_sick_days_as_worked,
-                vacation_days,
-                sick_days,
-                special_day_hours,
-            )
-        })
-        .sum()
-}
-
-fn period_target_hours(
-    period: &PeriodRollup,
-    target_hours: f64,
-    include_weekends: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-) -> (f64, usize) {
-    let mut days = 0usize;
-    let mut total = 0.0;
-    let mut current = period.start;
-    while current <= period.end {
-        let target = target_hours_for_day(
-            current,
-            target_hours,
-            include_weekends,
-            vacation_days,
-            sick_days,
-            special_day_hours,
-        );
-        if target > 0.0 {
-            days += 1;
+    label: &str,
+    absence: &AbsenceType,
+    bracketed: bool,
+) -> String {
+    match app.special_days().partial_days.get(&day) {
+        Some(amount) => format_special_day_label(
+            &format!("{label} ({})", amount.label()),
+            amount.target_hours(app.work_schedule().hours_for(day), absence.target_hours),
+            amount.credit_hours(absence.credit_hours),
+            bracketed,
+        ),
+        None => {
+            format_special_day_label(label, absence.target_hours, absence.credit_hours, bracketed)
         }
-        total += target;
-        current = current.succ_opt().unwrap_or(current + Duration::days(1));
-    }
-    (total, days)
-}
-
-fn period_overtime_hours(
-    period: &PeriodRollup,
-    daily: &[DailyTotal],
-    target_hours: f64,
-    include_weekends: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
-) -> f64 {
-    let worked_total = period_effective_hours(
-        period,
-        daily,
-        credit_vacation_days_as_worked,
-        credit_sick_days_as_worked,
-        vacation_days,
-        sick_days,
-        special_day_hours,
-    );
-    let (target_total, _) = period_target_hours(
-        period,
-        target_hours,
-        include_weekends,
-        vacation_days,
-        sick_days,
-        special_day_hours,
-    );
-    normalize_delta(worked_total - target_total)
-}
-
-fn period_worked_totals_until(
-    period: &PeriodRollup,
-    daily: &[DailyTotal],
-    active_end: NaiveDate,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-) -> (f64, usize) {
-    let cutoff = period.end.min(active_end);
-    if cutoff < period.start {
-        return (0.0, 0);
     }
-
-    daily
-        .iter()
-        .filter(|day| day.date >= period.start && day.date <= cutoff)
-        .fold((0.0f64, 0usize), |(worked_hours, worked_days), day| {
-            let effective = effective_hours_for_day(
-                day.date,
-                hours_from_seconds(day.seconds),
-                credit_vacation_days_as_worked,
-                credit_sick_days_as_worked,
-                vacation_days,
-                sick_days,
-                special_day_hours,
-            );
-            if effective > 0.0 {
-                (worked_hours + effective, worked_days + 1)
-            } else {
-                (worked_hours, worked_days)
-            }
-        })
 }
 
-fn target_hours_for_day(
-    day: NaiveDate,
-    target_hours: f64,
-    include_weekends: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-) -> f64 {
-    if sick_days.contains(&day) {
-        return special_day_hours.sick_target_hours;
-    }
-    if vacation_days.contains(&day) {
-        return special_day_hours.vacation_target_hours;
-    }
-    if include_weekends || day.weekday().number_from_monday() <= 5 {
-        target_hours
-    } else {
-        0.0
+fn rounding_delta_span(raw_seconds: i64, rounded_seconds: i64, theme: &Theme) -> Span<'static> {
+    if raw_seconds == rounded_seconds {
+        return Span::raw("");
     }
+    Span::styled(
+        format!(
+            "  (raw {:.2}h, rounding {:+.2}h)",
+            hours_from_seconds(raw_seconds),
+            hours_from_seconds(rounded_seconds - raw_seconds)
+        ),
+        theme.muted_style(),
+    )
 }
 
 struct CalendarRender {
@@ -1268,32 +1783,26 @@ fn build_calendar_lines(
     selected_date: Option<NaiveDate>,
     focus: RollupFocus,
     rollup_view: RollupView,
-    target_hours: f64,
+    balance: &BalanceContext,
     include_weekends: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
+    special_days: &SpecialDays,
     fetched_days: &HashSet<NaiveDate>,
     week_start: WeekStart,
+    today: NaiveDate,
     theme: &Theme,
 ) -> CalendarRender {
-    if matches!(rollup_view, RollupView::Yearly) {
+    if matches!(rollup_view, RollupView::Quarterly | RollupView::Yearly) {
         build_yearly_calendar_lines(
             daily,
             period,
             selected_date,
             focus,
-            target_hours,
+            balance,
             include_weekends,
-            vacation_days,
-            sick_days,
-            special_day_hours,
-            credit_vacation_days_as_worked,
-            credit_sick_days_as_worked,
+            special_days,
             fetched_days,
             week_start,
+            today,
             theme,
         )
     } else {
@@ -1302,15 +1811,12 @@ fn build_calendar_lines(
             period,
             selected_date,
             focus,
-            target_hours,
+            balance,
             include_weekends,
-            vacation_days,
-            sick_days,
-            special_day_hours,
-            credit_vacation_days_as_worked,
-            credit_sick_days_as_worked,
+            special_days,
             fetched_days,
             week_start,
+            today,
             theme,
         )
     }
@@ -1321,15 +1827,12 @@ fn build_period_calendar_grid_lines(
     period: &PeriodRollup,
     selected_date: Option<NaiveDate>,
     focus: RollupFocus,
-    target_hours: f64,
+    balance: &BalanceContext,
     include_weekends: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
+    special_days: &SpecialDays,
     fetched_days: &HashSet<NaiveDate>,
     week_start: WeekStart,
+    today: NaiveDate,
     theme: &Theme,
 ) -> CalendarRender {
     let mut map: HashMap<NaiveDate, i64> = HashMap::new();
@@ -1393,7 +1896,6 @@ fn build_period_calendar_grid_lines(
     }
 
     let border_style = theme.border_style();
-    let today = Local::now().date_naive();
     let horizontal = "─".repeat(cell_width);
     let mut lines = Vec::new();
     let mut selected_line = None;
@@ -1449,26 +1951,8 @@ fn build_period_calendar_grid_lines(
                 Some(date) => {
                     let is_fetched = fetched_days.contains(date);
                     let worked_hours = hours_from_seconds(*map.get(date).unwrap_or(&0));
-                    let hours = effective_hours_for_day(
-                        *date,
-                        worked_hours,
-                        credit_vacation_days_as_worked,
-                        credit_sick_days_as_worked,
-                        vacation_days,
-                        sick_days,
-                        special_day_hours,
-                    );
-                    let delta = normalize_delta(
-                        hours
-                            - target_hours_for_day(
-                                *date,
-                                target_hours,
-                                include_weekends,
-                                vacation_days,
-                                sick_days,
-                                special_day_hours,
-                            ),
-                    );
+                    let hours = balance.effective_hours(*date, worked_hours);
+                    let delta = balance.overtime_hours(*date, worked_hours);
                     let mut style = if is_fetched {
                         delta_style(delta, theme).add_modifier(Modifier::BOLD)
                     } else {
@@ -1489,23 +1973,40 @@ fn build_period_calendar_grid_lines(
                             }
                         }
                     }
+                    if special_days.holidays.contains(*date) {
+                        style = style.add_modifier(Modifier::ITALIC);
+                    }
                     if *date == today {
                         style = style.add_modifier(Modifier::UNDERLINED);
                     }
-                    let day_label = if !is_fetched && sick_days.contains(date) {
-                        format!("{:02}S?", date.day())
-                    } else if !is_fetched && vacation_days.contains(date) {
-                        format!("{:02}V?", date.day())
-                    } else if !is_fetched {
-                        format!("{:02}?", date.day())
-                    } else if sick_days.contains(date) {
-                        format!("{:02}S", date.day())
-                    } else if vacation_days.contains(date) {
-                        format!("{:02}V", date.day())
+                    let absence = special_days.absence(*date);
+                    // Partial-day absences use a lowercase glyph.
+                    let marker = if special_days.holidays.contains(*date) {
+                        "H".to_string()
+                    } else if let Some(absence) = absence {
+                        if special_days.partial_days.contains_key(date) {
+                            absence.glyph.to_ascii_lowercase().to_string()
+                        } else {
+                            absence.glyph.to_string()
+                        }
                     } else {
-                        format!("{:02}", date.day())
+                        String::new()
+                    };
+                    let pending = if is_fetched { "" } else { "?" };
+                    let day_label = format!("{:02}{marker}{pending}", date.day());
+                    let is_selected =
+                        Some(*date) == selected_date && matches!(focus, RollupFocus::Days);
+                    let day_style = match absence
+                        .and_then(|absence| absence.color.as_deref())
+                        .and_then(parse_hex_color)
+                    {
+                        Some(color) if !is_selected => style.fg(color),
+                        _ => style,
                     };
-                    day_values.push((format!("{:^width$}", day_label, width = cell_width), style));
+                    day_values.push((
+                        format!("{:^width$}", day_label, width = cell_width),
+                        day_style,
+                    ));
                     let hours_label = if is_fetched {
                         format!("{:.2}h", hours)
                     } else {
@@ -1551,15 +2052,12 @@ fn build_yearly_calendar_lines(
     period: &PeriodRollup,
     selected_date: Option<NaiveDate>,
     focus: RollupFocus,
-    target_hours: f64,
+    balance: &BalanceContext,
     include_weekends: bool,
-    vacation_days: &HashSet<NaiveDate>,
-    sick_days: &HashSet<NaiveDate>,
-    special_day_hours: SpecialDayHours,
-    credit_vacation_days_as_worked: bool,
-    credit_sick_days_as_worked: bool,
+    special_days: &SpecialDays,
     fetched_days: &HashSet<NaiveDate>,
     week_start: WeekStart,
+    today: NaiveDate,
     theme: &Theme,
 ) -> CalendarRender {
     let mut all_lines = Vec::new();
@@ -1594,21 +2092,20 @@ fn build_yearly_calendar_lines(
                 end: clamped_end,
                 days: 0,
                 seconds: 0,
+                raw_seconds: 0,
+                projects: Breakdown::new(),
             };
             let month_render = build_period_calendar_grid_lines(
                 daily,
                 &month_period,
                 selected_date,
                 focus,
-                target_hours,
+                balance,
                 include_weekends,
-                vacation_days,
-                sick_days,
-                special_day_hours,
-                credit_vacation_days_as_worked,
-                credit_sick_days_as_worked,
+                special_days,
                 fetched_days,
                 week_start,
+                today,
                 theme,
             );
             let start_line = all_lines.len();
@@ -1658,6 +2155,12 @@ fn calendar_scroll_offset(
     offset as u16
 }
 
+fn month_name(month: u32) -> String {
+    NaiveDate::from_ymd_opt(2000, month, 1)
+        .map(|date| date.format("%B").to_string())
+        .unwrap_or_default()
+}
+
 fn month_end(date: NaiveDate) -> NaiveDate {
     let (year, month) = if date.month() == 12 {
         (date.year() + 1, 1)
@@ -1733,6 +2236,14 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Cell::from(Span::styled("y", key_style)),
             Cell::from("Yesterday"),
         ]),
+        Row::new(vec![
+            Cell::from(Span::styled("w / W", key_style)),
+            Cell::from("This week / last week"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("e / E", key_style)),
+            Cell::from("This month / last month"),
+        ]),
         Row::new(vec![
             Cell::from(Span::styled("d", key_style)),
             Cell::from("Set date range"),
@@ -1747,7 +2258,15 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         ]),
         Row::new(vec![
             Cell::from(Span::styled("[ / ]", key_style)),
-            Cell::from("Previous / next active date range"),
+            Cell::from("Previous / next range (week or month when snapped)"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("f", key_style)),
+            Cell::from("Gap report for the range; Enter jumps to the day"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("i", key_style)),
+            Cell::from("List entry issues: overlaps, long timers, missing project/description"),
         ]),
         Row::new(vec![
             Cell::from(Span::styled("Tab", key_style)),
@@ -1766,6 +2285,14 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Cell::from(Span::styled("w / m / y", key_style)),
             Cell::from("Weekly / monthly / yearly view"),
         ]),
+        Row::new(vec![
+            Cell::from(Span::styled("Shift+Q", key_style)),
+            Cell::from("Quarterly view (fiscal quarters)"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("b", key_style)),
+            Cell::from("Billing cycle view; again for the next cycle"),
+        ]),
         Row::new(vec![
             Cell::from(Span::styled("[ / ]", key_style)),
             Cell::from("Previous / next rollup year"),
@@ -1774,6 +2301,22 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Cell::from(Span::styled("z", key_style)),
             Cell::from("Toggle weekends in rollups"),
         ]),
+        Row::new(vec![
+            Cell::from(Span::styled("p", key_style)),
+            Cell::from("Show top projects or top clients in rollups"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("c", key_style)),
+            Cell::from("Compare the period with earlier ones and show the trend"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("v", key_style)),
+            Cell::from("Work patterns: hour × weekday heatmap, start/end, untracked streaks"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("f", key_style)),
+            Cell::from("Gap report for the selected period"),
+        ]),
         Row::new(vec![
             Cell::from(Span::styled("k", key_style)),
             Cell::from("Toggle vacation for selected day"),
@@ -1782,6 +2325,14 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Cell::from(Span::styled("j", key_style)),
             Cell::from("Toggle sick day for selected day"),
         ]),
+        Row::new(vec![
+            Cell::from(Span::styled("t", key_style)),
+            Cell::from("Cycle absence type for selected day"),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("a", key_style)),
+            Cell::from("Set partial-day absence for selected day"),
+        ]),
         Row::new(vec![
             Cell::from(Span::styled("Shift+R", key_style)),
             Cell::from("Refetch selected day/week/month/year"),
@@ -1922,6 +2473,7 @@ fn draw_settings(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         .iter()
         .copied()
         .map(|item| {
+            let absence_label;
             let (label, value, disabled) = match item {
                 SettingsItem::Theme => {
                     let theme = app.settings_theme_display();
@@ -1936,45 +2488,95 @@ fn draw_settings(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
                     let value = if is_editing && editing_item == Some(SettingsItem::TargetHours) {
                         app.settings_input_value().to_string()
                     } else {
-                        format!("{:.2}h", app.target_hours)
+                        let today = app.timezone.today();
+                        match app.work_schedule().change_label(today) {
+                            Some(schedule) => {
+                                format!("{:.2}h (schedule: {schedule})", app.target_hours)
+                            }
+                            None => format!("{:.2}h", app.target_hours),
+                        }
                     };
                     ("Target hours", value, false)
                 }
-                SettingsItem::VacationTargetHours => {
+                SettingsItem::Timezone => {
+                    let value = if is_editing && editing_item == Some(SettingsItem::Timezone) {
+                        app.settings_input_value().to_string()
+                    } else {
+                        format!("{} ({})", app.timezone.label(), app.timezone_source())
+                    };
+                    ("Timezone", value, false)
+                }
+                SettingsItem::AbsenceTargetHours(_) | SettingsItem::AbsenceCreditHours(_) => {
+                    let value = if is_editing && editing_item == Some(item) {
+                        app.settings_input_value().to_string()
+                    } else {
+                        format!("{:.2}h", app.settings_absence_hours_display(item))
+                    };
+                    absence_label = app.absence_hours_label(item);
+                    (absence_label.as_str(), value, false)
+                }
+                SettingsItem::HolidayCountry => {
+                    let value = app
+                        .settings_holiday_country_display()
+                        .unwrap_or_else(|| "Off".to_string());
+                    ("Public holidays", value, false)
+                }
+                SettingsItem::HolidayImport => {
+                    let value = if is_editing && editing_item == Some(SettingsItem::HolidayImport) {
+                        app.settings_input_value().to_string()
+                    } else {
+                        format!("{} imported", app.imported_holiday_count())
+                    };
+                    ("Import holidays (.ics)", value, false)
+                }
+                SettingsItem::MaxEntryHours => {
+                    let value = if is_editing && editing_item == Some(SettingsItem::MaxEntryHours) {
+                        app.settings_input_value().to_string()
+                    } else if app.max_entry_hours() > 0.0 {
+                        format!("{:.2}h", app.max_entry_hours())
+                    } else {
+                        "Off".to_string()
+                    };
+                    ("Max entry duration", value, false)
+                }
+                SettingsItem::HolidayTargetHours => {
                     let value =
-                        if is_editing && editing_item == Some(SettingsItem::VacationTargetHours) {
+                        if is_editing && editing_item == Some(SettingsItem::HolidayTargetHours) {
                             app.settings_input_value().to_string()
                         } else {
-                            format!("{:.2}h", app.settings_vacation_target_hours_display())
+                            format!("{:.2}h", app.settings_holiday_target_hours_display())
                         };
-                    ("Vacation target hours", value, false)
+                    ("Holiday target hours", value, false)
                 }
-                SettingsItem::VacationCreditHours => {
+                SettingsItem::HolidayCreditHours => {
                     let value =
-                        if is_editing && editing_item == Some(SettingsItem::VacationCreditHours) {
+                        if is_editing && editing_item == Some(SettingsItem::HolidayCreditHours) {
                             app.settings_input_value().to_string()
                         } else {
-                            format!("{:.2}h", app.settings_vacation_credit_hours_display())
+                            format!("{:.2}h", app.settings_holiday_credit_hours_display())
                         };
-                    ("Vacation credit hours", value, false)
+                    ("Holiday credit hours", value, false)
                 }
-                SettingsItem::SickTargetHours => {
-                    let value = if is_editing && editing_item == Some(SettingsItem::SickTargetHours)
-                    {
+                SettingsItem::TimeBankStart => {
+                    let value = if is_editing && editing_item == Some(SettingsItem::TimeBankStart) {
                         app.settings_input_value().to_string()
                     } else {
-                        format!("{:.2}h", app.settings_sick_target_hours_display())
+                        app.settings_time_bank_start_display()
                     };
-                    ("Sick target hours", value, false)
+                    ("Time bank start", value, false)
                 }
-                SettingsItem::SickCreditHours => {
-                    let value = if is_editing && editing_item == Some(SettingsItem::SickCreditHours)
+                SettingsItem::TimeBankOpening => {
+                    let value = if is_editing && editing_item == Some(SettingsItem::TimeBankOpening)
                     {
                         app.settings_input_value().to_string()
                     } else {
-                        format!("{:.2}h", app.settings_sick_credit_hours_display())
+                        format!("{:+.2}h", app.settings_time_bank_opening_display())
                     };
-                    ("Sick credit hours", value, false)
+                    (
+                        "Time bank opening balance",
+                        value,
+                        app.time_bank().is_none(),
+                    )
                 }
                 SettingsItem::RollupsIncludeWeekends => {
                     let enabled = app.settings_rollups_include_weekends_display();
@@ -1988,6 +2590,18 @@ fn draw_settings(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
                         false,
                     )
                 }
+                SettingsItem::RememberWeekendToggle => {
+                    let enabled = app.settings_remember_weekend_toggle_display();
+                    (
+                        "Save z as weekend default",
+                        if enabled {
+                            "On".to_string()
+                        } else {
+                            "Off".to_string()
+                        },
+                        false,
+                    )
+                }
                 SettingsItem::RollupsWeekStart => {
                     let value = match app.settings_rollups_week_start_display() {
                         WeekStart::Monday => "Monday",
@@ -1995,10 +2609,75 @@ fn draw_settings(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
                     };
                     ("Week start", value.to_string(), false)
                 }
-                SettingsItem::CreditVacationDays => {
-                    let enabled = app.settings_credit_vacation_days_display();
+                SettingsItem::FiscalYearStart => (
+                    "Fiscal year start",
+                    month_name(app.settings_fiscal_year_start_display()),
+                    false,
+                ),
+                SettingsItem::GapLowDayPercent => {
+                    let value =
+                        if is_editing && editing_item == Some(SettingsItem::GapLowDayPercent) {
+                            app.settings_input_value().to_string()
+                        } else {
+                            match app.gap_thresholds().low_day_percent {
+                                0 => "Off".to_string(),
+                                percent => format!("{percent}%"),
+                            }
+                        };
+                    ("Gap report: low day under", value, false)
+                }
+                SettingsItem::GapMinMinutes => {
+                    let value = if is_editing && editing_item == Some(SettingsItem::GapMinMinutes) {
+                        app.settings_input_value().to_string()
+                    } else {
+                        match app.gap_thresholds().min_gap_minutes {
+                            0 => "Off".to_string(),
+                            minutes => format!("{minutes} min"),
+                        }
+                    };
+                    ("Gap report: breaks over", value, false)
+                }
+                SettingsItem::CreditAbsenceDays(index) => {
+                    let enabled = app.settings_credit_absence_days_display(index);
+                    absence_label = format!(
+                        "Credit {} as worked",
+                        app.absence_type(index).name.to_lowercase()
+                    );
+                    (
+                        absence_label.as_str(),
+                        if enabled {
+                            "On".to_string()
+                        } else {
+                            "Off".to_string()
+                        },
+                        false,
+                    )
+                }
+                SettingsItem::Notifications => {
+                    let enabled = app.settings_notifications_display();
+                    (
+                        "Desktop notifications",
+                        if enabled {
+                            "On".to_string()
+                        } else {
+                            "Off".to_string()
+                        },
+                        false,
+                    )
+                }
+                SettingsItem::EndOfDaySummary => {
+                    let value = if is_editing && editing_item == Some(SettingsItem::EndOfDaySummary)
+                    {
+                        app.settings_input_value().to_string()
+                    } else {
+                        app.settings_end_of_day_summary_display()
+                    };
+                    ("End-of-day summary", value, !app.notifications_enabled())
+                }
+                SettingsItem::RestoreDateRange => {
+                    let enabled = app.settings_restore_date_range_display();
                     (
-                        "Credit vacation as worked",
+                        "Reopen last date range",
                         if enabled {
                             "On".to_string()
                         } else {
@@ -2007,10 +2686,10 @@ fn draw_settings(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
                         false,
                     )
                 }
-                SettingsItem::CreditSickDays => {
-                    let enabled = app.settings_credit_sick_days_display();
+                SettingsItem::SplitAtMidnight => {
+                    let enabled = app.settings_split_at_midnight_display();
                     (
-                        "Credit sick as worked",
+                        "Split entries at midnight",
                         if enabled {
                             "On".to_string()
                         } else {
@@ -2031,14 +2710,15 @@ fn draw_settings(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
                 }
                 SettingsItem::RoundingMode => {
                     let value = rounding_cfg
-                        .map(|cfg| match cfg.mode {
-                            crate::rounding::RoundingMode::Closest => "closest".to_string(),
-                            crate::rounding::RoundingMode::Up => "up".to_string(),
-                            crate::rounding::RoundingMode::Down => "down".to_string(),
-                        })
+                        .map(|cfg| cfg.mode.label().to_string())
                         .unwrap_or_else(|| "—".to_string());
                     ("Rounding mode", value, rounding_cfg.is_none())
                 }
+                SettingsItem::RoundingScope => (
+                    "Rounding scope",
+                    app.settings_rounding_scope_display().label().to_string(),
+                    false,
+                ),
                 SettingsItem::TogglToken => {
                     let value = if is_editing && editing_item == Some(SettingsItem::TogglToken) {
                         app.settings_input_value().to_string()
@@ -2091,19 +2771,53 @@ fn draw_settings(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
         }
         SettingsFocus::Edit => match editing_item {
             Some(SettingsItem::TargetHours)
-            | Some(SettingsItem::VacationTargetHours)
-            | Some(SettingsItem::VacationCreditHours)
-            | Some(SettingsItem::SickTargetHours)
-            | Some(SettingsItem::SickCreditHours)
+            | Some(SettingsItem::AbsenceTargetHours(_))
+            | Some(SettingsItem::AbsenceCreditHours(_))
+            | Some(SettingsItem::HolidayTargetHours)
+            | Some(SettingsItem::HolidayCreditHours)
             | Some(SettingsItem::TogglToken) => "Enter save • Esc cancel",
+            Some(SettingsItem::HolidayCountry) => {
+                "Up/Down: Off, BE, DE, FR, GB, NL, US • Enter save • Esc cancel"
+            }
+            Some(SettingsItem::TimeBankStart) => {
+                "YYYY-MM-DD (empty disables the time bank) • Enter save • Esc cancel"
+            }
+            Some(SettingsItem::TimeBankOpening) => {
+                "Signed hours carried in, e.g. -4.5 or 12 • Enter save • Esc cancel"
+            }
+            Some(SettingsItem::HolidayImport) => {
+                "Type a path to an .ics file (empty clears imports) • Enter import • Esc cancel"
+            }
+            Some(SettingsItem::Timezone) => {
+                "Type an IANA name (empty follows Toggl profile) • Enter save • Esc cancel"
+            }
+            Some(SettingsItem::MaxEntryHours) => {
+                "Hours, up to 24 (0 turns the check off) • Enter save • Esc cancel"
+            }
+            Some(SettingsItem::GapLowDayPercent) => {
+                "Percent of expected hours, 0-100 (0 turns it off) • Enter save • Esc cancel"
+            }
+            Some(SettingsItem::GapMinMinutes) => {
+                "Minutes between entries (0 turns it off) • Enter save • Esc cancel"
+            }
             Some(SettingsItem::Theme)
             | Some(SettingsItem::RollupsIncludeWeekends)
+            | Some(SettingsItem::RememberWeekendToggle)
+            | Some(SettingsItem::RestoreDateRange)
             | Some(SettingsItem::RollupsWeekStart)
-            | Some(SettingsItem::CreditVacationDays)
-            | Some(SettingsItem::CreditSickDays) => "Up/Down change • Enter save • Esc cancel",
+            | Some(SettingsItem::FiscalYearStart)
+            | Some(SettingsItem::CreditAbsenceDays(_))
+            | Some(SettingsItem::SplitAtMidnight)
+            | Some(SettingsItem::Notifications) => "Up/Down change • Enter save • Esc cancel",
+            Some(SettingsItem::EndOfDaySummary) => {
+                "HH:MM local time (empty turns it off) • Enter save • Esc cancel"
+            }
             Some(SettingsItem::TimeRoundingToggle)
             | Some(SettingsItem::RoundingIncrement)
             | Some(SettingsItem::RoundingMode) => "Up/Down change • Enter save • Esc cancel",
+            Some(SettingsItem::RoundingScope) => {
+                "Up/Down: entry, line, day or period • Enter save • Esc cancel"
+            }
             Some(SettingsItem::ThemeStudio) => "Enter open • Esc cancel",
             None => "Esc cancel",
         },
@@ -2330,179 +3044,3 @@ fn parse_hex_color(value: &str) -> Option<Color> {
     let blue = u8::from_str_radix(&value[4..6], 16).ok()?;
     Some(Color::Rgb(red, green, blue))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
-        NaiveDate::from_ymd_opt(year, month, day).unwrap()
-    }
-
-    fn daily_total(year: i32, month: u32, day: u32, hours: f64) -> DailyTotal {
-        DailyTotal {
-            date: date(year, month, day),
-            seconds: (hours * 3600.0).round() as i64,
-        }
-    }
-
-    fn march_2026_period() -> PeriodRollup {
-        PeriodRollup {
-            label: "Mar 2026".to_string(),
-            start: date(2026, 3, 1),
-            end: date(2026, 3, 31),
-            days: 31,
-            seconds: 0,
-        }
-    }
-
-    fn approx_eq(actual: f64, expected: f64) {
-        assert!(
-            (actual - expected).abs() < 0.000_1,
-            "expected {expected:.4}, got {actual:.4}"
-        );
-    }
-
-    #[test]
-    fn sick_day_credit_and_target_hours_are_calculated_separately() {
-        let period = march_2026_period();
-        let daily = vec![
-            daily_total(2026, 3, 2, 0.0),
-            daily_total(2026, 3, 3, 0.0),
-            daily_total(2026, 3, 4, 0.0),
-            daily_total(2026, 3, 5, 0.0),
-            daily_total(2026, 3, 6, 10.5),
-            daily_total(2026, 3, 9, 14.0),
-            daily_total(2026, 3, 10, 10.5),
-            daily_total(2026, 3, 11, 9.5),
-            daily_total(2026, 3, 12, 8.0),
-            daily_total(2026, 3, 13, 10.0),
-            daily_total(2026, 3, 16, 11.75),
-            daily_total(2026, 3, 17, 6.0),
-            daily_total(2026, 3, 18, 8.75),
-            daily_total(2026, 3, 19, 10.25),
-            daily_total(2026, 3, 20, 10.25),
-            daily_total(2026, 3, 23, 9.25),
-            daily_total(2026, 3, 24, 7.0),
-            daily_total(2026, 3, 25, 7.0),
-            daily_total(2026, 3, 26, 11.5),
-            daily_total(2026, 3, 27, 4.0),
-        ];
-        let vacation_days = HashSet::new();
-        let sick_days = HashSet::from([
-            date(2026, 3, 2),
-            date(2026, 3, 3),
-            date(2026, 3, 4),
-            date(2026, 3, 5),
-        ]);
-        let special_day_hours = SpecialDayHours {
-            vacation_target_hours: 8.0,
-            vacation_credit_hours: 7.6,
-            sick_target_hours: 8.0,
-            sick_credit_hours: 7.6,
-        };
-
-        let total = period_effective_hours(
-            &period,
-            &daily,
-            false,
-            true,
-            &vacation_days,
-            &sick_days,
-            special_day_hours,
-        );
-        let (target, target_days) = period_target_hours(
-            &period,
-            8.0,
-            false,
-            &vacation_days,
-            &sick_days,
-            special_day_hours,
-        );
-        let delta = normalize_delta(total - target);
-        let overtime = period_overtime_hours(
-            &period,
-            &daily,
-            8.0,
-            false,
-            &vacation_days,
-            &sick_days,
-            special_day_hours,
-            false,
-            true,
-        );
-
-        approx_eq(total, 178.65);
-        approx_eq(target, 176.0);
-        approx_eq(delta, 2.65);
-        approx_eq(overtime, 2.65);
-        assert_eq!(target_days, 22);
-    }
-
-    #[test]
-    fn vacation_day_credit_and_target_hours_are_calculated_separately() {
-        let period = PeriodRollup {
-            label: "Apr 2026".to_string(),
-            start: date(2026, 4, 6),
-            end: date(2026, 4, 10),
-            days: 5,
-            seconds: 0,
-        };
-        let daily = vec![
-            daily_total(2026, 4, 6, 0.0),
-            daily_total(2026, 4, 7, 8.0),
-            daily_total(2026, 4, 8, 8.0),
-            daily_total(2026, 4, 9, 8.0),
-            daily_total(2026, 4, 10, 8.0),
-        ];
-        let vacation_days = HashSet::from([date(2026, 4, 6)]);
-        let sick_days = HashSet::new();
-        let special_day_hours = SpecialDayHours {
-            vacation_target_hours: 8.25,
-            vacation_credit_hours: 6.75,
-            sick_target_hours: 8.0,
-            sick_credit_hours: 7.5,
-        };
-
-        let total = period_effective_hours(
-            &period,
-            &daily,
-            true,
-            false,
-            &vacation_days,
-            &sick_days,
-            special_day_hours,
-        );
-        let (target, target_days) = period_target_hours(
-            &period,
-            8.0,
-            false,
-            &vacation_days,
-            &sick_days,
-            special_day_hours,
-        );
-        let day_credit = effective_hours_for_day(
-            date(2026, 4, 6),
-            0.0,
-            true,
-            false,
-            &vacation_days,
-            &sick_days,
-            special_day_hours,
-        );
-        let day_target = target_hours_for_day(
-            date(2026, 4, 6),
-            8.0,
-            false,
-            &vacation_days,
-            &sick_days,
-            special_day_hours,
-        );
-
-        approx_eq(total, 38.75);
-        approx_eq(target, 40.25);
-        approx_eq(day_credit, 6.75);
-        approx_eq(day_target, 8.25);
-        assert_eq!(target_days, 5);
-    }
-}
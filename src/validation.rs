@@ -0,0 +1,227 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+
+use crate::dates::Timezone;
+use crate::models::TimeEntry;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    /// Runs at the same time as the earlier entry `other_id`.
+    Overlap {
+        other_id: u64,
+        seconds: i64,
+    },
+    TooLong {
+        seconds: i64,
+    },
+    NoProject,
+    NoDescription,
+    /// Tracked on a day marked with this absence.
+    AbsenceDay {
+        absence: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryIssue {
+    pub entry_id: u64,
+    pub start: NaiveDateTime,
+    pub description: Option<String>,
+    pub kind: IssueKind,
+}
+
+/// Checks entries for overlaps, overly long durations, missing projects or
+/// descriptions, and time tracked on absence days. Running entries are
+/// treated as stopping at `now`. A `max_seconds` of zero skips the duration
+/// check. Issues are ordered by entry start in local time.
+pub fn validate_entries(
+    entries: &[TimeEntry],
+    timezone: Timezone,
+    now: DateTime<FixedOffset>,
+    max_seconds: i64,
+    absence_on: impl Fn(NaiveDate) -> Option<String>,
+) -> Vec<EntryIssue> {
+    let mut bounded: Vec<(&TimeEntry, DateTime<FixedOffset>, DateTime<FixedOffset>)> = entries
+        .iter()
+        .filter_map(|entry| {
+            let start = DateTime::parse_from_rfc3339(&entry.start).ok()?;
+            let stop = match entry.stop.as_deref() {
+                Some(stop) => DateTime::parse_from_rfc3339(stop).ok()?,
+                None => now,
+            };
+            Some((entry, start, stop.max(start)))
+        })
+        .collect();
+    bounded.sort_by(|left, right| left.1.cmp(&right.1).then(left.0.id.cmp(&right.0.id)));
+
+    let mut issues = Vec::new();
+    // The entry reaching furthest so far, to catch overlaps with long entries.
+    let mut latest: Option<(u64, DateTime<FixedOffset>)> = None;
+    for (entry, start, stop) in bounded {
+        let local_start = timezone.convert(&start).naive_local();
+        let mut push = |kind| {
+            issues.push(EntryIssue {
+                entry_id: entry.id,
+                start: local_start,
+                description: entry.description.clone(),
+                kind,
+            })
+        };
+
+        if let Some((other_id, other_stop)) = latest
+            && start < other_stop
+        {
+            push(IssueKind::Overlap {
+                other_id,
+                seconds: (other_stop.min(stop) - start).num_seconds(),
+            });
+        }
+        let seconds = (stop - start).num_seconds();
+        if max_seconds > 0 && seconds > max_seconds {
+            push(IssueKind::TooLong { seconds });
+        }
+        if entry.project_id.is_none() {
+            push(IssueKind::NoProject);
+        }
+        if entry
+            .description
+            .as_deref()
+            .is_none_or(|description| description.trim().is_empty())
+        {
+            push(IssueKind::NoDescription);
+        }
+        if let Some(absence) = absence_on(local_start.date()) {
+            push(IssueKind::AbsenceDay { absence });
+        }
+
+        if latest.is_none_or(|(_, other_stop)| stop > other_stop) {
+            latest = Some((entry.id, stop));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UTC: Timezone = Timezone::Named(chrono_tz::UTC);
+
+    fn entry(id: u64, start: &str, stop: &str) -> TimeEntry {
+        TimeEntry {
+            id,
+            description: Some("Work".to_string()),
+            duration: 0,
+            start: start.to_string(),
+            stop: Some(stop.to_string()),
+            project_id: Some(7),
+        }
+    }
+
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z").unwrap()
+    }
+
+    fn kinds(issues: &[EntryIssue]) -> Vec<(u64, IssueKind)> {
+        issues
+            .iter()
+            .map(|issue| (issue.entry_id, issue.kind.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn overlaps_are_reported_against_the_longest_earlier_entry() {
+        let entries = vec![
+            entry(3, "2026-03-02T11:00:00Z", "2026-03-02T11:30:00Z"),
+            entry(1, "2026-03-02T08:00:00Z", "2026-03-02T12:00:00Z"),
+            entry(2, "2026-03-02T09:00:00Z", "2026-03-02T10:00:00Z"),
+            entry(4, "2026-03-02T12:00:00Z", "2026-03-02T13:00:00Z"),
+        ];
+        let issues = validate_entries(&entries, UTC, now(), 0, |_| None);
+
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                (
+                    2,
+                    IssueKind::Overlap {
+                        other_id: 1,
+                        seconds: 3600
+                    }
+                ),
+                (
+                    3,
+                    IssueKind::Overlap {
+                        other_id: 1,
+                        seconds: 1800
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_long_and_incomplete_entries() {
+        let mut untitled = entry(2, "2026-03-03T08:00:00Z", "2026-03-03T09:00:00Z");
+        untitled.description = Some("  ".to_string());
+        untitled.project_id = None;
+        let entries = vec![
+            entry(1, "2026-03-02T08:00:00Z", "2026-03-02T22:00:00Z"),
+            untitled,
+        ];
+        let issues = validate_entries(&entries, UTC, now(), 10 * 3600, |_| None);
+
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                (1, IssueKind::TooLong { seconds: 14 * 3600 }),
+                (2, IssueKind::NoProject),
+                (2, IssueKind::NoDescription),
+            ]
+        );
+    }
+
+    #[test]
+    fn running_entries_are_checked_up_to_now() {
+        let mut running = entry(1, "2026-03-09T22:00:00Z", "2026-03-09T22:00:00Z");
+        running.stop = None;
+        let entries = vec![
+            running,
+            entry(2, "2026-03-10T09:00:00Z", "2026-03-10T10:00:00Z"),
+        ];
+        let issues = validate_entries(&entries, UTC, now(), 10 * 3600, |_| None);
+
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                (1, IssueKind::TooLong { seconds: 14 * 3600 }),
+                (
+                    2,
+                    IssueKind::Overlap {
+                        other_id: 1,
+                        seconds: 3600
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_entries_on_absence_days_in_local_time() {
+        let berlin = Timezone::Named(chrono_tz::Europe::Berlin);
+        let entries = vec![entry(1, "2026-03-02T23:30:00Z", "2026-03-03T00:30:00Z")];
+        let vacation = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let issues = validate_entries(&entries, berlin, now(), 0, |day| {
+            (day == vacation).then(|| "Vacation".to_string())
+        });
+
+        assert_eq!(
+            kinds(&issues),
+            vec![(
+                1,
+                IssueKind::AbsenceDay {
+                    absence: "Vacation".to_string()
+                }
+            )]
+        );
+    }
+}